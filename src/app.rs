@@ -4,6 +4,22 @@ use crate::models::{AppView, ProcessingProgress, ProcessingState, ProcessorConfi
 use crate::processor::ProcessorManager;
 use std::sync::mpsc;
 
+/// 从开始按钮启动的处理任务
+pub struct MainRun {
+    pub processor_id: String,
+    pub processor_name: String,
+    pub input_path: std::path::PathBuf,
+    pub output_dir: std::path::PathBuf,
+    pub task: crate::tasks::TaskHandle<crate::error::Result<crate::models::ProcessingResult>>,
+}
+
+/// 后台对比两个文件版本的结果
+pub struct FileComparison {
+    pub old: std::path::PathBuf,
+    pub new: std::path::PathBuf,
+    pub comparison: crate::engine::SheetComparison,
+}
+
 pub struct IntegratedPowerApp {
     // 管理器
     pub config_manager: ConfigManager,
@@ -28,21 +44,92 @@ pub struct IntegratedPowerApp {
     // 日志查看器
     pub log_viewer: crate::ui::LogViewer,
 
-    // 进度接收通道
-    pub progress_rx: Option<mpsc::Receiver<ProcessingProgress>>,
+    // 后台任务（结果与进度经通道回传，更新时请求重绘）
+    pub task_manager: crate::tasks::TaskManager,
+
+    // 从最近文件中选中、待选择处理器后预填的输入路径
+    pub pending_input_path: Option<std::path::PathBuf>,
+
+    // 错误报告记录器
+    pub error_logger: crate::logger::ErrorLogger,
+
+    // 最近完成的文件（进度视图显示）
+    pub recent_outcomes: crate::models::RecentOutcomes,
+
+    // 未能加载中文字体时的一次性提示
+    pub font_hint: Option<String>,
+
+    // 后台历史记录导入任务及其进度（已解析, 总数）
+    pub history_import: Option<crate::history::HistoryImportTask>,
+    pub history_import_progress: (usize, usize),
+
+    // 导出历史记录时是否匿名化路径
+    pub history_export_anonymize: bool,
+
+    // 当前窗口标题（仅在变化时发送视口命令）
+    window_title: String,
+
+    // 勾选参与对比运行的处理器 ID，以及运行结果接收通道
+    pub compare_selection: Vec<String>,
+    pub multi_run_task: Option<crate::tasks::TaskHandle<crate::error::Result<crate::processor::MultiRunResult>>>,
+
+    // 开始按钮启动的处理任务（运行期间 processing_state 为 Processing）
+    pub main_run: Option<MainRun>,
+
+    // 最近一次识别的统计信息（状态栏显示识别成功率）
+    pub last_identification_stats: Option<crate::models::IdentificationStatistics>,
+
+    // 识别预览、样本验证和文件对比使用的识别配置（按设置中的配置来源缓存）
+    pub recognition_profile: Option<crate::engine::LoadedProfile>,
+
+    // 正在后台进行的文件版本对比
+    pub file_compare_task: Option<crate::tasks::TaskHandle<crate::error::Result<FileComparison>>>,
+
+    // 处理任务队列（先进先出，依次执行）
+    pub job_queue: crate::processor::JobQueue,
+
+    // 最近完成任务的摘要对话框
+    pub processing_summary: Option<crate::ui::summary_dialog::ProcessingSummary>,
+
+    // 自动保存计时器（防抖）
+    auto_save: crate::config::AutoSaveTimer,
+    // 计时器已记录的处理器配置修改计数，以及已写盘的修改计数
+    auto_save_seen_revision: u64,
+    saved_config_revision: u64,
+    // 计时器已记录的界面状态（选中的处理器、窗口尺寸和位置）
+    auto_save_ui_state: (Option<String>, Option<crate::config::WindowGeometry>),
 }
 
+/// 未能加载中文字体时显示的提示
+const FONT_MISSING_HINT: &str =
+    "未能加载中文字体，界面中的中文可能显示为方块。请在「设置」中指定自定义字体路径后重启应用。";
+
 impl IntegratedPowerApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        // 配置中文字体支持
-        Self::setup_custom_fonts(&cc.egui_ctx);
-
         // 加载配置
         let config_manager = ConfigManager::load().unwrap_or_else(|e| {
             eprintln!("加载配置失败: {}, 使用默认配置", e);
             ConfigManager::default()
         });
 
+        // 按配置决定日志是否输出到控制台
+        crate::logger::LOGGER.set_console_output(config_manager.get_config().console_log);
+
+        // 检查运行时资源，便于从日志面板排查打包问题
+        crate::resources::log_resource_check();
+
+        // 配置中文字体支持
+        let font_loaded = Self::setup_custom_fonts(
+            &cc.egui_ctx,
+            config_manager.get_config().custom_font_path.as_deref(),
+        );
+        let font_hint = if font_loaded {
+            None
+        } else {
+            crate::log_warning!("⚠ 未找到可用的中文字体，将使用默认字体，中文可能无法正常显示");
+            Some(FONT_MISSING_HINT.to_string())
+        };
+
         // 加载历史记录
         let max_entries = config_manager.get_config().max_history_entries;
         let history_manager = HistoryManager::load(max_entries).unwrap_or_else(|e| {
@@ -50,12 +137,29 @@ impl IntegratedPowerApp {
             HistoryManager::new(max_entries)
         });
 
-        // 创建处理器管理器
-        let processor_manager = ProcessorManager::new();
+        // 创建处理器管理器（应用用户的处理器注册表）
+        let processor_manager = ProcessorManager::load();
 
         // 加载处理器配置
         let processor_configs = Self::load_processor_configs().unwrap_or_default();
 
+        let auto_save = crate::config::AutoSaveTimer::new(std::time::Duration::from_secs(
+            config_manager.get_config().auto_save_interval_secs,
+        ));
+
+        // 恢复上次选中的处理器（已被移除或禁用的处理器不恢复）
+        let selected_processor = config_manager
+            .get_config()
+            .last_selected_processor
+            .clone()
+            .filter(|id| processor_manager.has_processor(id));
+        let auto_save_ui_state = (selected_processor.clone(), config_manager.get_config().window_geometry);
+
+        // 后台任务运行时
+        let task_manager = crate::tasks::TaskManager::new()
+            .expect("无法创建后台任务运行时")
+            .with_repaint(cc.egui_ctx.clone());
+
         // 记录应用启动
         crate::log_info!("IntegratedPower 应用启动");
 
@@ -64,63 +168,96 @@ impl IntegratedPowerApp {
             processor_manager,
             history_manager,
             current_view: AppView::Home,
-            selected_processor: None,
+            selected_processor,
             processing_state: ProcessingState::Idle,
             progress: ProcessingProgress::default(),
             search_query: String::new(),
             error_message: None,
             processor_configs,
             log_viewer: crate::ui::LogViewer::default(),
-            progress_rx: None,
+            task_manager,
+            pending_input_path: None,
+            error_logger: crate::logger::ErrorLogger::new(),
+            recent_outcomes: crate::models::RecentOutcomes::default(),
+            font_hint,
+            history_import: None,
+            history_import_progress: (0, 0),
+            history_export_anonymize: true,
+            window_title: crate::models::APP_TITLE.to_string(),
+            compare_selection: Vec::new(),
+            multi_run_task: None,
+            main_run: None,
+            last_identification_stats: None,
+            recognition_profile: None,
+            file_compare_task: None,
+            job_queue: crate::processor::JobQueue::new(),
+            processing_summary: None,
+            auto_save,
+            auto_save_seen_revision: 0,
+            saved_config_revision: 0,
+            auto_save_ui_state,
         }
     }
 
-    fn setup_custom_fonts(ctx: &egui::Context) {
+    // 按顺序读取候选字体文件，返回第一个成功读取的字体数据
+    fn load_first_font(candidates: &[std::path::PathBuf]) -> Option<Vec<u8>> {
+        candidates.iter().find_map(|p| std::fs::read(p).ok())
+    }
+
+    // 配置中文字体，返回是否成功加载到中文字体
+    fn setup_custom_fonts(ctx: &egui::Context, custom_path: Option<&std::path::Path>) -> bool {
         let mut fonts = egui::FontDefinitions::default();
 
-        // 1) 优先尝试加载项目资源字体（思源黑体）
+        // 0) 用户在设置中指定的字体优先
         let mut loaded_key: Option<String> = None;
-        let resource_candidates: Vec<std::path::PathBuf> = {
-            let mut v = Vec::new();
-            // 运行目录相对路径
-            v.push(std::path::PathBuf::from("resources/fonts/SourceHanSansSC-Regular.otf"));
-            // 可执行文件所在目录相对路径
-            if let Ok(exe) = std::env::current_exe() {
-                if let Some(dir) = exe.parent() {
-                    v.push(dir.join("resources/fonts/SourceHanSansSC-Regular.otf"));
+        if let Some(path) = custom_path {
+            match Self::load_first_font(&[path.to_path_buf()]) {
+                Some(font_data) => {
+                    let key = "custom_cn_font".to_owned();
+                    fonts.font_data.insert(key.clone(), egui::FontData::from_owned(font_data));
+                    loaded_key = Some(key);
+                }
+                None => {
+                    crate::log_warning!("无法读取自定义字体: {}", path.display());
                 }
             }
-            v
-        };
+        }
+
+        // 1) 尝试加载项目资源字体（思源黑体）
+        // 依次为运行目录、可执行文件所在目录下的相对路径
+        let resource_candidates = crate::resources::candidate_paths(
+            crate::resources::FONT_RESOURCE,
+            &crate::resources::resource_roots(),
+        );
 
-        for p in resource_candidates {
-            if let Ok(font_data) = std::fs::read(&p) {
+        if loaded_key.is_none() {
+            if let Some(font_data) = Self::load_first_font(&resource_candidates) {
                 let key = "source_han_sans_sc".to_owned();
                 fonts.font_data.insert(key.clone(), egui::FontData::from_owned(font_data));
                 loaded_key = Some(key);
-                break;
             }
         }
 
         // 2) 若资源字体不可用，在 Windows 上回退到常见系统中文字体
         #[cfg(target_os = "windows")]
         if loaded_key.is_none() {
-            let candidates = [
+            let candidates: Vec<std::path::PathBuf> = [
                 "C:\\Windows\\Fonts\\simhei.ttf",
                 "C:\\Windows\\Fonts\\msyh.ttf",
                 "C:\\Windows\\Fonts\\msyh.ttc",
-            ];
-            for path in candidates.iter() {
-                if let Ok(font_data) = std::fs::read(path) {
-                    let key = "win_cn_font".to_owned();
-                    fonts.font_data.insert(key.clone(), egui::FontData::from_owned(font_data));
-                    loaded_key = Some(key);
-                    break;
-                }
+            ]
+            .iter()
+            .map(std::path::PathBuf::from)
+            .collect();
+            if let Some(font_data) = Self::load_first_font(&candidates) {
+                let key = "win_cn_font".to_owned();
+                fonts.font_data.insert(key.clone(), egui::FontData::from_owned(font_data));
+                loaded_key = Some(key);
             }
         }
 
         // 3) 若已加载到中文字体，则注入到比例与等宽字体族，保证 .monospace 等场景不缺字
+        let loaded = loaded_key.is_some();
         if let Some(key) = loaded_key {
             fonts
                 .families
@@ -135,6 +272,7 @@ impl IntegratedPowerApp {
         }
 
         ctx.set_fonts(fonts);
+        loaded
     }
 
     fn apply_theme(&self, ctx: &egui::Context) {
@@ -168,12 +306,25 @@ impl IntegratedPowerApp {
         config.theme = new_theme;
 
         if let Err(e) = self.config_manager.update_config(config) {
-            self.error_message = Some(format!("保存主题设置失败: {}", e));
+            self.report_error("保存主题设置失败", e);
+        }
+    }
+
+    // 显示错误提示并记录到日志
+    pub fn report_error(&mut self, context: &str, error: impl std::fmt::Display) {
+        self.error_message = Some(format!("{}: {}", context, error));
+        self.error_logger.log_error(context, error);
+    }
+
+    // 记录最近使用的输入文件
+    pub fn remember_recent_input(&mut self, path: std::path::PathBuf) {
+        if let Err(e) = self.config_manager.add_recent_input_file(path) {
+            self.error_logger.log_warning(format!("保存最近使用的文件失败: {}", e));
         }
     }
 
     // 保存处理器配置
-    pub fn save_processor_configs(&self) -> anyhow::Result<()> {
+    pub fn save_processor_configs(&mut self) -> anyhow::Result<()> {
         let config_path = Self::get_processor_configs_path()?;
         
         if let Some(parent) = config_path.parent() {
@@ -182,6 +333,7 @@ impl IntegratedPowerApp {
         
         let json = serde_json::to_string_pretty(&self.processor_configs)?;
         std::fs::write(&config_path, json)?;
+        self.saved_config_revision = self.processor_configs.revision();
         
         Ok(())
     }
@@ -209,12 +361,320 @@ impl IntegratedPowerApp {
         Ok(app_config_dir.join("processor_configs.json"))
     }
 
-    fn poll_processing_tasks(&mut self, ctx: &egui::Context) {
-        if let Some(rx) = &self.progress_rx {
-            if let Ok(progress) = rx.try_recv() {
-                self.progress = progress;
-                ctx.request_repaint();
+    fn poll_processing_tasks(&mut self) {
+        for (_, progress) in self.task_manager.drain_progress() {
+            if let Some(outcome) = progress.completed.clone() {
+                self.recent_outcomes.push(outcome);
             }
+            self.progress = progress;
+        }
+    }
+
+    /// 是否有处理任务正在进行（主任务、对比运行或队列任务），此时不能开始新的处理
+    pub fn is_busy(&self) -> bool {
+        Self::busy(&self.processing_state, self.multi_run_task.is_some(), &self.job_queue)
+    }
+
+    /// [`is_busy`](Self::is_busy) 的判断条件
+    pub fn busy(
+        processing_state: &ProcessingState,
+        multi_run_running: bool,
+        job_queue: &crate::processor::JobQueue,
+    ) -> bool {
+        processing_state.is_processing() || multi_run_running || job_queue.is_running()
+    }
+
+    fn poll_main_run(&mut self) {
+        let Some(run) = &self.main_run else {
+            return;
+        };
+        let outcome = match run.task.try_recv() {
+            Ok(outcome) => outcome,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err(crate::error::AppError::processing_error("处理任务意外结束")),
+        };
+        let Some(run) = self.main_run.take() else {
+            return;
+        };
+        if self.current_view == AppView::Processing {
+            self.current_view = AppView::Home;
+        }
+
+        match outcome {
+            Ok(result) => {
+                if result.is_empty_input() {
+                    let message = format!("输入目录中没有 Excel 文件: {}", run.input_path.display());
+                    self.error_logger.log_warning(message.clone());
+                    self.error_message = Some(message);
+                } else {
+                    self.processing_summary = Some(crate::ui::summary_dialog::ProcessingSummary::from_result(
+                        run.processor_name.clone(),
+                        &result,
+                        &run.output_dir,
+                    ));
+                }
+                if let Some(reason) = &result.aborted {
+                    self.error_logger.log_warning(reason.clone());
+                }
+                let entry = crate::history::HistoryEntry::new(
+                    run.processor_id,
+                    run.processor_name,
+                    run.input_path,
+                    run.output_dir,
+                    result.clone(),
+                );
+                if let Err(e) = self.history_manager.add_entry(entry) {
+                    self.error_logger.log_warning(format!("保存历史记录失败: {}", e));
+                }
+                self.processing_state = ProcessingState::Completed(result);
+            }
+            Err(e) => {
+                self.report_error("处理失败", e.user_message());
+                self.processing_state = ProcessingState::Error(e.user_message());
+            }
+        }
+    }
+
+    // 文件对比完成后把差异写入日志，有单元格变化时询问是否另存差异工作簿
+    fn poll_file_compare(&mut self) {
+        let Some(task) = &self.file_compare_task else {
+            return;
+        };
+        let outcome = match task.try_recv() {
+            Ok(outcome) => outcome,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.file_compare_task = None;
+                return;
+            }
+        };
+        self.file_compare_task = None;
+
+        let FileComparison { old, new, comparison } = match outcome {
+            Ok(result) => result,
+            Err(e) => {
+                crate::log_error!("对比文件失败: {}", e);
+                return;
+            }
+        };
+        crate::log_info!("对比 {} -> {}", old.display(), new.display());
+        comparison.log();
+
+        if comparison.cell_changes.is_empty() {
+            return;
+        }
+        if let Some(output) = rfd::FileDialog::new()
+            .set_title("保存差异工作簿（可取消）")
+            .add_filter("Excel 文件", &["xlsx"])
+            .set_file_name("差异.xlsx")
+            .save_file()
+        {
+            match crate::engine::write_diff_workbook(&new, &comparison, &output) {
+                Ok(()) => crate::log_info!("差异工作簿已保存: {}", output.display()),
+                Err(e) => crate::log_error!("保存差异工作簿失败: {}", e),
+            }
+        }
+    }
+
+    fn poll_multi_run(&mut self) {
+        let Some(task) = &self.multi_run_task else {
+            return;
+        };
+        let outcome = match task.try_recv() {
+            Ok(outcome) => outcome,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.multi_run_task = None;
+                return;
+            }
+        };
+        self.multi_run_task = None;
+
+        match outcome {
+            Ok(multi) => {
+                for entry in multi.history_entries() {
+                    if let Err(e) = self.history_manager.add_entry(entry) {
+                        self.error_logger.log_warning(format!("保存历史记录失败: {}", e));
+                    }
+                }
+            }
+            Err(e) => self.report_error("对比运行失败", e.user_message()),
+        }
+    }
+
+    fn poll_job_queue(&mut self, ctx: &egui::Context) {
+        // 主任务进行中时队列暂停，避免两个任务同时处理
+        self.job_queue
+            .set_held(self.processing_state.is_processing() || self.multi_run_task.is_some());
+        if self.job_queue.is_idle() {
+            return;
+        }
+
+        // 队列任务的进度保存在队列中，不覆盖主任务的进度
+        let poll = self.job_queue.poll();
+        if let Some(outcome) = poll.progress.and_then(|progress| progress.completed) {
+            self.recent_outcomes.push(outcome);
+        }
+        for entry in poll.finished {
+            if entry.result.is_empty_input() {
+                let message = format!("输入目录中没有 Excel 文件: {}", entry.input_dir.display());
+                self.error_logger.log_warning(message.clone());
+                self.error_message = Some(message);
+            }
+            if let Some(reason) = &entry.result.aborted {
+                self.error_logger.log_warning(reason.clone());
+            }
+            if !entry.result.is_empty_input() {
+                self.processing_summary = Some(crate::ui::summary_dialog::ProcessingSummary::from_result(
+                    entry.processor_name.clone(),
+                    &entry.result,
+                    &entry.output_dir,
+                ));
+            }
+            if let Err(e) = self.history_manager.add_entry(entry) {
+                self.error_logger.log_warning(format!("保存历史记录失败: {}", e));
+            }
+        }
+        for (label, e) in poll.failed {
+            self.report_error(&format!("队列任务失败: {}", label), e.user_message());
+        }
+
+        ctx.request_repaint_after(std::time::Duration::from_millis(200));
+    }
+
+    // 自动保存处理器配置和界面状态：最后一次修改后安静设定的秒数再写盘（防抖）
+    fn tick_auto_save(&mut self, ctx: &egui::Context) {
+        let now = std::time::Instant::now();
+        let interval = std::time::Duration::from_secs(self.config_manager.get_config().auto_save_interval_secs);
+        self.auto_save.set_interval(interval);
+
+        // 只比较修改计数和少量界面状态，不逐帧比较整个配置
+        let revision = self.processor_configs.revision();
+        if revision != self.auto_save_seen_revision {
+            self.auto_save_seen_revision = revision;
+            self.auto_save.mark_dirty(now);
+        }
+        let geometry = self.current_window_geometry(ctx);
+        if self.auto_save_ui_state.0 != self.selected_processor || self.auto_save_ui_state.1 != geometry {
+            self.auto_save_ui_state = (self.selected_processor.clone(), geometry);
+            self.auto_save.mark_dirty(now);
+        }
+
+        if self.auto_save.due(now) {
+            self.auto_save_now();
+        }
+
+        // 空闲时界面不会重绘，按剩余时间安排一次重绘，保证修改后无操作也能按时保存
+        if let Some(wait) = self.auto_save.remaining(now) {
+            ctx.request_repaint_after(wait);
+        }
+    }
+
+    // 写入有变化的处理器配置和界面状态
+    fn auto_save_now(&mut self) {
+        if self.processor_configs.revision() != self.saved_config_revision {
+            match self.save_processor_configs() {
+                Ok(()) => crate::log_debug!("已自动保存处理器配置"),
+                Err(e) => self.error_logger.log_warning(format!("自动保存处理器配置失败: {}", e)),
+            }
+        }
+
+        let (selected, geometry) = self.auto_save_ui_state.clone();
+        let current = self.config_manager.get_config();
+        let geometry = geometry.filter(|_| current.remember_window_geometry);
+        let geometry_changed = geometry.is_some() && geometry != current.window_geometry;
+        if current.last_selected_processor == selected && !geometry_changed {
+            return;
+        }
+
+        let mut config = current.clone();
+        config.last_selected_processor = selected;
+        if geometry_changed {
+            config.window_geometry = geometry;
+        }
+        match self.config_manager.update_config(config) {
+            Ok(()) => crate::log_debug!("已自动保存界面状态"),
+            Err(e) => self.error_logger.log_warning(format!("自动保存界面状态失败: {}", e)),
+        }
+    }
+
+    // 当前窗口尺寸和位置（已限制在合理范围内）
+    fn current_window_geometry(&self, ctx: &egui::Context) -> Option<crate::config::WindowGeometry> {
+        ctx.input(|i| {
+            let viewport = i.viewport();
+            crate::config::WindowGeometry::from_rects(viewport.inner_rect, viewport.outer_rect)
+        })
+        .map(|geometry| geometry.clamped())
+    }
+
+    fn poll_history_import(&mut self, ctx: &egui::Context) {
+        use crate::history::ImportEvent;
+
+        let Some(task) = &self.history_import else {
+            return;
+        };
+
+        let mut finished = None;
+        while let Some(event) = task.try_recv() {
+            match event {
+                ImportEvent::Progress { parsed, total } => {
+                    self.history_import_progress = (parsed, total);
+                }
+                other => {
+                    finished = Some(other);
+                    break;
+                }
+            }
+        }
+        ctx.request_repaint();
+
+        let Some(event) = finished else {
+            return;
+        };
+        self.history_import = None;
+
+        match event {
+            ImportEvent::Finished(Ok(entries)) => match self.history_manager.merge_imported(entries) {
+                Ok(count) => crate::log_info!("已导入 {} 条历史记录", count),
+                Err(e) => self.report_error("保存导入的历史记录失败", e.user_message()),
+            },
+            ImportEvent::Finished(Err(e)) => self.report_error("导入历史记录失败", e.user_message()),
+            ImportEvent::Cancelled => crate::log_info!("已取消导入历史记录"),
+            ImportEvent::Progress { .. } => {}
+        }
+    }
+
+    // 处理中在窗口标题显示进度，便于最小化时查看
+    fn update_window_title(&mut self, ctx: &egui::Context) {
+        let title = match self.job_queue.progress() {
+            Some(progress) if !self.processing_state.is_processing() => progress.window_title(true),
+            _ => self.progress.window_title(self.processing_state.is_processing()),
+        };
+        if title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = title;
+        }
+    }
+
+    // 关闭窗口时记录窗口尺寸和位置，下次启动时恢复
+    fn save_window_geometry_on_close(&mut self, ctx: &egui::Context) {
+        if !self.config_manager.get_config().remember_window_geometry {
+            return;
+        }
+        let Some(geometry) = ctx.input(|i| {
+            let viewport = i.viewport();
+            viewport
+                .close_requested()
+                .then(|| crate::config::WindowGeometry::from_rects(viewport.inner_rect, viewport.outer_rect))
+                .flatten()
+        }) else {
+            return;
+        };
+
+        let mut config = self.config_manager.get_config().clone();
+        config.window_geometry = Some(geometry.clamped());
+        if let Err(e) = self.config_manager.update_config(config) {
+            self.error_logger.log_warning(format!("保存窗口尺寸失败: {}", e));
         }
     }
 
@@ -238,6 +698,34 @@ impl IntegratedPowerApp {
             self.error_message = None;
         }
     }
+
+    // 显示一次性的字体提示
+    fn show_font_hint(&mut self, ctx: &egui::Context) {
+        let Some(hint) = self.font_hint.clone() else {
+            return;
+        };
+
+        let mut dismissed = false;
+        egui::Window::new("字体提示")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(hint);
+                ui.horizontal(|ui| {
+                    if ui.button("前往设置").clicked() {
+                        self.current_view = crate::models::AppView::Settings;
+                        dismissed = true;
+                    }
+                    if ui.button("关闭").clicked() {
+                        dismissed = true;
+                    }
+                });
+            });
+
+        if dismissed {
+            self.font_hint = None;
+        }
+    }
 }
 
 impl eframe::App for IntegratedPowerApp {
@@ -253,10 +741,51 @@ impl eframe::App for IntegratedPowerApp {
         // 显示错误对话框
         self.show_error(ctx);
 
+        // 显示字体提示
+        self.show_font_hint(ctx);
+
+        // 显示处理完成摘要
+        crate::ui::summary_dialog::render(self, ctx);
+
         // 显示日志查看器
         self.log_viewer.render(ctx);
 
         // 处理后台任务
-        self.poll_processing_tasks(ctx);
+        self.poll_processing_tasks();
+        self.poll_history_import(ctx);
+        self.poll_main_run();
+        self.poll_file_compare();
+        self.poll_multi_run();
+        self.poll_job_queue(ctx);
+        self.tick_auto_save(ctx);
+        self.update_window_title(ctx);
+        self.save_window_geometry_on_close(ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_first_font_none_when_no_candidate_readable() {
+        let dir = tempfile::tempdir().unwrap();
+        let candidates = vec![dir.path().join("missing-a.ttf"), dir.path().join("missing-b.otf")];
+
+        assert!(IntegratedPowerApp::load_first_font(&candidates).is_none());
+        assert!(IntegratedPowerApp::load_first_font(&[]).is_none());
+    }
+
+    #[test]
+    fn test_load_first_font_skips_missing_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let font = dir.path().join("font.ttf");
+        std::fs::write(&font, b"font-bytes").unwrap();
+
+        let candidates = vec![dir.path().join("missing.ttf"), font];
+        assert_eq!(
+            IntegratedPowerApp::load_first_font(&candidates).as_deref(),
+            Some(&b"font-bytes"[..])
+        );
     }
 }
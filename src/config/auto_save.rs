@@ -0,0 +1,131 @@
+// 定时自动保存
+use std::time::{Duration, Instant};
+
+/// 连续修改时最多推迟保存的倍数（相对于间隔）
+pub const MAX_DEFER_FACTOR: u32 = 4;
+
+/// 自动保存的防抖计时器
+///
+/// 每次修改时调用 [`mark_dirty`](Self::mark_dirty)，最后一次修改后安静 `interval` 才保存，
+/// 避免连续修改时频繁写盘；一直有修改时，距第一次未保存的修改超过
+/// `interval * MAX_DEFER_FACTOR` 也会保存，避免意外退出时丢失太多修改。
+/// 间隔为零表示关闭自动保存。
+#[derive(Debug, Clone)]
+pub struct AutoSaveTimer {
+    interval: Duration,
+    /// 第一次和最近一次未保存的修改时间
+    pending: Option<(Instant, Instant)>,
+}
+
+impl AutoSaveTimer {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            pending: None,
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.interval.is_zero()
+    }
+
+    /// 记录一次修改，重新开始计算安静时间
+    pub fn mark_dirty(&mut self, now: Instant) {
+        let first = self.pending.map_or(now, |(first, _)| first);
+        self.pending = Some((first, now));
+    }
+
+    /// 是否有尚未保存的修改
+    pub fn is_dirty(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// 到了保存时间时返回 true，并清除未保存标记
+    pub fn due(&mut self, now: Instant) -> bool {
+        if self.remaining(now) != Some(Duration::ZERO) {
+            return false;
+        }
+        self.pending = None;
+        true
+    }
+
+    /// 距下一次保存的剩余时间，关闭或没有未保存的修改时返回 None
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let (first, last) = self.pending?;
+        let quiet = self.interval.saturating_sub(now.saturating_duration_since(last));
+        let deferred = (self.interval * MAX_DEFER_FACTOR).saturating_sub(now.saturating_duration_since(first));
+        Some(quiet.min(deferred))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saves_after_quiet_interval() {
+        let start = Instant::now();
+        let mut timer = AutoSaveTimer::new(Duration::from_secs(30));
+
+        // 没有修改时不保存
+        assert!(!timer.due(start + Duration::from_secs(60)));
+        assert_eq!(timer.remaining(start), None);
+
+        timer.mark_dirty(start);
+        assert!(!timer.due(start + Duration::from_secs(29)));
+        assert_eq!(timer.remaining(start + Duration::from_secs(10)), Some(Duration::from_secs(20)));
+
+        // 再次修改后重新计算安静时间
+        timer.mark_dirty(start + Duration::from_secs(20));
+        assert!(!timer.due(start + Duration::from_secs(30)));
+        assert!(!timer.due(start + Duration::from_secs(49)));
+        assert!(timer.due(start + Duration::from_secs(50)));
+
+        // 保存后没有新的修改就不再保存
+        assert!(!timer.is_dirty());
+        assert!(!timer.due(start + Duration::from_secs(200)));
+    }
+
+    #[test]
+    fn test_continuous_changes_save_within_max_defer() {
+        let start = Instant::now();
+        let mut timer = AutoSaveTimer::new(Duration::from_secs(10));
+
+        // 每 5 秒修改一次，安静时间永远不足，到达最长推迟时间时仍会保存
+        let mut saved_at = None;
+        for step in 0..20u64 {
+            let now = start + Duration::from_secs(step * 5);
+            timer.mark_dirty(now);
+            if timer.due(now) {
+                saved_at = Some(step * 5);
+                break;
+            }
+        }
+        assert_eq!(saved_at, Some(40));
+    }
+
+    #[test]
+    fn test_zero_interval_disables_auto_save() {
+        let start = Instant::now();
+        let mut timer = AutoSaveTimer::new(Duration::ZERO);
+        timer.mark_dirty(start);
+        assert!(!timer.due(start + Duration::from_secs(3600)));
+        assert_eq!(timer.remaining(start), None);
+
+        // 开启后未保存的修改按新间隔保存
+        timer.set_interval(Duration::from_secs(5));
+        assert!(!timer.due(start + Duration::from_secs(4)));
+        assert!(timer.due(start + Duration::from_secs(5)));
+    }
+}
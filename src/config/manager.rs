@@ -1,5 +1,6 @@
 use crate::error::{AppError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -18,8 +19,66 @@ pub struct AppConfig {
     pub parallel_processing: bool,
     /// 最大并行任务数
     pub max_parallel_tasks: usize,
+    /// 是否按文件大小和内存预算自动降低并行数
+    #[serde(default)]
+    pub adaptive_parallelism: bool,
+    /// 自适应并行的内存预算（MB）
+    #[serde(default = "default_memory_budget_mb")]
+    pub memory_budget_mb: u64,
+    /// 输入目录中没有 Excel 文件时是否视为错误
+    #[serde(default)]
+    pub fail_on_empty_input: bool,
+    /// 写出 Excel 后是否重新打开校验
+    #[serde(default)]
+    pub verify_output: bool,
+    /// 最近使用的输入文件（最新的在前）
+    #[serde(default)]
+    pub recent_input_files: Vec<PathBuf>,
+    /// 结果覆盖输入文件前是否自动备份
+    #[serde(default = "default_true")]
+    pub backup_before_overwrite: bool,
+    /// 是否把日志同时输出到控制台（默认仅调试构建开启）
+    #[serde(default = "default_console_log")]
+    pub console_log: bool,
+    /// 自定义中文字体路径（优先于内置资源字体加载）
+    #[serde(default)]
+    pub custom_font_path: Option<PathBuf>,
+    /// 配置和界面状态的自动保存间隔（秒），0 表示关闭
+    #[serde(default = "default_auto_save_interval")]
+    pub auto_save_interval_secs: u64,
+    /// 是否记住窗口尺寸和位置
+    #[serde(default = "default_true")]
+    pub remember_window_geometry: bool,
+    /// 上次关闭时的窗口尺寸和位置
+    #[serde(default)]
+    pub window_geometry: Option<super::WindowGeometry>,
+    /// 上次选中的处理器，启动时恢复
+    #[serde(default)]
+    pub last_selected_processor: Option<String>,
+    /// 各处理器默认使用的识别配置（处理器 ID -> 内置配置名称或配置文件路径）
+    #[serde(default)]
+    pub processor_profiles: HashMap<String, String>,
 }
 
+fn default_true() -> bool {
+    true
+}
+
+fn default_console_log() -> bool {
+    cfg!(debug_assertions)
+}
+
+fn default_auto_save_interval() -> u64 {
+    30
+}
+
+fn default_memory_budget_mb() -> u64 {
+    2048
+}
+
+/// 最近使用的输入文件最多保留条数
+pub const MAX_RECENT_INPUT_FILES: usize = 8;
+
 /// 主题类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Theme {
@@ -40,11 +99,52 @@ impl Default for AppConfig {
             max_history_entries: 100,
             parallel_processing: true,
             max_parallel_tasks: num_cpus::get().max(2).min(8),
+            adaptive_parallelism: false,
+            memory_budget_mb: default_memory_budget_mb(),
+            fail_on_empty_input: false,
+            verify_output: false,
+            recent_input_files: Vec::new(),
+            backup_before_overwrite: true,
+            console_log: default_console_log(),
+            custom_font_path: None,
+            auto_save_interval_secs: default_auto_save_interval(),
+            remember_window_geometry: true,
+            window_geometry: None,
+            last_selected_processor: None,
+            processor_profiles: HashMap::new(),
         }
     }
 }
 
 impl AppConfig {
+    /// 记录最近使用的输入文件（去重，最新的在前，超出上限时丢弃最旧的）
+    pub fn push_recent_input_file(&mut self, path: PathBuf) {
+        self.recent_input_files.retain(|p| p != &path);
+        self.recent_input_files.insert(0, path);
+        self.recent_input_files.truncate(MAX_RECENT_INPUT_FILES);
+    }
+
+    /// 指定处理器的默认识别配置，未设置时为 None（使用内置配置）
+    pub fn profile_spec(&self, processor_id: &str) -> Option<&str> {
+        self.processor_profiles
+            .get(processor_id)
+            .map(|spec| spec.trim())
+            .filter(|spec| !spec.is_empty())
+    }
+
+    /// 启动时使用的窗口尺寸和位置（未开启记忆或没有记录时为默认值）
+    pub fn startup_window_geometry(&self) -> super::WindowGeometry {
+        self.window_geometry
+            .filter(|_| self.remember_window_geometry)
+            .unwrap_or_default()
+    }
+
+    /// 自适应并行开启时的内存预算（字节），关闭时为 None
+    pub fn memory_budget(&self) -> Option<u64> {
+        self.adaptive_parallelism
+            .then(|| self.memory_budget_mb.saturating_mul(1024 * 1024))
+    }
+
     /// 验证配置
     pub fn validate(&self) -> Result<()> {
         // 验证历史记录条目数
@@ -210,6 +310,12 @@ impl ConfigManager {
         self.save()
     }
 
+    /// 记录最近使用的输入文件
+    pub fn add_recent_input_file(&mut self, path: PathBuf) -> Result<()> {
+        self.config.push_recent_input_file(path);
+        self.save()
+    }
+
     /// 获取配置文件路径
     fn get_config_path() -> Result<PathBuf> {
         // 使用用户配置目录
@@ -279,4 +385,54 @@ mod tests {
         let deserialized: AppConfig = toml::from_str(&toml_str).unwrap();
         assert_eq!(deserialized.theme, Theme::Dark);
     }
+
+    #[test]
+    fn test_push_recent_input_file() {
+        let mut config = AppConfig::default();
+
+        config.push_recent_input_file(PathBuf::from("a.xlsx"));
+        config.push_recent_input_file(PathBuf::from("b.xlsx"));
+        config.push_recent_input_file(PathBuf::from("a.xlsx"));
+
+        // 去重并将最新的放在最前
+        assert_eq!(
+            config.recent_input_files,
+            vec![PathBuf::from("a.xlsx"), PathBuf::from("b.xlsx")]
+        );
+
+        for i in 0..20 {
+            config.push_recent_input_file(PathBuf::from(format!("{}.xlsx", i)));
+        }
+        assert_eq!(config.recent_input_files.len(), MAX_RECENT_INPUT_FILES);
+        assert_eq!(config.recent_input_files[0], PathBuf::from("19.xlsx"));
+    }
+
+    #[test]
+    fn test_processor_profiles_round_trip() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.profile_spec("cargo_analysis"), None);
+
+        config
+            .processor_profiles
+            .insert("cargo_analysis".to_string(), "/data/profiles/cargo.toml".to_string());
+        config.processor_profiles.insert("auxiliary_material".to_string(), "  ".to_string());
+
+        let deserialized: AppConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(deserialized.profile_spec("cargo_analysis"), Some("/data/profiles/cargo.toml"));
+        assert_eq!(deserialized.profile_spec("auxiliary_material"), None);
+    }
+
+    #[test]
+    fn test_recent_input_files_default_when_missing() {
+        // 旧版本配置文件中没有 recent_input_files 字段
+        let mut toml_str = toml::to_string(&AppConfig::default()).unwrap();
+        toml_str = toml_str
+            .lines()
+            .filter(|l| !l.starts_with("recent_input_files"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let config: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert!(config.recent_input_files.is_empty());
+    }
 }
@@ -1,5 +1,9 @@
 // Config 模块
+pub mod auto_save;
 pub mod manager;
+pub mod window_geometry;
 
 // 重新导出常用类型
+pub use auto_save::AutoSaveTimer;
 pub use manager::{AppConfig, ConfigManager, Theme};
+pub use window_geometry::WindowGeometry;
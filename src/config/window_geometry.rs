@@ -0,0 +1,122 @@
+// 窗口尺寸和位置
+//
+// 关闭窗口时记录当前尺寸和位置，下次启动时按记录的值创建窗口。
+use serde::{Deserialize, Serialize};
+
+/// 默认窗口尺寸
+pub const DEFAULT_WINDOW_SIZE: [f32; 2] = [1200.0, 800.0];
+/// 最小窗口尺寸
+pub const MIN_WINDOW_SIZE: [f32; 2] = [800.0, 600.0];
+
+/// 窗口几何信息（逻辑像素）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    /// 内容区宽度
+    pub width: f32,
+    /// 内容区高度
+    pub height: f32,
+    /// 窗口左上角位置，未记录时由系统决定
+    #[serde(default)]
+    pub position: Option<[f32; 2]>,
+}
+
+impl Default for WindowGeometry {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_WINDOW_SIZE[0],
+            height: DEFAULT_WINDOW_SIZE[1],
+            position: None,
+        }
+    }
+}
+
+impl WindowGeometry {
+    /// 由视口的内容区和外框矩形构建，没有内容区尺寸时返回 None
+    pub fn from_rects(inner: Option<egui::Rect>, outer: Option<egui::Rect>) -> Option<Self> {
+        let inner = inner?;
+        Some(Self {
+            width: inner.width(),
+            height: inner.height(),
+            position: outer.map(|rect| [rect.min.x, rect.min.y]),
+        })
+    }
+
+    /// 不小于最小尺寸，无效数值替换为默认值
+    pub fn clamped(self) -> Self {
+        let size = |value: f32, default: f32, min: f32| {
+            if value.is_finite() {
+                value.max(min)
+            } else {
+                default
+            }
+        };
+        Self {
+            width: size(self.width, DEFAULT_WINDOW_SIZE[0], MIN_WINDOW_SIZE[0]),
+            height: size(self.height, DEFAULT_WINDOW_SIZE[1], MIN_WINDOW_SIZE[1]),
+            position: self.position.filter(|[x, y]| x.is_finite() && y.is_finite()),
+        }
+    }
+
+    /// 应用到窗口构建器（先限制到最小尺寸）
+    pub fn apply(self, builder: egui::ViewportBuilder) -> egui::ViewportBuilder {
+        let geometry = self.clamped();
+        let builder = builder
+            .with_inner_size([geometry.width, geometry.height])
+            .with_min_inner_size(MIN_WINDOW_SIZE);
+        match geometry.position {
+            Some(position) => builder.with_position(position),
+            None => builder,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geometry_save_and_restore() {
+        let inner = egui::Rect::from_min_size(egui::pos2(108.0, 130.0), egui::vec2(1440.0, 900.0));
+        let outer = egui::Rect::from_min_size(egui::pos2(100.0, 100.0), egui::vec2(1456.0, 938.0));
+        let geometry = WindowGeometry::from_rects(Some(inner), Some(outer)).unwrap();
+        assert_eq!(
+            geometry,
+            WindowGeometry {
+                width: 1440.0,
+                height: 900.0,
+                position: Some([100.0, 100.0]),
+            }
+        );
+
+        let config = crate::config::AppConfig {
+            window_geometry: Some(geometry),
+            ..Default::default()
+        };
+        let restored: crate::config::AppConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(restored.window_geometry, Some(geometry));
+
+        assert_eq!(WindowGeometry::from_rects(None, Some(outer)), None);
+
+        // 关闭记忆后启动时使用默认尺寸
+        let disabled = crate::config::AppConfig {
+            remember_window_geometry: false,
+            ..restored.clone()
+        };
+        assert_eq!(restored.startup_window_geometry(), geometry);
+        assert_eq!(disabled.startup_window_geometry(), WindowGeometry::default());
+    }
+
+    #[test]
+    fn test_geometry_clamped_to_minimum() {
+        let geometry = WindowGeometry {
+            width: 300.0,
+            height: f32::NAN,
+            position: Some([f32::INFINITY, 0.0]),
+        }
+        .clamped();
+
+        assert_eq!(geometry.width, MIN_WINDOW_SIZE[0]);
+        assert_eq!(geometry.height, DEFAULT_WINDOW_SIZE[1]);
+        assert_eq!(geometry.position, None);
+    }
+}
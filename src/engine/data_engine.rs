@@ -1,17 +1,296 @@
+use crate::engine::output_name::{
+    format_output_name, validate_output_name_pattern, ConflictStrategy, OutputNameContext,
+};
 use crate::error::{AppError, Result};
-use crate::models::{ProcessingProgress, ProcessingStats};
+use crate::models::{CancelToken, OutputFormat, ProcessingError, ProcessingProgress, ProcessingResult};
 use polars::prelude::*;
 use std::path::Path;
 use tokio::task;
 
+/// 支持读取的文件扩展名（小写，不含点）
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["xlsx", "xls"];
+
+/// 批量汇总表的工作表名称
+pub const SUMMARY_SHEET_NAME: &str = "汇总";
+
+/// 批量汇总工作簿的文件名（写入输出目录）
+pub const SUMMARY_FILE_NAME: &str = "批量汇总.xlsx";
+
+/// 自动检测表头时最多检查的行数
+pub const HEADER_SCAN_ROWS: usize = 20;
+
+/// 表头单元格的最大字符数，更长的文本通常是标题或说明
+const MAX_HEADER_CELL_CHARS: usize = 30;
+
+/// 检测表头所在行（返回下标，从0开始）
+///
+/// 取前 [`HEADER_SCAN_ROWS`] 行中第一个过半单元格为非空短文本（非数字）的行，
+/// 跳过表格上方的标题、制表日期等说明行。没有符合条件的行时返回 0。
+pub fn detect_header_row(rows: &[Vec<String>]) -> usize {
+    rows.iter()
+        .take(HEADER_SCAN_ROWS)
+        .position(|row| {
+            let width = row.len();
+            let header_like = row
+                .iter()
+                .map(|cell| cell.trim())
+                .filter(|cell| {
+                    !cell.is_empty()
+                        && cell.chars().count() <= MAX_HEADER_CELL_CHARS
+                        && crate::engine::parse_localized_number(cell).is_none()
+                })
+                .count();
+            width > 0 && header_like * 2 > width
+        })
+        .unwrap_or(0)
+}
+
+/// 处理单个文件时的内存占用约为文件大小的倍数（xlsx 解压并载入 DataFrame 的经验值）
+pub const MEMORY_PER_FILE_FACTOR: u64 = 10;
+
+/// 按内存预算估算并行处理数
+///
+/// 假设最大的几个文件同时处理，取估算内存之和不超过 `memory_budget`（字节）的最多文件数，
+/// 结果不超过 `max_parallel` 且至少为 1。没有文件或预算为 0 时返回 `max_parallel`。
+pub fn estimate_concurrency(file_sizes: &[u64], memory_budget: u64, max_parallel: usize) -> usize {
+    let max_parallel = max_parallel.max(1);
+    if file_sizes.is_empty() || memory_budget == 0 {
+        return max_parallel;
+    }
+
+    let mut sizes = file_sizes.to_vec();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut used: u64 = 0;
+    let mut fits = 0;
+    for size in sizes.into_iter().take(max_parallel) {
+        used = used.saturating_add(size.saturating_mul(MEMORY_PER_FILE_FACTOR));
+        if used > memory_budget {
+            break;
+        }
+        fits += 1;
+    }
+    fits.clamp(1, max_parallel)
+}
+
+/// CSV 输出选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// 列分隔符
+    pub delimiter: u8,
+    /// 是否在文件开头写入 UTF-8 BOM（Excel 依赖 BOM 才能正确显示中文）
+    pub utf8_bom: bool,
+}
+
+impl CsvOptions {
+    /// 可选的列分隔符及其显示名称
+    pub const DELIMITERS: [(u8, &'static str); 3] = [(b',', "逗号 ,"), (b';', "分号 ;"), (b'\t', "制表符")];
+
+    pub fn delimiter_label(&self) -> &'static str {
+        Self::DELIMITERS
+            .iter()
+            .find(|(d, _)| *d == self.delimiter)
+            .map_or("自定义", |(_, label)| label)
+    }
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            utf8_bom: true,
+        }
+    }
+}
+
+/// 批量处理选项
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// 单个文件失败后是否继续处理剩余文件
+    pub continue_on_error: bool,
+    /// 输出文件名模式（None 表示沿用输入文件名），见 [`format_output_name`]
+    pub output_name_pattern: Option<String>,
+    /// 处理器 ID（用于输出文件名中的 `{processor}` 占位符）
+    pub processor_id: String,
+    /// 输出会覆盖输入文件时，是否先备份输入文件
+    pub backup_before_overwrite: bool,
+    /// 并行处理时是否按输入顺序汇总输出文件（关闭则按完成顺序）
+    pub preserve_input_order: bool,
+    /// 单个文件的处理时限（None 表示不限时）
+    pub file_timeout: Option<std::time::Duration>,
+    /// 输出文件格式
+    pub output_format: OutputFormat,
+    /// 处理完成后是否在输出目录生成批量汇总表
+    pub generate_summary: bool,
+    /// 输出文件已存在时的处理方式
+    pub conflict_strategy: ConflictStrategy,
+    /// 输出格式为 Excel 时，输出文件沿用各自输入文件的扩展名（.xls 不支持写出，仍输出 .xlsx）
+    pub mirror_input_extension: bool,
+    /// 并行处理的内存预算（字节），设置后按文件大小自动降低并行数，None 表示固定并行数
+    pub memory_budget: Option<u64>,
+    /// 输出格式为 CSV 时的分隔符和编码
+    pub csv: CsvOptions,
+    /// 输入目录中没有 Excel 文件时是否作为错误返回（否则返回标记为空输入的结果）
+    pub fail_on_empty_input: bool,
+    /// 写出 Excel 后重新打开校验，校验失败按该文件处理失败计
+    pub verify_output: bool,
+    /// 允许的最多失败文件数，超过后停止处理剩余文件（None 表示不限）
+    pub fail_fast_threshold: Option<usize>,
+    /// 取消信号，用户取消和失败过多中止共用；每次运行应使用新的信号
+    pub cancel: CancelToken,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            continue_on_error: true,
+            output_name_pattern: None,
+            processor_id: String::new(),
+            backup_before_overwrite: true,
+            preserve_input_order: true,
+            file_timeout: None,
+            output_format: OutputFormat::Xlsx,
+            generate_summary: false,
+            conflict_strategy: ConflictStrategy::Overwrite,
+            mirror_input_extension: false,
+            memory_budget: None,
+            csv: CsvOptions::default(),
+            fail_on_empty_input: false,
+            verify_output: false,
+            fail_fast_threshold: None,
+            cancel: CancelToken::default(),
+        }
+    }
+}
+
+impl BatchOptions {
+    /// 失败文件数超过阈值时返回中止原因
+    pub fn fail_fast_reason(&self, failed: usize) -> Option<String> {
+        self.fail_fast_threshold
+            .filter(|threshold| failed > *threshold)
+            .map(|threshold| format!("失败文件过多（{} 个，超过阈值 {}），已中止批量处理", failed, threshold))
+    }
+
+    /// 文件处理失败后是否需要停止批量处理，需要时发出取消信号
+    ///
+    /// 失败数超过阈值或关闭了失败后继续时，通过 `cancel` 通知其余文件停止，
+    /// 与用户取消走同一条路径。
+    fn stop_after_failure(&self, failed: usize) {
+        let reason = match self.fail_fast_reason(failed) {
+            Some(reason) => reason,
+            None if !self.continue_on_error => "文件处理失败且未开启失败后继续，已停止处理剩余文件".to_string(),
+            None => return,
+        };
+        if self.cancel.cancel(reason.clone()) {
+            tracing::warn!("{}", reason);
+        }
+    }
+
+    /// 校验选项组合，批量处理开始前调用
+    pub fn validate(&self) -> Result<()> {
+        match &self.output_name_pattern {
+            Some(pattern) => validate_output_name_pattern(pattern, self.conflict_strategy),
+            None => Ok(()),
+        }
+    }
+
+    /// 计算输入文件对应的输出路径
+    ///
+    /// 输出格式不是 Excel 时，扩展名替换为对应格式的扩展名；输出 Excel 且开启
+    /// `mirror_input_extension` 时，扩展名与输入文件一致。Excel 输出只支持 xlsx 格式，
+    /// 得到 .xls 扩展名（输入为 .xls 或模式指定）时改为 .xlsx 并记录警告。
+    /// 冲突策略为追加序号时，跳过已存在的文件名。
+    pub fn output_path(&self, input_path: &Path, output_dir: &Path) -> std::path::PathBuf {
+        self.conflict_strategy.resolve(self.planned_output_path(input_path, output_dir))
+    }
+
+    /// 同 [`output_path`](Self::output_path)，同时跳过 `reserved` 中已分配给其他文件的路径
+    ///
+    /// 并行处理在写出前为所有文件分配输出路径时使用，见 [`ConflictStrategy::resolve_reserved`]。
+    pub fn reserve_output_path(
+        &self,
+        input_path: &Path,
+        output_dir: &Path,
+        reserved: &mut std::collections::HashSet<std::path::PathBuf>,
+    ) -> std::path::PathBuf {
+        self.conflict_strategy
+            .resolve_reserved(self.planned_output_path(input_path, output_dir), reserved)
+    }
+
+    /// 按文件名模式和输出格式得到的输出路径（未处理冲突）
+    fn planned_output_path(&self, input_path: &Path, output_dir: &Path) -> std::path::PathBuf {
+        let path = match &self.output_name_pattern {
+            Some(pattern) => {
+                let ctx = OutputNameContext::from_input(input_path, &self.processor_id);
+                output_dir.join(format_output_name(pattern, &ctx))
+            }
+            None => output_dir.join(input_path.file_name().unwrap_or_default()),
+        };
+
+        match self.output_format {
+            OutputFormat::Xlsx => {
+                let path = match Self::excel_extension(input_path) {
+                    Some(ext) if self.mirror_input_extension => path.with_extension(ext),
+                    _ => path,
+                };
+                // 只能写出 xlsx 格式，写成 .xls 扩展名的文件 Excel 无法打开
+                if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xls")) {
+                    tracing::warn!("不支持写出 .xls 格式，{} 改为输出 .xlsx", path.display());
+                    path.with_extension("xlsx")
+                } else {
+                    path
+                }
+            }
+            format => path.with_extension(format.extension()),
+        }
+    }
+
+    /// 输入文件受支持的 Excel 扩展名（小写）
+    fn excel_extension(input_path: &Path) -> Option<String> {
+        let ext = input_path.extension()?.to_str()?.to_ascii_lowercase();
+        SUPPORTED_EXTENSIONS.contains(&ext.as_str()).then_some(ext)
+    }
+}
+
 /// 数据处理引擎
 pub struct DataEngine;
 
 impl DataEngine {
-    /// 读取 Excel 文件为 DataFrame（使用 umya-spreadsheet）
+    /// 校验文件扩展名是否受支持
+    ///
+    /// 在任何读取操作之前调用，避免拖放等绕过文件对话框过滤的路径进入读取流程。
+    pub fn validate_format(path: &Path) -> Result<()> {
+        let supported = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| {
+                SUPPORTED_EXTENSIONS
+                    .iter()
+                    .any(|s| ext.eq_ignore_ascii_case(s))
+            })
+            .unwrap_or(false);
+
+        if supported {
+            Ok(())
+        } else {
+            Err(AppError::unsupported_format(path))
+        }
+    }
+
+    /// 读取 Excel 文件为 DataFrame（使用 umya-spreadsheet），自动检测表头所在行
     pub fn read_excel(path: &Path) -> Result<DataFrame> {
+        Self::read_excel_with_header_row(path, None)
+    }
+
+    /// 读取 Excel 文件为 DataFrame
+    ///
+    /// `header_row` 为表头所在行号（从1开始），None 表示用 [`detect_header_row`] 自动检测。
+    /// 表头之前的行被忽略，之后的行作为数据。
+    pub fn read_excel_with_header_row(path: &Path, header_row: Option<u32>) -> Result<DataFrame> {
         tracing::debug!("读取 Excel 文件: {}", path.display());
 
+        Self::validate_format(path)?;
+
         let book = umya_spreadsheet::reader::xlsx::read(path)
             .map_err(|e| AppError::excel_error(format!("无法打开文件: {}", e)))?;
 
@@ -28,9 +307,25 @@ impl DataEngine {
             return Err(AppError::excel_error("工作表为空"));
         }
 
-        // 读取表头（第一行）
+        let header_row = match header_row {
+            Some(row) => row.max(1),
+            None => {
+                let scanned: Vec<Vec<String>> = (1..=height.min(HEADER_SCAN_ROWS as u32))
+                    .map(|row| (1..=width).map(|col| ws.get_value((col, row))).collect())
+                    .collect();
+                detect_header_row(&scanned) as u32 + 1
+            }
+        };
+        if header_row > height {
+            return Err(AppError::excel_error(format!("表头行 {} 超出工作表范围", header_row)));
+        }
+        if header_row > 1 {
+            tracing::debug!("表头位于第 {} 行", header_row);
+        }
+
+        // 读取表头
         let headers: Vec<String> = (1..=width)
-            .map(|col| ws.get_value((col, 1)))
+            .map(|col| ws.get_value((col, header_row)))
             .enumerate()
             .map(|(i, v)| if v.is_empty() { format!("Column_{}", i) } else { v })
             .collect();
@@ -39,7 +334,7 @@ impl DataEngine {
         let mut columns: Vec<Column> = Vec::new();
         for (col_idx, header) in headers.iter().enumerate() {
             let mut values: Vec<String> = Vec::new();
-            for row in 2..=height {
+            for row in (header_row + 1)..=height {
                 let v = ws.get_value(((col_idx + 1) as u32, row));
                 values.push(v);
             }
@@ -57,30 +352,188 @@ impl DataEngine {
         (rows, cols)
     }
 
-    /// 写入 DataFrame 到 Excel 文件
+    /// 写入 DataFrame 到 Excel 文件（使用 umya-spreadsheet）
+    ///
+    /// 第一行为列名，之后每行对应一行数据；数值写为数字单元格，空值写为空单元格。
     pub fn write_excel(df: &DataFrame, path: &Path) -> Result<()> {
         tracing::debug!("写入 Excel 文件: {}", path.display());
 
-        // 将 DataFrame 转换为 CSV 格式（临时方案）
-        // 注意：Polars 原生不支持直接写入 Excel，这里使用 CSV 作为中间格式
-        // 在实际应用中，可以使用 rust_xlsxwriter 等库来写入真正的 Excel 文件
+        let mut book = umya_spreadsheet::new_file();
+        let ws = book
+            .get_sheet_mut(&0)
+            .ok_or_else(|| AppError::excel_error("无法创建工作表"))?;
+
+        for (col_idx, column) in df.get_columns().iter().enumerate() {
+            let col = col_idx as u32 + 1;
+            ws.get_cell_mut((col, 1)).set_value(column.name().to_string());
+
+            let series = column.as_materialized_series();
+            for row_idx in 0..series.len() {
+                let value = series
+                    .get(row_idx)
+                    .map_err(|e| AppError::polars_error(format!("读取数据失败: {}", e)))?;
+                let cell = ws.get_cell_mut((col, row_idx as u32 + 2));
+                match value {
+                    AnyValue::Null => {
+                        cell.set_value(String::new());
+                    }
+                    AnyValue::String(s) => {
+                        cell.set_value(s.to_string());
+                    }
+                    AnyValue::StringOwned(s) => {
+                        cell.set_value(s.to_string());
+                    }
+                    AnyValue::Boolean(b) => {
+                        cell.set_value_bool(b);
+                    }
+                    other => match other.extract::<f64>() {
+                        Some(number) => {
+                            cell.set_value_number(number);
+                        }
+                        None => {
+                            cell.set_value(other.to_string());
+                        }
+                    },
+                }
+            }
+        }
+
+        umya_spreadsheet::writer::xlsx::write(&book, path)
+            .map_err(|e| AppError::excel_error(format!("写入文件失败: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// 重新打开写出的 Excel 文件，确认能正常读取且行数与数据一致（表头 + 数据行）
+    ///
+    /// 用于发现写入过程中被截断或损坏的文件，校验失败返回 [`AppError::OutputVerification`]。
+    pub fn verify_excel_output(df: &DataFrame, path: &Path) -> Result<()> {
+        let fail = |reason: String| AppError::OutputVerification(path.to_path_buf(), reason);
+
+        let book = umya_spreadsheet::reader::xlsx::read(path).map_err(|e| fail(format!("无法重新打开: {}", e)))?;
+        let ws = book
+            .get_sheet_collection()
+            .first()
+            .ok_or_else(|| fail("工作簿中没有工作表".to_string()))?;
+
+        let expected = if df.width() == 0 { 0 } else { df.height() + 1 };
+        let (rows, _) = Self::worksheet_size(ws);
+        if rows as usize != expected {
+            return Err(fail(format!("预期 {} 行（含表头），实际 {} 行", expected, rows)));
+        }
+
+        tracing::debug!("输出文件校验通过: {}", path.display());
+        Ok(())
+    }
+
+    /// 保留格式的单元格值转换
+    ///
+    /// 用 umya 打开源工作簿，对指定工作表（None 表示所有工作表）中每个非公式单元格调用
+    /// `transform`，返回 `Some(新值)` 时原地替换单元格的值，样式、合并单元格等格式保持不变，
+    /// 最后另存到 `output_path`。返回被修改的单元格数。
+    pub fn transform_preserving_format<F>(
+        input_path: &Path,
+        output_path: &Path,
+        sheet: Option<&str>,
+        mut transform: F,
+    ) -> Result<usize>
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        Self::validate_format(input_path)?;
+
+        let mut book = umya_spreadsheet::reader::xlsx::read(input_path)
+            .map_err(|e| AppError::excel_error(format!("无法打开文件: {}", e)))?;
+
+        if let Some(name) = sheet {
+            if book.get_sheet_by_name(name).is_none() {
+                return Err(AppError::excel_error(format!("未找到工作表: {}", name)));
+            }
+        }
+
+        let mut changed = 0;
+        for ws in book.get_sheet_collection_mut() {
+            if sheet.is_some_and(|name| ws.get_name() != name) {
+                continue;
+            }
+            for cell in ws.get_cell_collection_mut() {
+                if cell.is_formula() {
+                    continue;
+                }
+                let value = cell.get_value().to_string();
+                if let Some(new_value) = transform(&value) {
+                    if new_value != value {
+                        cell.set_value(new_value);
+                        changed += 1;
+                    }
+                }
+            }
+        }
 
+        umya_spreadsheet::writer::xlsx::write(&book, output_path)
+            .map_err(|e| AppError::excel_error(format!("写入文件失败: {}", e)))?;
+
+        tracing::info!(
+            "保留格式转换完成: {} -> {}，修改 {} 个单元格",
+            input_path.display(),
+            output_path.display(),
+            changed
+        );
+        Ok(changed)
+    }
+
+    /// 按指定格式写出 DataFrame，`csv` 仅在输出 CSV 时使用
+    pub fn write_output(df: &DataFrame, path: &Path, format: OutputFormat, csv: CsvOptions) -> Result<()> {
+        match format {
+            OutputFormat::Xlsx => Self::write_excel(df, path),
+            OutputFormat::Csv => Self::write_csv(df, path, csv),
+            OutputFormat::Parquet => {
+                tracing::debug!("写入 Parquet 文件: {}", path.display());
+                let mut df_mut = df.clone();
+                let file = std::fs::File::create(path)?;
+                ParquetWriter::new(file)
+                    .finish(&mut df_mut)
+                    .map_err(|e| AppError::polars_error(format!("写入 Parquet 文件失败: {}", e)))?;
+                Ok(())
+            }
+        }
+    }
+
+    /// 写出 CSV 文件
+    pub fn write_csv(df: &DataFrame, path: &Path, csv: CsvOptions) -> Result<()> {
+        tracing::debug!("写入 CSV 文件: {}", path.display());
         let mut df_mut = df.clone();
         let mut file = std::fs::File::create(path)?;
         CsvWriter::new(&mut file)
+            .include_bom(csv.utf8_bom)
+            .with_separator(csv.delimiter)
             .finish(&mut df_mut)
-            .map_err(|e| AppError::polars_error(format!("写入文件失败: {}", e)))?;
-
+            .map_err(|e| AppError::polars_error(format!("写入 CSV 文件失败: {}", e)))?;
         Ok(())
     }
 
+    /// 输入目录中没有 Excel 文件时的处理结果，按 `options.fail_on_empty_input` 决定是否报错
+    fn empty_input_outcome(input_dir: &Path, options: &BatchOptions) -> Result<ProcessingResult> {
+        tracing::warn!("输入目录中没有找到 Excel 文件: {}", input_dir.display());
+        if options.fail_on_empty_input {
+            Err(AppError::NoExcelFiles(input_dir.to_path_buf()))
+        } else {
+            Ok(ProcessingResult::empty_input())
+        }
+    }
+
     /// 批量处理文件
+    ///
+    /// `options.continue_on_error` 为 true 时单个文件失败不会中断剩余文件的处理；
+    /// 为 false 时遇到第一个失败即停止。所有失败都记录在结果的 `errors` 中。
+    /// `options.cancel` 被取消（用户取消或失败过多）后跳过剩余文件，结果标记为已中止。
     pub async fn process_batch<F, P>(
         input_dir: &Path,
         output_dir: &Path,
         processor: P,
         progress_callback: F,
-    ) -> Result<ProcessingStats>
+        options: &BatchOptions,
+    ) -> Result<ProcessingResult>
     where
         F: Fn(ProcessingProgress) + Send + Sync + 'static,
         P: Fn(DataFrame) -> Result<DataFrame> + Send + Sync + 'static + Clone,
@@ -91,6 +544,8 @@ impl DataEngine {
             output_dir.display()
         );
 
+        options.validate()?;
+
         // 验证目录
         if !input_dir.exists() {
             return Err(AppError::DirectoryNotFound(input_dir.to_path_buf()));
@@ -105,17 +560,21 @@ impl DataEngine {
         let total_files = files.len();
 
         if total_files == 0 {
-            tracing::warn!("输入目录中没有找到 xlsx 文件");
-            return Ok(ProcessingStats::new());
+            return Self::empty_input_outcome(input_dir, options);
         }
 
         tracing::info!("找到 {} 个文件待处理", total_files);
 
-        let mut stats = ProcessingStats::new();
+        let mut result = ProcessingResult::new(total_files);
         let start_time = std::time::Instant::now();
 
         // 处理每个文件
         for (idx, file_path) in files.iter().enumerate() {
+            if options.cancel.is_cancelled() {
+                tracing::warn!("已取消，跳过剩余 {} 个文件", total_files - idx);
+                break;
+            }
+
             let file_name = file_path
                 .file_name()
                 .and_then(|n| n.to_str())
@@ -127,24 +586,46 @@ impl DataEngine {
             progress_callback(progress.clone());
 
             // 处理文件
-            let output_path = output_dir.join(file_name);
+            let output_path = options.output_path(file_path, output_dir);
             let processor_clone = processor.clone();
 
-            match Self::process_single_file(file_path, &output_path, processor_clone).await {
-                Ok(_) => {
-                    stats.files_succeeded += 1;
+            let outcome = Self::process_single_file(
+                file_path,
+                &output_path,
+                processor_clone,
+                options,
+            )
+            .await;
+
+            // 通知该文件已完成
+            let mut done = ProcessingProgress::new(total_files);
+            done.update(idx + 1, file_name.to_string());
+            done.mark_completed(file_name, outcome.is_ok());
+            progress_callback(done);
+
+            match outcome {
+                Ok(rows) => {
+                    result.add_success();
+                    result.add_output(output_path, rows);
                     tracing::info!("成功处理: {}", file_name);
                 }
+                // 处理中途被取消的文件没有写出，不计为失败
+                Err(AppError::OperationCancelled) if options.cancel.is_cancelled() => {
+                    tracing::warn!("已取消: {}", file_name);
+                }
                 Err(e) => {
-                    stats.files_failed += 1;
                     tracing::error!("处理失败 {}: {}", file_name, e);
+                    result.add_failure(ProcessingError::new(file_path.clone(), e.user_message()));
+                    options.stop_after_failure(result.failed);
                 }
             }
-
-            stats.files_processed += 1;
         }
 
-        stats.total_duration = start_time.elapsed();
+        if let Some(reason) = options.cancel.reason() {
+            result.mark_aborted(reason);
+        }
+        result.set_duration(start_time.elapsed());
+        Self::write_summary_if_enabled(&result, output_dir, options);
 
         // 最终进度更新
         let mut final_progress = ProcessingProgress::new(total_files);
@@ -153,45 +634,229 @@ impl DataEngine {
 
         tracing::info!(
             "批量处理完成: 成功={}, 失败={}, 耗时={:?}",
-            stats.files_succeeded,
-            stats.files_failed,
-            stats.total_duration
+            result.successful,
+            result.failed,
+            result.duration
         );
 
-        Ok(stats)
+        Ok(result)
+    }
+
+    /// 处理单个输入文件并写出到 `output_path`，返回只含该文件的处理结果
+    ///
+    /// 与批量处理共用读取、备份、写出和取消逻辑；输出文件已存在时按 `options.conflict_strategy` 处理。
+    /// 文件处理失败记录在结果的 `errors` 中，便于界面与批量处理统一展示。
+    pub async fn process_file<P>(
+        input_path: &Path,
+        output_path: &Path,
+        processor: P,
+        options: &BatchOptions,
+    ) -> Result<ProcessingResult>
+    where
+        P: Fn(DataFrame) -> Result<DataFrame> + Send + Sync + 'static,
+    {
+        Self::validate_format(input_path)?;
+        if let Some(parent) = output_path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        let output_path = options.conflict_strategy.resolve(output_path.to_path_buf());
+
+        let mut result = ProcessingResult::new(1);
+        let start_time = std::time::Instant::now();
+        match Self::process_single_file(input_path, &output_path, processor, options).await {
+            Ok(rows) => {
+                result.add_success();
+                result.add_output(output_path, rows);
+                tracing::info!("成功处理: {}", input_path.display());
+            }
+            Err(AppError::OperationCancelled) if options.cancel.is_cancelled() => {
+                tracing::warn!("已取消: {}", input_path.display());
+            }
+            Err(e) => {
+                tracing::error!("处理失败 {}: {}", input_path.display(), e);
+                result.add_failure(ProcessingError::new(input_path.to_path_buf(), e.user_message()));
+            }
+        }
+
+        if let Some(reason) = options.cancel.reason() {
+            result.mark_aborted(reason);
+        }
+        result.set_duration(start_time.elapsed());
+        Ok(result)
+    }
+
+    /// 备份文件到带时间戳的同级文件
+    ///
+    /// 备份文件名形如 `原文件名_20240101_120000.xlsx.bak`，同一秒内重复备份时追加序号。
+    /// 返回备份文件路径。
+    pub fn backup_file(path: &Path) -> Result<std::path::PathBuf> {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| AppError::processing_error(format!("无效的文件路径: {}", path.display())))?;
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| file_name.clone());
+        let ext = path
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default();
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+
+        let mut backup_path = path.with_file_name(format!("{}_{}{}.bak", stem, timestamp, ext));
+        let mut counter = 1;
+        while backup_path.exists() {
+            backup_path = path.with_file_name(format!("{}_{}_{}{}.bak", stem, timestamp, counter, ext));
+            counter += 1;
+        }
+
+        std::fs::copy(path, &backup_path)?;
+        tracing::info!("已备份 {} -> {}", path.display(), backup_path.display());
+
+        Ok(backup_path)
+    }
+
+    /// 输出路径与输入路径相同（原地写回）时备份输入文件
+    ///
+    /// 写入单独的输出目录时不做备份，返回None。
+    fn backup_if_overwriting(input_path: &Path, output_path: &Path) -> Result<Option<std::path::PathBuf>> {
+        let overwriting = match (input_path.canonicalize(), output_path.canonicalize()) {
+            (Ok(input), Ok(output)) => input == output,
+            _ => false,
+        };
+
+        if overwriting {
+            Self::backup_file(input_path).map(Some)
+        } else {
+            Ok(None)
+        }
     }
 
-    /// 处理单个文件
+    /// 处理单个文件，返回写出的数据行数
+    ///
+    /// 设置了 `options.file_timeout` 时，超时返回 [`AppError::Timeout`]，不再等待该文件。
+    /// 阻塞任务本身无法被强制终止，会在后台运行完处理器；超时后它不会再备份或写出文件。
+    /// 写出前 `options.cancel` 已被取消时同样不写出，返回 [`AppError::OperationCancelled`]。
     async fn process_single_file<P>(
         input_path: &Path,
         output_path: &Path,
         processor: P,
-    ) -> Result<()>
+        options: &BatchOptions,
+    ) -> Result<usize>
     where
         P: Fn(DataFrame) -> Result<DataFrame> + Send + Sync + 'static,
     {
         let input_path = input_path.to_path_buf();
         let output_path = output_path.to_path_buf();
+        let backup_before_overwrite = options.backup_before_overwrite;
+        let output_format = options.output_format;
+        let csv_options = options.csv;
+        let verify_output = options.verify_output && output_format == OutputFormat::Xlsx;
+        // 超时后置位，阻止仍在后台运行的任务产生副作用
+        let cancelled = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let task_cancelled = std::sync::Arc::clone(&cancelled);
+        let batch_cancel = options.cancel.clone();
 
         // 在独立任务中处理文件
-        task::spawn_blocking(move || {
+        let handle = task::spawn_blocking(move || {
             // 读取文件
             let df = Self::read_excel(&input_path)?;
 
             // 应用处理器
             let processed_df = processor(df)?;
 
+            // 已超时的文件已记为失败，批量处理已取消时也不再备份或写出
+            if task_cancelled.load(std::sync::atomic::Ordering::SeqCst) || batch_cancel.is_cancelled() {
+                return Err(AppError::OperationCancelled);
+            }
+
+            // 原地写回前先备份输入文件
+            if backup_before_overwrite {
+                Self::backup_if_overwriting(&input_path, &output_path)?;
+            }
+
             // 写入结果
-            Self::write_excel(&processed_df, &output_path)?;
+            Self::write_output(&processed_df, &output_path, output_format, csv_options)?;
+            if verify_output {
+                Self::verify_excel_output(&processed_df, &output_path)?;
+            }
 
-            Ok::<(), AppError>(())
-        })
-        .await
-        .map_err(|e| AppError::processing_error(format!("任务执行失败: {}", e)))??;
+            Ok::<usize, AppError>(processed_df.height())
+        });
+
+        let joined = match options.file_timeout {
+            Some(limit) => tokio::time::timeout(limit, handle).await.map_err(|_| {
+                cancelled.store(true, std::sync::atomic::Ordering::SeqCst);
+                AppError::Timeout(limit)
+            })?,
+            None => handle.await,
+        };
+        joined.map_err(|e| AppError::processing_error(format!("任务执行失败: {}", e)))?
+    }
 
+    /// 将批量处理结果写入工作簿的「汇总」工作表
+    ///
+    /// 文件已存在时追加（或替换已有的）汇总工作表，否则新建只含汇总表的工作簿。
+    pub fn write_summary_sheet(result: &ProcessingResult, path: &Path) -> Result<()> {
+        let mut book = if path.exists() {
+            umya_spreadsheet::reader::xlsx::read(path)
+                .map_err(|e| AppError::excel_error(format!("读取汇总工作簿失败: {}", e)))?
+        } else {
+            umya_spreadsheet::new_file_empty_worksheet()
+        };
+
+        if book.get_sheet_by_name(SUMMARY_SHEET_NAME).is_some() {
+            book.remove_sheet_by_name(SUMMARY_SHEET_NAME)
+                .map_err(|e| AppError::excel_error(format!("移除旧汇总表失败: {}", e)))?;
+        }
+        let sheet = book
+            .new_sheet(SUMMARY_SHEET_NAME)
+            .map_err(|e| AppError::excel_error(format!("创建汇总表失败: {}", e)))?;
+
+        sheet.get_cell_mut("A1").set_value("文件");
+        sheet.get_cell_mut("B1").set_value("行数");
+
+        let mut row = 2u32;
+        for (file, rows) in result.output_files.iter().zip(&result.output_rows) {
+            let name = file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            sheet.get_cell_mut((1, row)).set_value(name);
+            sheet.get_cell_mut((2, row)).set_value_number(*rows as f64);
+            row += 1;
+        }
+
+        // 合计
+        sheet.get_cell_mut((1, row)).set_value("合计");
+        sheet.get_cell_mut((2, row)).set_value_number(result.total_rows() as f64);
+        sheet.get_cell_mut((1, row + 1)).set_value("成功文件数");
+        sheet.get_cell_mut((2, row + 1)).set_value_number(result.successful as f64);
+        sheet.get_cell_mut((1, row + 2)).set_value("失败文件数");
+        sheet.get_cell_mut((2, row + 2)).set_value_number(result.failed as f64);
+
+        umya_spreadsheet::writer::xlsx::write(&book, path)
+            .map_err(|e| AppError::excel_error(format!("写入汇总工作簿失败: {}", e)))?;
+
+        tracing::info!("已生成批量汇总表: {}", path.display());
         Ok(())
     }
 
+    /// 开启 `generate_summary` 时生成批量汇总表，失败只记录警告，不影响处理结果
+    fn write_summary_if_enabled(result: &ProcessingResult, output_dir: &Path, options: &BatchOptions) {
+        if !options.generate_summary || result.output_files.is_empty() {
+            return;
+        }
+
+        let path = output_dir.join(SUMMARY_FILE_NAME);
+        if let Err(e) = Self::write_summary_sheet(result, &path) {
+            tracing::warn!("生成批量汇总表失败: {}", e);
+        }
+    }
+
     /// 扫描目录中的所有 xlsx 文件
     fn scan_xlsx_files(dir: &Path) -> Result<Vec<std::path::PathBuf>> {
         let mut files = Vec::new();
@@ -214,13 +879,17 @@ impl DataEngine {
     }
 
     /// 并行批量处理文件
+    ///
+    /// 失败处理与 [`process_batch`](Self::process_batch) 相同：需要停止时通过 `options.cancel`
+    /// 发出取消信号，尚未开始的文件跳过，正在处理的文件不再写出。
     pub async fn process_batch_parallel<F, P>(
         input_dir: &Path,
         output_dir: &Path,
         processor: P,
         progress_callback: F,
         max_parallel: usize,
-    ) -> Result<ProcessingStats>
+        options: &BatchOptions,
+    ) -> Result<ProcessingResult>
     where
         F: Fn(ProcessingProgress) + Send + Sync + 'static + Clone,
         P: Fn(DataFrame) -> Result<DataFrame> + Send + Sync + 'static + Clone,
@@ -232,6 +901,8 @@ impl DataEngine {
             max_parallel
         );
 
+        options.validate()?;
+
         // 验证目录
         if !input_dir.exists() {
             return Err(AppError::DirectoryNotFound(input_dir.to_path_buf()));
@@ -246,59 +917,103 @@ impl DataEngine {
         let total_files = files.len();
 
         if total_files == 0 {
-            tracing::warn!("输入目录中没有找到 xlsx 文件");
-            return Ok(ProcessingStats::new());
+            return Self::empty_input_outcome(input_dir, options);
         }
 
         tracing::info!("找到 {} 个文件待处理", total_files);
 
-        let mut stats = ProcessingStats::new();
+        // 自适应模式下按文件大小估算内存占用，必要时降低并行数
+        let max_parallel = match options.memory_budget {
+            Some(budget) => {
+                let sizes: Vec<u64> = files
+                    .iter()
+                    .map(|path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0))
+                    .collect();
+                let adapted = estimate_concurrency(&sizes, budget, max_parallel);
+                if adapted < max_parallel {
+                    tracing::info!("按内存预算将并行数从 {} 降为 {}", max_parallel, adapted);
+                }
+                adapted
+            }
+            None => max_parallel,
+        };
+
+        let mut result = ProcessingResult::new(total_files);
         let start_time = std::time::Instant::now();
 
         // 使用信号量限制并发数
         let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(max_parallel));
         let processed_count = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
         let success_count = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
-        let failure_count = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let errors = std::sync::Arc::new(std::sync::Mutex::new(Vec::<ProcessingError>::new()));
+        // 记录输入位置，便于按输入顺序汇总输出
+        let outputs = std::sync::Arc::new(std::sync::Mutex::new(Vec::<(usize, std::path::PathBuf, usize)>::new()));
 
         let mut tasks = Vec::new();
+        // 所有任务在写出前分配输出路径，记录已分配的路径，避免追加序号时多个文件分到同一路径
+        let mut reserved_outputs = std::collections::HashSet::new();
 
-        for file_path in files {
+        for (index, file_path) in files.into_iter().enumerate() {
             let file_name = file_path
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown")
                 .to_string();
 
-            let output_path = output_dir.join(&file_name);
+            let output_path = options.reserve_output_path(&file_path, output_dir, &mut reserved_outputs);
+            let outputs = outputs.clone();
+            let options = options.clone();
             let processor = processor.clone();
             let progress_callback = progress_callback.clone();
             let semaphore = semaphore.clone();
             let processed_count = processed_count.clone();
             let success_count = success_count.clone();
-            let failure_count = failure_count.clone();
+            let errors = errors.clone();
 
             let task = tokio::spawn(async move {
                 let _permit = semaphore.acquire().await.unwrap();
 
-                let result = Self::process_single_file(&file_path, &output_path, processor).await;
+                if options.cancel.is_cancelled() {
+                    tracing::warn!("已取消，跳过: {}", file_name);
+                    return;
+                }
+
+                let result = Self::process_single_file(
+                    &file_path,
+                    &output_path,
+                    processor,
+                    &options,
+                )
+                .await;
 
                 let processed = processed_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                let succeeded = result.is_ok();
 
                 match result {
-                    Ok(_) => {
+                    Ok(rows) => {
                         success_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                        if let Ok(mut outputs) = outputs.lock() {
+                            outputs.push((index, output_path, rows));
+                        }
                         tracing::info!("成功处理: {}", file_name);
                     }
+                    // 处理中途被取消的文件没有写出，不计为失败
+                    Err(AppError::OperationCancelled) if options.cancel.is_cancelled() => {
+                        tracing::warn!("已取消: {}", file_name);
+                    }
                     Err(e) => {
-                        failure_count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
                         tracing::error!("处理失败 {}: {}", file_name, e);
+                        if let Ok(mut errors) = errors.lock() {
+                            errors.push(ProcessingError::new(file_path.clone(), e.user_message()));
+                            options.stop_after_failure(errors.len());
+                        }
                     }
                 }
 
                 // 更新进度
                 let mut progress = ProcessingProgress::new(total_files);
-                progress.update(processed, file_name);
+                progress.update(processed, file_name.clone());
+                progress.mark_completed(file_name, succeeded);
                 progress_callback(progress);
             });
 
@@ -310,19 +1025,36 @@ impl DataEngine {
             let _ = task.await;
         }
 
-        stats.files_processed = total_files;
-        stats.files_succeeded = success_count.load(std::sync::atomic::Ordering::SeqCst);
-        stats.files_failed = failure_count.load(std::sync::atomic::Ordering::SeqCst);
-        stats.total_duration = start_time.elapsed();
+        for _ in 0..success_count.load(std::sync::atomic::Ordering::SeqCst) {
+            result.add_success();
+        }
+        let mut errors = std::mem::take(&mut *errors.lock().unwrap());
+        errors.sort_by(|a, b| a.file.cmp(&b.file));
+        for error in errors {
+            result.add_failure(error);
+        }
+        // outputs 中为完成顺序，需要时按输入位置重新排列
+        let mut outputs = std::mem::take(&mut *outputs.lock().unwrap());
+        if options.preserve_input_order {
+            outputs.sort_by_key(|(index, _, _)| *index);
+        }
+        for (_, output_path, rows) in outputs {
+            result.add_output(output_path, rows);
+        }
+        if let Some(reason) = options.cancel.reason() {
+            result.mark_aborted(reason);
+        }
+        result.set_duration(start_time.elapsed());
+        Self::write_summary_if_enabled(&result, output_dir, options);
 
         tracing::info!(
             "并行批量处理完成: 成功={}, 失败={}, 耗时={:?}",
-            stats.files_succeeded,
-            stats.files_failed,
-            stats.total_duration
+            result.successful,
+            result.failed,
+            result.duration
         );
 
-        Ok(stats)
+        Ok(result)
     }
 }
 
@@ -345,20 +1077,795 @@ mod tests {
         assert_eq!(files.len(), 2);
     }
 
+    #[test]
+    fn test_validate_format() {
+        assert!(DataEngine::validate_format(Path::new("data.xlsx")).is_ok());
+        assert!(DataEngine::validate_format(Path::new("DATA.XLSX")).is_ok());
+        assert!(DataEngine::validate_format(Path::new("legacy.xls")).is_ok());
+
+        for path in ["data.csv", "data.numbers", "data"] {
+            let err = DataEngine::validate_format(Path::new(path)).unwrap_err();
+            assert!(err.is_unsupported_format());
+        }
+    }
+
+    #[test]
+    fn test_read_excel_rejects_unsupported_format() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.csv");
+        std::fs::write(&path, b"a,b\n1,2\n").unwrap();
+
+        let err = DataEngine::read_excel(&path).unwrap_err();
+        assert!(matches!(err, AppError::UnsupportedFormat(p) if p == path));
+    }
+
     #[tokio::test]
     async fn test_process_batch_empty_dir() {
         let input_dir = tempdir().unwrap();
         let output_dir = tempdir().unwrap();
 
-        let stats = DataEngine::process_batch(
+        let result = DataEngine::process_batch(
             input_dir.path(),
             output_dir.path(),
             |df| Ok(df),
             |_| {},
+            &BatchOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.total_files, 0);
+        // 空输入与处理成功但没有文件的结果可以区分
+        assert!(result.is_empty_input());
+        assert!(!ProcessingResult::new(0).is_empty_input());
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_empty_dir_as_error() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let options = BatchOptions {
+            fail_on_empty_input: true,
+            ..Default::default()
+        };
+
+        let err = DataEngine::process_batch(input_dir.path(), output_dir.path(), |df| Ok(df), |_| {}, &options)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::NoExcelFiles(ref dir) if dir == input_dir.path()));
+    }
+
+    /// 写入一个只有表头和一行数据的 xlsx 文件
+    fn write_test_workbook(path: &Path, value: &str) {
+        let mut book = umya_spreadsheet::new_file();
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("名称");
+        sheet.get_cell_mut("A2").set_value(value);
+        umya_spreadsheet::writer::xlsx::write(&book, path).unwrap();
+    }
+
+    /// 值为 "fail" 的文件处理失败，其余原样返回，并统计调用次数
+    fn failing_processor(
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    ) -> impl Fn(DataFrame) -> Result<DataFrame> + Send + Sync + Clone + 'static {
+        move |df: DataFrame| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let value = df.column("名称").unwrap().as_materialized_series().str().unwrap().get(0).unwrap_or("").to_string();
+            if value == "fail" {
+                Err(AppError::processing_error("模拟失败"))
+            } else {
+                Ok(df)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_continues_after_failure() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_test_workbook(&input_dir.path().join("a.xlsx"), "ok");
+        write_test_workbook(&input_dir.path().join("b.xlsx"), "fail");
+        write_test_workbook(&input_dir.path().join("c.xlsx"), "ok");
+
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let result = DataEngine::process_batch(
+            input_dir.path(),
+            output_dir.path(),
+            failing_processor(calls.clone()),
+            |_| {},
+            &BatchOptions::default(),
         )
         .await
         .unwrap();
 
-        assert_eq!(stats.files_processed, 0);
+        // 第二个文件失败后，第三个文件仍然被处理
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+        assert_eq!(result.successful, 2);
+        assert_eq!(result.failed, 1);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].file, input_dir.path().join("b.xlsx"));
+        assert!(output_dir.path().join("c.xlsx").exists());
+    }
+
+    #[test]
+    fn test_batch_options_output_path() {
+        let input = Path::new("/in/货物分析表.xlsx");
+        let output_dir = Path::new("/out");
+
+        let options = BatchOptions::default();
+        assert_eq!(options.output_path(input, output_dir), output_dir.join("货物分析表.xlsx"));
+
+        let options = BatchOptions {
+            output_name_pattern: Some("{name}_{processor}.xlsx".to_string()),
+            processor_id: "cargo_analysis".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.output_path(input, output_dir),
+            output_dir.join("货物分析表_cargo_analysis.xlsx")
+        );
+    }
+
+    #[test]
+    fn test_write_output_parquet_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("result.parquet");
+        let df = DataFrame::new(vec![
+            Series::new("名称".into(), vec!["a", "b"]).into_column(),
+            Series::new("数量".into(), vec![1i64, 2]).into_column(),
+        ])
+        .unwrap();
+
+        DataEngine::write_output(&df, &path, OutputFormat::Parquet, CsvOptions::default()).unwrap();
+
+        let file = std::fs::File::open(&path).unwrap();
+        let read_back = ParquetReader::new(file).finish().unwrap();
+        assert!(read_back.equals(&df));
+    }
+
+    #[test]
+    fn test_read_excel_skips_metadata_rows_before_header() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("with_title.xlsx");
+
+        let mut book = umya_spreadsheet::new_file();
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("2024年第一季度货物清单");
+        sheet.get_cell_mut("A2").set_value("制表日期: 2024-04-01");
+        sheet.get_cell_mut("A3").set_value("名称");
+        sheet.get_cell_mut("B3").set_value("规格");
+        sheet.get_cell_mut("C3").set_value("数量");
+        sheet.get_cell_mut("A4").set_value("电缆");
+        sheet.get_cell_mut("B4").set_value("YJV-3x4");
+        sheet.get_cell_mut("C4").set_value("10");
+        sheet.get_cell_mut("A5").set_value("开关");
+        sheet.get_cell_mut("C5").set_value("5");
+        umya_spreadsheet::writer::xlsx::write(&book, &path).unwrap();
+
+        let df = DataEngine::read_excel(&path).unwrap();
+        let names: Vec<&str> = df.get_column_names().iter().map(|n| n.as_str()).collect();
+        assert_eq!(names, vec!["名称", "规格", "数量"]);
+        assert_eq!(df.height(), 2);
+
+        // 显式指定表头行
+        let df = DataEngine::read_excel_with_header_row(&path, Some(1)).unwrap();
+        assert_eq!(df.get_column_names()[0].as_str(), "2024年第一季度货物清单");
+        assert_eq!(df.height(), 4);
+    }
+
+    #[test]
+    fn test_write_excel_round_trip_and_verify() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("out.xlsx");
+        let df = DataFrame::new(vec![
+            Series::new("名称".into(), vec!["电缆", "开关"]).into_column(),
+            Series::new("数量".into(), vec![10i64, 5]).into_column(),
+        ])
+        .unwrap();
+
+        DataEngine::write_excel(&df, &path).unwrap();
+        DataEngine::verify_excel_output(&df, &path).unwrap();
+
+        let read = DataEngine::read_excel(&path).unwrap();
+        assert_eq!(read.height(), 2);
+        assert_eq!(read.column("数量").unwrap().as_materialized_series().str().unwrap().get(0), Some("10"));
+
+        // 行数与数据不符
+        let more = df.vstack(&df).unwrap();
+        assert!(matches!(
+            DataEngine::verify_excel_output(&more, &path),
+            Err(AppError::OutputVerification(..))
+        ));
+    }
+
+    #[test]
+    fn test_verify_flags_truncated_output() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("out.xlsx");
+        let df = DataFrame::new(vec![Series::new("名称".into(), vec!["电缆"; 50]).into_column()]).unwrap();
+        DataEngine::write_excel(&df, &path).unwrap();
+
+        // 模拟写入中断：只保留前一半内容
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+
+        let err = DataEngine::verify_excel_output(&df, &path).unwrap_err();
+        assert!(matches!(err, AppError::OutputVerification(ref p, _) if p == &path));
+        assert!(err.user_message().contains("校验失败"));
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_with_output_verification() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_test_workbook(&input_dir.path().join("a.xlsx"), "ok");
+
+        // 正常写出的文件通过校验
+        let options = BatchOptions {
+            verify_output: true,
+            ..Default::default()
+        };
+        let result = DataEngine::process_batch(input_dir.path(), output_dir.path(), |df| Ok(df), |_| {}, &options)
+            .await
+            .unwrap();
+        assert_eq!(result.successful, 1);
+        assert_eq!(result.failed, 0);
+    }
+
+    #[test]
+    fn test_detect_header_row() {
+        let row = |cells: &[&str]| cells.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        // 第一行即表头
+        assert_eq!(detect_header_row(&[row(&["名称", "数量"]), row(&["电缆", "10"])]), 0);
+        // 数字行不视为表头
+        assert_eq!(detect_header_row(&[row(&["1", "2", "3"]), row(&["序号", "名称", "数量"])]), 1);
+        // 没有符合条件的行时回退到第一行
+        assert_eq!(detect_header_row(&[row(&["", ""]), row(&["1", ""])]), 0);
+    }
+
+    #[test]
+    fn test_estimate_concurrency_caps_by_memory_budget() {
+        const MB: u64 = 1024 * 1024;
+
+        // 每个 50MB 文件约占 500MB，2GB 预算最多同时处理 4 个
+        assert_eq!(estimate_concurrency(&[50 * MB; 10], 2048 * MB, 8), 4);
+        // 小文件不受预算限制，仍以配置的并行数为上限
+        assert_eq!(estimate_concurrency(&[MB; 10], 2048 * MB, 8), 8);
+        // 按最大的文件估算：两个大文件就超出预算
+        assert_eq!(estimate_concurrency(&[150 * MB, 120 * MB, MB, MB], 2048 * MB, 8), 1);
+        // 单个文件超出预算时至少保留 1 个并行
+        assert_eq!(estimate_concurrency(&[500 * MB], 2048 * MB, 8), 1);
+        // 没有文件或未设置预算时回退到配置值
+        assert_eq!(estimate_concurrency(&[], 2048 * MB, 6), 6);
+        assert_eq!(estimate_concurrency(&[50 * MB; 10], 0, 6), 6);
+    }
+
+    #[test]
+    fn test_write_csv_bom_and_delimiter() {
+        let dir = tempdir().unwrap();
+        let df = DataFrame::new(vec![
+            Series::new("名称".into(), vec!["电缆"]).into_column(),
+            Series::new("数量".into(), vec![3i64]).into_column(),
+        ])
+        .unwrap();
+
+        let with_bom = dir.path().join("bom.csv");
+        DataEngine::write_output(&df, &with_bom, OutputFormat::Csv, CsvOptions::default()).unwrap();
+        let bytes = std::fs::read(&with_bom).unwrap();
+        assert!(bytes.starts_with(&[0xEF, 0xBB, 0xBF]));
+        assert_eq!(std::str::from_utf8(&bytes[3..]).unwrap(), "名称,数量\n电缆,3\n");
+
+        let plain = dir.path().join("plain.csv");
+        let options = CsvOptions {
+            delimiter: b';',
+            utf8_bom: false,
+        };
+        DataEngine::write_output(&df, &plain, OutputFormat::Csv, options).unwrap();
+        assert_eq!(std::fs::read_to_string(&plain).unwrap(), "名称;数量\n电缆;3\n");
+    }
+
+    #[test]
+    fn test_output_path_uses_format_extension() {
+        let options = BatchOptions {
+            output_format: OutputFormat::Parquet,
+            ..Default::default()
+        };
+
+        let path = options.output_path(Path::new("/in/data.xlsx"), Path::new("/out"));
+
+        assert_eq!(path, Path::new("/out/data.parquet"));
+    }
+
+    #[test]
+    fn test_output_path_mirrors_input_extension() {
+        let mut options = BatchOptions {
+            output_name_pattern: Some("{name}_结果.xlsx".to_string()),
+            ..Default::default()
+        };
+
+        let xls = Path::new("/in/old.XLS");
+        assert_eq!(options.output_path(xls, Path::new("/out")), Path::new("/out/old_结果.xlsx"));
+
+        // 不支持写出 .xls，沿用输入扩展名时 .xls 输入仍输出 .xlsx
+        options.mirror_input_extension = true;
+        assert_eq!(options.output_path(xls, Path::new("/out")), Path::new("/out/old_结果.xlsx"));
+        assert_eq!(
+            options.output_path(Path::new("/in/new.xlsx"), Path::new("/out")),
+            Path::new("/out/new_结果.xlsx")
+        );
+
+        // 非 Excel 输出格式不受影响
+        options.output_format = OutputFormat::Csv;
+        assert_eq!(options.output_path(xls, Path::new("/out")), Path::new("/out/old_结果.csv"));
+    }
+
+    #[test]
+    fn test_backup_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.xlsx");
+        std::fs::write(&path, b"original").unwrap();
+
+        let first = DataEngine::backup_file(&path).unwrap();
+        let second = DataEngine::backup_file(&path).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), path.parent());
+        assert!(first.to_string_lossy().ends_with(".xlsx.bak"));
+        assert_eq!(std::fs::read(&first).unwrap(), b"original");
+        assert_eq!(std::fs::read(&second).unwrap(), b"original");
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_backs_up_before_overwrite() {
+        let dir = tempdir().unwrap();
+        let input = dir.path().join("a.xlsx");
+        write_test_workbook(&input, "ok");
+        let original = std::fs::read(&input).unwrap();
+
+        // 输出目录与输入目录相同，结果会覆盖输入文件
+        let result = DataEngine::process_batch(
+            dir.path(),
+            dir.path(),
+            // 只保留表头，确保写回的内容与输入不同
+            |df| Ok(df.head(Some(0))),
+            |_| {},
+            &BatchOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.successful, 1);
+
+        let backups: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.to_string_lossy().ends_with(".bak"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(std::fs::read(&backups[0]).unwrap(), original);
+        assert_ne!(std::fs::read(&input).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_no_backup_for_separate_output() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_test_workbook(&input_dir.path().join("a.xlsx"), "ok");
+
+        DataEngine::process_batch(
+            input_dir.path(),
+            output_dir.path(),
+            |df| Ok(df),
+            |_| {},
+            &BatchOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        let has_backup = std::fs::read_dir(input_dir.path())
+            .unwrap()
+            .chain(std::fs::read_dir(output_dir.path()).unwrap())
+            .filter_map(|e| e.ok())
+            .any(|e| e.path().to_string_lossy().ends_with(".bak"));
+        assert!(!has_backup);
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_parallel_preserves_input_order() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        // 第一个文件最慢，完成顺序与输入顺序不同
+        write_test_workbook(&input_dir.path().join("a.xlsx"), "slow");
+        write_test_workbook(&input_dir.path().join("b.xlsx"), "ok");
+        write_test_workbook(&input_dir.path().join("c.xlsx"), "ok");
+
+        let processor = |df: DataFrame| {
+            let value = df.column("名称").unwrap().as_materialized_series().str().unwrap().get(0).unwrap_or("").to_string();
+            if value == "slow" {
+                std::thread::sleep(std::time::Duration::from_millis(300));
+            }
+            Ok(df)
+        };
+
+        let result = DataEngine::process_batch_parallel(
+            input_dir.path(),
+            output_dir.path(),
+            processor,
+            |_| {},
+            3,
+            &BatchOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        let expected: Vec<_> = ["a.xlsx", "b.xlsx", "c.xlsx"]
+            .iter()
+            .map(|name| output_dir.path().join(name))
+            .collect();
+        assert_eq!(result.output_files, expected);
+    }
+
+    #[test]
+    fn test_transform_preserving_format_keeps_styles() {
+        let dir = tempdir().unwrap();
+        let input = dir.path().join("source.xlsx");
+        let output = dir.path().join("cleaned.xlsx");
+
+        let mut book = umya_spreadsheet::new_file();
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("  项目编号  ");
+        sheet.get_style_mut("A1").set_background_color("FFC6EFCE");
+        sheet.get_style_mut("A1").get_font_mut().set_bold(true);
+        sheet.add_merge_cells("A1:B1");
+        sheet.get_cell_mut("A2").set_value("不变");
+        umya_spreadsheet::writer::xlsx::write(&book, &input).unwrap();
+
+        let changed = DataEngine::transform_preserving_format(&input, &output, None, |value| {
+            Some(value.trim().to_string())
+        })
+        .unwrap();
+        assert_eq!(changed, 1);
+
+        let book = umya_spreadsheet::reader::xlsx::read(&output).unwrap();
+        let sheet = book.get_sheet(&0).unwrap();
+        assert_eq!(sheet.get_value("A1"), "项目编号");
+        assert_eq!(sheet.get_value("A2"), "不变");
+
+        let style = sheet.get_style("A1");
+        assert_eq!(style.get_background_color().unwrap().get_argb(), "FFC6EFCE");
+        assert!(*style.get_font().unwrap().get_bold());
+        assert_eq!(sheet.get_merge_cells().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_generates_summary_sheet() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_test_workbook(&input_dir.path().join("a.xlsx"), "ok");
+        write_test_workbook(&input_dir.path().join("b.xlsx"), "ok");
+
+        let options = BatchOptions {
+            generate_summary: true,
+            ..Default::default()
+        };
+        let result = DataEngine::process_batch(
+            input_dir.path(),
+            output_dir.path(),
+            |df| Ok(df),
+            |_| {},
+            &options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.output_rows, vec![1, 1]);
+
+        let book = umya_spreadsheet::reader::xlsx::read(output_dir.path().join(SUMMARY_FILE_NAME)).unwrap();
+        let sheet = book.get_sheet_by_name(SUMMARY_SHEET_NAME).expect("缺少汇总表");
+        assert_eq!(sheet.get_value("A1"), "文件");
+        assert_eq!(sheet.get_value("A2"), "a.xlsx");
+        assert_eq!(sheet.get_value("B2"), "1");
+        assert_eq!(sheet.get_value("A3"), "b.xlsx");
+        assert_eq!(sheet.get_value("A4"), "合计");
+        assert_eq!(sheet.get_value("B4"), "2");
+        assert_eq!(sheet.get_value("A5"), "成功文件数");
+        assert_eq!(sheet.get_value("B5"), "2");
+    }
+
+    #[test]
+    fn test_write_summary_sheet_replaces_existing_sheet() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("汇总.xlsx");
+        write_test_workbook(&path, "原有数据");
+
+        let mut result = ProcessingResult::new(1);
+        result.add_success();
+        result.add_output(dir.path().join("x.xlsx"), 3);
+
+        DataEngine::write_summary_sheet(&result, &path).unwrap();
+        DataEngine::write_summary_sheet(&result, &path).unwrap();
+
+        let book = umya_spreadsheet::reader::xlsx::read(&path).unwrap();
+        // 原有工作表保留，汇总表只有一个
+        assert_eq!(book.get_sheet_collection().len(), 2);
+        let sheet = book.get_sheet_by_name(SUMMARY_SHEET_NAME).unwrap();
+        assert_eq!(sheet.get_value("A2"), "x.xlsx");
+        assert_eq!(sheet.get_value("B3"), "3");
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_records_timeout_as_failure() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_test_workbook(&input_dir.path().join("a.xlsx"), "slow");
+        write_test_workbook(&input_dir.path().join("b.xlsx"), "ok");
+
+        let processor = |df: DataFrame| {
+            let value = df.column("名称").unwrap().as_materialized_series().str().unwrap().get(0).unwrap_or("").to_string();
+            if value == "slow" {
+                std::thread::sleep(std::time::Duration::from_secs(2));
+            }
+            Ok(df)
+        };
+        let options = BatchOptions {
+            file_timeout: Some(std::time::Duration::from_millis(500)),
+            ..Default::default()
+        };
+
+        let result = DataEngine::process_batch(
+            input_dir.path(),
+            output_dir.path(),
+            processor,
+            |_| {},
+            &options,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.successful, 1);
+        assert_eq!(result.failed, 1);
+        assert_eq!(result.errors[0].file, input_dir.path().join("a.xlsx"));
+        assert!(result.errors[0].error_message.contains("超时"));
+
+        // 后台任务结束后也不会写出已超时的文件
+        tokio::time::sleep(std::time::Duration::from_millis(2500)).await;
+        assert!(!output_dir.path().join("a.xlsx").exists());
+        assert!(output_dir.path().join("b.xlsx").exists());
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_stops_on_error_when_disabled() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_test_workbook(&input_dir.path().join("a.xlsx"), "ok");
+        write_test_workbook(&input_dir.path().join("b.xlsx"), "fail");
+        write_test_workbook(&input_dir.path().join("c.xlsx"), "ok");
+
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let result = DataEngine::process_batch(
+            input_dir.path(),
+            output_dir.path(),
+            failing_processor(calls.clone()),
+            |_| {},
+            &BatchOptions {
+                continue_on_error: false,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(result.successful, 1);
+        assert_eq!(result.failed, 1);
+        assert!(!output_dir.path().join("c.xlsx").exists());
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_aborts_after_failure_threshold() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        for (name, value) in [("a", "fail"), ("b", "ok"), ("c", "fail"), ("d", "fail"), ("e", "ok")] {
+            write_test_workbook(&input_dir.path().join(format!("{}.xlsx", name)), value);
+        }
+        let options = BatchOptions {
+            fail_fast_threshold: Some(1),
+            ..Default::default()
+        };
+
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let result = DataEngine::process_batch(
+            input_dir.path(),
+            output_dir.path(),
+            failing_processor(calls.clone()),
+            |_| {},
+            &options,
+        )
+        .await
+        .unwrap();
+
+        // 第二个失败（c.xlsx）超过阈值后停止，d、e 不再处理
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+        assert_eq!((result.successful, result.failed), (1, 2));
+        assert!(result.was_aborted());
+        assert!(result.aborted.as_deref().unwrap().contains("失败文件过多"));
+        assert!(!output_dir.path().join("e.xlsx").exists());
+
+        // 并行处理（单并发保证顺序）同样中止，新的运行使用新的取消信号
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let result = DataEngine::process_batch_parallel(
+            input_dir.path(),
+            output_dir.path(),
+            failing_processor(calls.clone()),
+            |_| {},
+            1,
+            &BatchOptions {
+                cancel: CancelToken::new(),
+                ..options.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+        assert!(result.was_aborted());
+
+        // 未超过阈值时正常结束
+        let result = DataEngine::process_batch(
+            input_dir.path(),
+            output_dir.path(),
+            failing_processor(std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0))),
+            |_| {},
+            &BatchOptions {
+                fail_fast_threshold: Some(3),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.failed, 3);
+        assert!(!result.was_aborted());
+    }
+
+    #[tokio::test]
+    async fn test_user_cancel_stops_batch_like_fail_fast() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        for name in ["a", "b", "c"] {
+            write_test_workbook(&input_dir.path().join(format!("{}.xlsx", name)), "ok");
+        }
+
+        // 处理第一个文件时用户点击取消：该文件不写出，剩余文件跳过
+        let cancelling_processor = |cancel: CancelToken, calls: std::sync::Arc<std::sync::atomic::AtomicUsize>| {
+            move |df: DataFrame| -> Result<DataFrame> {
+                calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                cancel.cancel("用户取消了处理");
+                Ok(df)
+            }
+        };
+
+        let options = BatchOptions::default();
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let result = DataEngine::process_batch(
+            input_dir.path(),
+            output_dir.path(),
+            cancelling_processor(options.cancel.clone(), calls.clone()),
+            |_| {},
+            &options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!((result.successful, result.failed), (0, 0));
+        assert_eq!(result.aborted.as_deref(), Some("用户取消了处理"));
+        assert!(std::fs::read_dir(output_dir.path()).unwrap().next().is_none());
+
+        // 并行处理时已开始的文件同样不再写出
+        let options = BatchOptions::default();
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let result = DataEngine::process_batch_parallel(
+            input_dir.path(),
+            output_dir.path(),
+            cancelling_processor(options.cancel.clone(), calls.clone()),
+            |_| {},
+            2,
+            &options,
+        )
+        .await
+        .unwrap();
+        assert!(calls.load(std::sync::atomic::Ordering::SeqCst) <= 2);
+        assert_eq!((result.successful, result.failed), (0, 0));
+        assert_eq!(result.aborted.as_deref(), Some("用户取消了处理"));
+        assert!(std::fs::read_dir(output_dir.path()).unwrap().next().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_process_file_reports_single_output() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let input = input_dir.path().join("a.xlsx");
+        write_test_workbook(&input, "ok");
+        let output = output_dir.path().join("结果").join("out.xlsx");
+
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let result = DataEngine::process_file(&input, &output, failing_processor(calls.clone()), &BatchOptions::default())
+            .await
+            .unwrap();
+        assert_eq!((result.successful, result.failed), (1, 0));
+        assert_eq!(result.single_output_file(), Some(output.as_path()));
+        assert!(output.exists());
+
+        // 处理失败记录在结果中
+        write_test_workbook(&input, "fail");
+        let result = DataEngine::process_file(&input, &output, failing_processor(calls), &BatchOptions::default())
+            .await
+            .unwrap();
+        assert_eq!((result.successful, result.failed), (0, 1));
+        assert_eq!(result.single_output_file(), None);
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_parallel_reserves_suffixed_output_names() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_test_workbook(&input_dir.path().join("a.xlsx"), "ok");
+        write_test_workbook(&input_dir.path().join("b.xlsx"), "ok");
+
+        // 模式不含 {name}，追加序号时两个文件不能写到同一个输出文件
+        let options = BatchOptions {
+            output_name_pattern: Some("结果.xlsx".to_string()),
+            conflict_strategy: ConflictStrategy::AppendSuffix,
+            ..Default::default()
+        };
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let result = DataEngine::process_batch_parallel(
+            input_dir.path(),
+            output_dir.path(),
+            failing_processor(calls),
+            |_| {},
+            2,
+            &options,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.successful, 2);
+        assert_eq!(
+            result.output_files,
+            vec![output_dir.path().join("结果.xlsx"), output_dir.path().join("结果 (1).xlsx")]
+        );
+        for file in &result.output_files {
+            assert!(file.exists(), "{}", file.display());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_never_writes_xls_outputs() {
+        let input_dir = tempdir().unwrap();
+        write_test_workbook(&input_dir.path().join("a.xlsx"), "ok");
+
+        for mirror_input_extension in [false, true] {
+            let output_dir = tempdir().unwrap();
+            let options = BatchOptions {
+                output_name_pattern: Some("{name}_结果.xls".to_string()),
+                mirror_input_extension,
+                ..Default::default()
+            };
+            let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let result = DataEngine::process_batch(
+                input_dir.path(),
+                output_dir.path(),
+                failing_processor(calls),
+                |_| {},
+                &options,
+            )
+            .await
+            .unwrap();
+
+            // 输出为可以重新读取的 .xlsx，没有内容为 xlsx 的 .xls 文件
+            let output = output_dir.path().join("a_结果.xlsx");
+            assert_eq!(result.output_files, vec![output.clone()]);
+            assert!(!output_dir.path().join("a_结果.xls").exists());
+            assert_eq!(DataEngine::read_excel(&output).unwrap().height(), 1);
+        }
     }
 }
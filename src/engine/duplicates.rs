@@ -0,0 +1,177 @@
+// 重复行检测
+//
+// 按指定的关键列比较行内容，找出与前面某一行重复的行，
+// 供处理器标记或删除，并报告重复数量。首次出现的行不算重复。
+use crate::error::{AppError, Result};
+use crate::models::WorksheetData;
+use polars::prelude::*;
+use std::collections::HashSet;
+
+/// 重复行检测结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DuplicateReport {
+    /// 重复行的下标（从0开始，升序），不含每组中首次出现的行
+    pub duplicate_rows: Vec<usize>,
+}
+
+impl DuplicateReport {
+    pub fn count(&self) -> usize {
+        self.duplicate_rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.duplicate_rows.is_empty()
+    }
+
+    pub fn is_duplicate(&self, row_index: usize) -> bool {
+        self.duplicate_rows.binary_search(&row_index).is_ok()
+    }
+}
+
+/// 检测工作表中的重复行
+///
+/// `key_columns` 为参与比较的列下标，为空时比较整行。内容去除首尾空白后比较，
+/// 关键列全部为空的行（空行）不参与检测。
+pub fn find_duplicate_rows(worksheet: &WorksheetData, key_columns: &[usize]) -> DuplicateReport {
+    let mut seen = HashSet::new();
+    let mut duplicate_rows = Vec::new();
+
+    for (index, row) in worksheet.rows.iter().enumerate() {
+        let key: Vec<&str> = if key_columns.is_empty() {
+            row.cells.iter().map(|cell| cell.content.trim()).collect()
+        } else {
+            key_columns
+                .iter()
+                .map(|&col| row.get_cell(col).map_or("", |cell| cell.content.trim()))
+                .collect()
+        };
+        // 整行比较时忽略末尾的空单元格，避免单元格数量不同导致漏判
+        let key_len = key.iter().rposition(|s| !s.is_empty()).map_or(0, |i| i + 1);
+        if key_len == 0 {
+            continue;
+        }
+        let key = if key_columns.is_empty() { &key[..key_len] } else { &key[..] };
+
+        if !seen.insert(key.to_vec()) {
+            duplicate_rows.push(index);
+        }
+    }
+
+    DuplicateReport { duplicate_rows }
+}
+
+/// 检测 DataFrame 中的重复行
+///
+/// `key_columns` 为参与比较的列名，为空时比较所有列。各列统一转为字符串后比较，
+/// 空值与空值视为相同。
+pub fn find_duplicate_rows_df(df: &DataFrame, key_columns: &[&str]) -> Result<DuplicateReport> {
+    let names: Vec<PlSmallStr> = if key_columns.is_empty() {
+        df.get_column_names_owned()
+    } else {
+        key_columns.iter().map(|&name| name.into()).collect()
+    };
+
+    let columns = names
+        .iter()
+        .map(|name| {
+            df.column(name)
+                .and_then(|column| column.as_materialized_series().cast(&DataType::String))
+                .map_err(|e| AppError::polars_error(format!("读取关键列 {} 失败: {}", name, e)))
+        })
+        .collect::<Result<Vec<Series>>>()?;
+    let columns = columns
+        .iter()
+        .map(|series| series.str().map_err(|e| AppError::polars_error(e.to_string())))
+        .collect::<Result<Vec<&StringChunked>>>()?;
+
+    let mut seen = HashSet::new();
+    let mut duplicate_rows = Vec::new();
+    for index in 0..df.height() {
+        let key: Vec<Option<&str>> = columns.iter().map(|column| column.get(index)).collect();
+        if !seen.insert(key) {
+            duplicate_rows.push(index);
+        }
+    }
+
+    Ok(DuplicateReport { duplicate_rows })
+}
+
+/// 删除检测出的重复行，保留每组中首次出现的行
+pub fn remove_duplicate_rows(df: &DataFrame, report: &DuplicateReport) -> Result<DataFrame> {
+    if report.is_empty() {
+        return Ok(df.clone());
+    }
+    let mask = BooleanChunked::from_iter_values(
+        "keep".into(),
+        (0..df.height()).map(|index| !report.is_duplicate(index)),
+    );
+    df.filter(&mask)
+        .map_err(|e| AppError::polars_error(format!("删除重复行失败: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellData, CellStyle, RowData};
+
+    fn worksheet(rows: &[&[&str]]) -> WorksheetData {
+        let rows = rows
+            .iter()
+            .enumerate()
+            .map(|(row_index, contents)| {
+                let cells = contents
+                    .iter()
+                    .enumerate()
+                    .map(|(column_index, content)| CellData {
+                        column_index,
+                        content: content.to_string(),
+                        style: CellStyle::default(),
+                        merge_info: None,
+                        comment: None,
+                        raw_content: None,
+                    })
+                    .collect();
+                RowData::new(row_index, cells)
+            })
+            .collect();
+        WorksheetData {
+            name: "Sheet1".to_string(),
+            rows,
+        }
+    }
+
+    #[test]
+    fn test_worksheet_duplicates_by_key_columns() {
+        let sheet = worksheet(&[
+            &["1", "电缆", "10"],
+            &["2", "开关", "5"],
+            &["3", "电缆", "10"],
+            &["4", "插座", "8"],
+            &["5", "开关 ", "5"],
+        ]);
+
+        let report = find_duplicate_rows(&sheet, &[1, 2]);
+        assert_eq!(report.duplicate_rows, vec![2, 4]);
+        assert_eq!(report.count(), 2);
+
+        // 包含序号列时没有重复
+        assert!(find_duplicate_rows(&sheet, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_dataframe_duplicates_found_and_removed() {
+        let df = DataFrame::new(vec![
+            Series::new("名称".into(), vec!["电缆", "开关", "电缆", "插座", "开关"]).into_column(),
+            Series::new("数量".into(), vec![10i64, 5, 10, 8, 5]).into_column(),
+        ])
+        .unwrap();
+
+        let report = find_duplicate_rows_df(&df, &["名称", "数量"]).unwrap();
+        assert_eq!(report.duplicate_rows, vec![2, 4]);
+
+        let deduplicated = remove_duplicate_rows(&df, &report).unwrap();
+        assert_eq!(deduplicated.height(), 3);
+
+        assert!(find_duplicate_rows_df(&df, &["不存在"]).is_err());
+    }
+}
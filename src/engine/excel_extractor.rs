@@ -1,19 +1,78 @@
 // Excel Data Extraction Layer
-use crate::engine::{IdentificationError, IdentificationResult};
+use crate::engine::data_engine::DataEngine;
+use crate::engine::{IdentificationError, IdentificationResult, TextNormalization};
 use crate::models::{CellData, CellStyle, MergeInfo, RgbColor, RowData, WorksheetData};
+use std::collections::HashMap;
 use std::path::Path;
 
+/// 以 (列, 行)（从1开始）为键的批注文本
+type CommentMap = HashMap<(u32, u32), String>;
+
+/// 默认最多读取的列数
+pub const DEFAULT_MAX_COLUMNS: u32 = 256;
+
+/// 读取工作表的选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractOptions {
+    /// 最多读取的列数
+    pub max_columns: u32,
+    /// 单元格文本规范化（原始内容另存于 `CellData::raw_content`）
+    pub normalization: TextNormalization,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            max_columns: DEFAULT_MAX_COLUMNS,
+            normalization: TextNormalization::default(),
+        }
+    }
+}
+
 /// Excel数据提取器
 pub struct ExcelExtractor;
 
 impl ExcelExtractor {
-    /// 从Excel文件读取工作表数据
+    /// 从Excel文件读取工作表数据，最多读取 [`DEFAULT_MAX_COLUMNS`] 列
     pub fn read_worksheet(
         path: &Path,
         sheet_index: usize,
     ) -> IdentificationResult<WorksheetData> {
+        Self::read_worksheet_with_max_columns(path, sheet_index, DEFAULT_MAX_COLUMNS)
+    }
+
+    /// 从Excel文件读取工作表数据，最多读取 `max_columns` 列
+    pub fn read_worksheet_with_max_columns(
+        path: &Path,
+        sheet_index: usize,
+        max_columns: u32,
+    ) -> IdentificationResult<WorksheetData> {
+        let options = ExtractOptions {
+            max_columns,
+            ..Default::default()
+        };
+        Self::read_worksheet_with_options(path, sheet_index, &options)
+    }
+
+    /// 从Excel文件读取工作表数据
+    ///
+    /// 先扫描一遍单元格找出最后一个有内容（或批注）的列，末尾的全空列不再读取；
+    /// 读取的列数另受 `options.max_columns` 限制，避免极宽的表格拖慢识别。
+    /// 开启文本规范化时，规范化后的文本写入 `content`，原始文本保存在 `raw_content`。
+    pub fn read_worksheet_with_options(
+        path: &Path,
+        sheet_index: usize,
+        options: &ExtractOptions,
+    ) -> IdentificationResult<WorksheetData> {
+        let max_columns = options.max_columns;
         tracing::debug!("Reading Excel worksheet from: {}", path.display());
 
+        DataEngine::validate_format(path).map_err(|e| {
+            let msg = e.user_message();
+            tracing::error!("{}", msg);
+            IdentificationError::file_read_error(msg)
+        })?;
+
         let book = umya_spreadsheet::reader::xlsx::read(path).map_err(|e| {
             let msg = format!("无法打开Excel文件: {}", e);
             tracing::error!("{}", msg);
@@ -53,9 +112,21 @@ impl ExcelExtractor {
             });
         }
 
+        let comments = Self::extract_comments(worksheet);
+
+        let populated_col = Self::last_populated_column(worksheet, &comments).max(1);
+        let max_col = populated_col.min(max_col).min(max_columns.max(1));
+        if populated_col > max_col {
+            tracing::warn!(
+                "Worksheet has {} populated columns, only the first {} are read",
+                populated_col,
+                max_col
+            );
+        }
+
         let mut rows = Vec::new();
         for row_idx in 1..=max_row {
-            let row_data = Self::extract_row(worksheet, row_idx, max_col);
+            let row_data = Self::extract_row(worksheet, row_idx, max_col, &comments, options.normalization);
             rows.push(row_data);
         }
 
@@ -71,15 +142,31 @@ impl ExcelExtractor {
         })
     }
 
+    /// 最后一个有内容或批注的列号（从1开始），全空时返回 0
+    fn last_populated_column(worksheet: &umya_spreadsheet::Worksheet, comments: &CommentMap) -> u32 {
+        let by_content = worksheet
+            .get_cell_collection()
+            .iter()
+            .filter(|cell| !cell.get_value().trim().is_empty())
+            .map(|cell| *cell.get_coordinate().get_col_num())
+            .max()
+            .unwrap_or(0);
+        let by_comment = comments.keys().map(|(col, _)| *col).max().unwrap_or(0);
+
+        by_content.max(by_comment)
+    }
+
     fn extract_row(
         worksheet: &umya_spreadsheet::Worksheet,
         row_idx: u32,
         max_col: u32,
+        comments: &CommentMap,
+        normalization: TextNormalization,
     ) -> RowData {
         let mut cells = Vec::new();
 
         for col_idx in 1..=max_col {
-            let cell_data = Self::extract_cell(worksheet, col_idx, row_idx);
+            let cell_data = Self::extract_cell(worksheet, col_idx, row_idx, comments, normalization);
             cells.push(cell_data);
         }
 
@@ -93,19 +180,48 @@ impl ExcelExtractor {
         worksheet: &umya_spreadsheet::Worksheet,
         col_idx: u32,
         row_idx: u32,
+        comments: &CommentMap,
+        normalization: TextNormalization,
     ) -> CellData {
-        let content = worksheet.get_value((col_idx, row_idx));
+        let raw = worksheet.get_value((col_idx, row_idx));
+        let normalized = match normalization.apply(&raw) {
+            std::borrow::Cow::Owned(normalized) => Some(normalized),
+            std::borrow::Cow::Borrowed(_) => None,
+        };
+        let (content, raw_content) = match normalized {
+            Some(normalized) => (normalized, Some(raw)),
+            None => (raw, None),
+        };
         let style = Self::extract_cell_style(worksheet, col_idx, row_idx);
         let merge_info = Self::extract_merge_info(worksheet, col_idx, row_idx);
+        let comment = comments.get(&(col_idx, row_idx)).cloned();
 
         CellData {
             column_index: (col_idx - 1) as usize,
             content,
             style,
             merge_info,
+            comment,
+            raw_content,
         }
     }
 
+    /// 读取工作表中的所有批注
+    fn extract_comments(worksheet: &umya_spreadsheet::Worksheet) -> CommentMap {
+        worksheet
+            .get_comments()
+            .iter()
+            .filter_map(|comment| {
+                let coordinate = comment.get_coordinate();
+                let text = comment.get_text().get_text().trim().to_string();
+                if text.is_empty() {
+                    return None;
+                }
+                Some(((*coordinate.get_col_num(), *coordinate.get_row_num()), text))
+            })
+            .collect()
+    }
+
     fn extract_cell_style(
         worksheet: &umya_spreadsheet::Worksheet,
         col_idx: u32,
@@ -140,25 +256,33 @@ impl ExcelExtractor {
     }
 
     fn extract_rgb_color(color: &umya_spreadsheet::Color) -> Option<RgbColor> {
-        let rgb_str = color.get_argb();
-        if rgb_str.len() >= 8 {
-            match (
-                u8::from_str_radix(&rgb_str[2..4], 16),
-                u8::from_str_radix(&rgb_str[4..6], 16),
-                u8::from_str_radix(&rgb_str[6..8], 16),
-            ) {
-                (Ok(r), Ok(g), Ok(b)) => {
-                    return Some(RgbColor::new(r, g, b));
-                }
-                _ => {
-                    tracing::warn!("Failed to parse RGB color from: {}", rgb_str);
-                }
-            }
-        } else if !rgb_str.is_empty() {
-            tracing::warn!("Invalid ARGB format (too short): {}", rgb_str);
+        Self::parse_argb(color.get_argb())
+    }
+
+    /// 解析 umya 返回的颜色字符串
+    ///
+    /// 支持 "AARRGGBB"、"RRGGBB"（无透明度）以及带 `#` 前缀的写法；
+    /// 主题色等无法解析的字符串返回 None。
+    fn parse_argb(argb: &str) -> Option<RgbColor> {
+        let hex = argb.trim().trim_start_matches('#');
+        if hex.is_empty() {
+            return None;
         }
 
-        None
+        let rgb = match hex.len() {
+            8 if hex.is_ascii() => &hex[2..],
+            6 => hex,
+            _ => {
+                tracing::warn!("Unsupported color format: {}", argb);
+                return None;
+            }
+        };
+
+        let color = RgbColor::from_hex(rgb);
+        if color.is_none() {
+            tracing::warn!("Failed to parse RGB color from: {}", argb);
+        }
+        color
     }
 
     fn extract_merge_info(
@@ -190,3 +314,108 @@ impl ExcelExtractor {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_argb_formats() {
+        let green = Some(RgbColor::new(198, 239, 206));
+        assert_eq!(ExcelExtractor::parse_argb("FFC6EFCE"), green);
+        assert_eq!(ExcelExtractor::parse_argb("C6EFCE"), green);
+        assert_eq!(ExcelExtractor::parse_argb("#FFC6EFCE"), green);
+        assert_eq!(ExcelExtractor::parse_argb("#c6efce"), green);
+        assert_eq!(ExcelExtractor::parse_argb(" FFC6EFCE "), green);
+
+        // 空字符串、主题色、非十六进制等返回 None
+        assert_eq!(ExcelExtractor::parse_argb(""), None);
+        assert_eq!(ExcelExtractor::parse_argb("theme:4"), None);
+        assert_eq!(ExcelExtractor::parse_argb("FFGGHHII"), None);
+        assert_eq!(ExcelExtractor::parse_argb("柜号颜色"), None);
+    }
+
+    #[test]
+    fn test_read_cell_comment() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("comments.xlsx");
+
+        let mut book = umya_spreadsheet::new_file();
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("序号");
+        sheet.get_cell_mut("B1").set_value("名称");
+        let mut comment = umya_spreadsheet::Comment::default();
+        comment.new_comment("B1");
+        comment.set_author("tester");
+        comment.set_text_string("含备用件");
+        sheet.add_comments(comment);
+        umya_spreadsheet::writer::xlsx::write(&book, &path).unwrap();
+
+        let worksheet = ExcelExtractor::read_worksheet(&path, 0).unwrap();
+        let row = worksheet.get_row(0).unwrap();
+
+        assert_eq!(row.get_cell(0).unwrap().comment, None);
+        assert_eq!(row.get_cell(1).unwrap().comment.as_deref(), Some("含备用件"));
+    }
+
+    #[test]
+    fn test_trailing_empty_columns_excluded() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("wide.xlsx");
+
+        let mut book = umya_spreadsheet::new_file();
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("序号");
+        sheet.get_cell_mut("C1").set_value("名称");
+        sheet.get_cell_mut("A2").set_value("1");
+        // 末尾只有样式、没有内容的列
+        sheet
+            .get_cell_mut("Z2")
+            .get_style_mut()
+            .set_background_color("FFFFCCFD");
+        sheet.get_cell_mut("AZ1").set_value("");
+        umya_spreadsheet::writer::xlsx::write(&book, &path).unwrap();
+
+        let worksheet = ExcelExtractor::read_worksheet(&path, 0).unwrap();
+        assert_eq!(worksheet.rows.len(), 2);
+        for row in &worksheet.rows {
+            assert_eq!(row.cells.len(), 3);
+        }
+
+        // 列数上限小于有内容的列数时按上限截断
+        let bounded = ExcelExtractor::read_worksheet_with_max_columns(&path, 0, 1).unwrap();
+        assert_eq!(bounded.rows[0].cells.len(), 1);
+    }
+
+    #[test]
+    fn test_normalization_keeps_raw_content() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("spaces.xlsx");
+
+        let mut book = umya_spreadsheet::new_file();
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("  柜号：Ａ01  ");
+        sheet.get_cell_mut("B1").set_value("电缆");
+        umya_spreadsheet::writer::xlsx::write(&book, &path).unwrap();
+
+        let options = ExtractOptions {
+            normalization: TextNormalization::standard().with_full_width_to_half(true),
+            ..Default::default()
+        };
+        let worksheet = ExcelExtractor::read_worksheet_with_options(&path, 0, &options).unwrap();
+        let row = worksheet.get_row(0).unwrap();
+
+        let changed = row.get_cell(0).unwrap();
+        assert_eq!(changed.content, "柜号:A01");
+        assert_eq!(changed.raw_content.as_deref(), Some("  柜号：Ａ01  "));
+
+        let unchanged = row.get_cell(1).unwrap();
+        assert_eq!(unchanged.content, "电缆");
+        assert_eq!(unchanged.raw_content, None);
+
+        // 默认不做规范化
+        let plain = ExcelExtractor::read_worksheet(&path, 0).unwrap();
+        assert_eq!(plain.rows[0].get_cell(0).unwrap().content, "  柜号：Ａ01  ");
+    }
+}
@@ -1,11 +1,52 @@
 // File Type Profile Configuration
 use crate::engine::{
-    ColorRule, CompositeLogic, CompositeRule, MergeRequirement, MergeStateRule,
-    RecognitionRule, TextPattern, TextPatternRule,
+    ColorRule, CompositeLogic, CompositeRule, DefaultRule, IdentificationError, IdentificationResult,
+    MergeRequirement, MergeStateRule, RecognitionRule, TextPattern, TextPatternRule,
 };
 use crate::models::{RgbColor, RowType};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// 货物分析表中数据行才会填写的列（B-E列：名称、数量、单价、总价）
+const CARGO_DATA_COLUMNS: [usize; 4] = [1, 2, 3, 4];
+
+/// 用户调整过的配置文件（TOML），在内置配置的基础上修改
+///
+/// ```toml
+/// base = "cargo_analysis"
+/// name = "我的货物分析表"
+/// row_type_order = ["grand_total", "project_number"]
+/// default_row_type = "数据行"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ProfileFile {
+    /// 内置配置名称
+    base: String,
+    /// 配置名称（默认沿用内置配置的名称）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// 识别顺序（规则名称列表）
+    #[serde(default)]
+    row_type_order: Vec<String>,
+    /// 没有规则匹配时的行类型（显示名称或变体名称）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_row_type: Option<String>,
+}
+
+impl ProfileFile {
+    fn read(path: &Path) -> IdentificationResult<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            IdentificationError::file_read_error(format!("无法读取识别配置 {}: {}", path.display(), e))
+        })?;
+        toml::from_str(&text).map_err(|e| {
+            IdentificationError::invalid_configuration(format!("识别配置格式错误 {}: {}", path.display(), e))
+        })
+    }
+}
 
 /// 行类型的完整定义，包含识别规则和元数据
+#[derive(Clone)]
 pub struct RowTypeDefinition {
     /// 行类型
     pub row_type: RowType,
@@ -39,6 +80,7 @@ impl RowTypeDefinition {
 }
 
 /// 文件类型配置，定义特定文件类型的所有行类型和识别规则
+#[derive(Clone)]
 pub struct FileTypeProfile {
     /// 配置名称
     pub name: String,
@@ -46,6 +88,8 @@ pub struct FileTypeProfile {
     pub description: String,
     /// 行类型定义列表 (按优先级排序)
     pub row_type_definitions: Vec<RowTypeDefinition>,
+    /// 没有规则匹配时的行类型（默认Unknown）
+    pub default_row_type: RowType,
 }
 
 impl FileTypeProfile {
@@ -55,17 +99,83 @@ impl FileTypeProfile {
             name,
             description,
             row_type_definitions: Vec::new(),
+            default_row_type: RowType::Unknown,
         }
     }
 
+    /// 设置没有规则匹配时的行类型，例如把未匹配的行都视为数据行
+    pub fn with_default_row_type(mut self, row_type: RowType) -> Self {
+        self.default_row_type = row_type;
+        self
+    }
+
     /// 添加行类型定义
     pub fn add_row_type(&mut self, definition: RowTypeDefinition) {
         self.row_type_definitions.push(definition);
-        // 按优先级排序（高优先级在前）
+        self.sort_by_priority();
+    }
+
+    /// 按优先级排序（高优先级在前，同优先级保持原有顺序）
+    fn sort_by_priority(&mut self) {
         self.row_type_definitions
             .sort_by(|a, b| b.priority.cmp(&a.priority));
     }
 
+    /// 调整行类型定义的顺序
+    ///
+    /// 将位于 `from` 的定义移动到 `to`，然后把原有的优先级取值
+    /// 按新顺序重新分配（保持降序），再按优先级重新排序。
+    /// 索引越界时不做任何修改。
+    pub fn move_row_type(&mut self, from: usize, to: usize) {
+        let len = self.row_type_definitions.len();
+        if from >= len || to >= len || from == to {
+            return;
+        }
+
+        let mut priorities: Vec<u8> = self
+            .row_type_definitions
+            .iter()
+            .map(|def| def.priority)
+            .collect();
+        priorities.sort_by(|a, b| b.cmp(a));
+
+        let definition = self.row_type_definitions.remove(from);
+        self.row_type_definitions.insert(to, definition);
+
+        for (definition, priority) in self.row_type_definitions.iter_mut().zip(priorities) {
+            definition.priority = priority;
+        }
+        self.sort_by_priority();
+    }
+
+    /// 当前的识别顺序（规则名称列表，优先级从高到低）
+    pub fn row_type_order(&self) -> Vec<String> {
+        self.row_type_definitions
+            .iter()
+            .map(|def| def.rule.name().to_string())
+            .collect()
+    }
+
+    /// 按保存的规则名称顺序重新排列行类型定义
+    ///
+    /// 未出现在 `order` 中的定义保持相对顺序排在后面，未知名称会被忽略。
+    pub fn apply_row_type_order(&mut self, order: &[String]) {
+        for (target, name) in order.iter().enumerate() {
+            if target >= self.row_type_definitions.len() {
+                break;
+            }
+            if let Some(from) = self
+                .row_type_definitions
+                .iter()
+                .position(|def| def.rule.name() == name)
+            {
+                if from >= target {
+                    self.move_row_type(from, target);
+                }
+            }
+        }
+    }
+
     /// 验证配置
     pub fn validate(&self) -> Result<(), String> {
         if self.row_type_definitions.is_empty() {
@@ -84,7 +194,7 @@ impl FileTypeProfile {
     /// 2. 柜号行 - 灰蓝色背景 RGB(127, 150, 152) 或 B列包含"柜号:"
     /// 3. 表头行 - 浅灰色背景 RGB(217, 217, 217)
     /// 4. 合计行 - 浅粉色背景 RGB(255, 204, 253)，区分"单台合计"和"小计"
-    /// 5. 数据行 - 白色背景或无背景色
+    /// 5. 数据行 - 白色背景或无背景色；其余有内容的行兜底视为数据行（置信度较低）
     pub fn cargo_analysis() -> Self {
         let mut profile = Self::new(
             "cargo_analysis".to_string(),
@@ -248,6 +358,20 @@ impl FileTypeProfile {
             1,
         ));
 
+        // 8. 数据行兜底 (优先级0)
+        // 带浅色底纹等未预期颜色的数据行不依赖颜色识别；只看数据列，
+        // 标题、备注等只在A列有文字的行仍归为未知
+        profile.add_row_type(RowTypeDefinition::new(
+            RowType::Data,
+            "数据行（兜底）".to_string(),
+            "其他规则均不匹配但名称、数量或价格列有内容的行".to_string(),
+            Box::new(
+                DefaultRule::new("non_empty_fallback".to_string())
+                    .with_columns(CARGO_DATA_COLUMNS.to_vec()),
+            ),
+            0,
+        ));
+
         profile
     }
 
@@ -273,12 +397,250 @@ impl FileTypeProfile {
 
         profile
     }
+
+    /// 按名称获取内置配置
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "cargo_analysis" => Some(Self::cargo_analysis()),
+            "auxiliary_material" => Some(Self::auxiliary_material()),
+            _ => None,
+        }
+    }
+
+    /// 读取 TOML 配置文件，在其指定的内置配置上应用识别顺序和默认行类型
+    pub fn load_file(path: &Path) -> IdentificationResult<Self> {
+        let file = ProfileFile::read(path)?;
+
+        let mut profile = Self::builtin(&file.base).ok_or_else(|| {
+            IdentificationError::invalid_configuration(format!("未知的内置识别配置: {}", file.base))
+        })?;
+        if let Some(name) = file.name {
+            profile.name = name;
+        }
+        profile.apply_row_type_order(&file.row_type_order);
+        if let Some(label) = file.default_row_type {
+            profile.default_row_type = RowType::from_label(&label);
+        }
+        Ok(profile)
+    }
+
+    /// 把识别顺序写回 TOML 配置文件，文件中的其他设置保持不变（注释不会保留）
+    pub fn save_row_type_order(path: &Path, order: &[String]) -> IdentificationResult<()> {
+        let mut file = ProfileFile::read(path)?;
+        file.row_type_order = order.to_vec();
+        let text = toml::to_string(&file).map_err(|e| {
+            IdentificationError::invalid_configuration(format!("无法生成识别配置 {}: {}", path.display(), e))
+        })?;
+        std::fs::write(path, text).map_err(|e| {
+            IdentificationError::invalid_configuration(format!("无法写入识别配置 {}: {}", path.display(), e))
+        })
+    }
+
+    /// 按内置配置名称或配置文件路径获取配置
+    pub fn resolve(spec: &str) -> IdentificationResult<Self> {
+        let spec = spec.trim();
+        match Self::builtin(spec) {
+            Some(profile) => Ok(profile),
+            None => Self::load_file(Path::new(spec)),
+        }
+    }
+
+    /// 按设置中指定的配置获取识别配置，未指定或无法加载时使用内置的货物分析配置
+    ///
+    /// 与处理器创建时的规则一致，界面中的预览、验证和对比也应通过它获取配置。
+    pub fn resolve_or_builtin(spec: Option<&str>) -> Self {
+        match spec {
+            Some(spec) => Self::resolve(spec).unwrap_or_else(|e| {
+                crate::log_warning!("识别配置 {} 加载失败，使用内置配置: {}", spec, e);
+                Self::cargo_analysis()
+            }),
+            None => Self::cargo_analysis(),
+        }
+    }
+}
+
+/// 配置来源是 TOML 文件时返回其路径，内置配置名称返回 None
+fn profile_file_path(spec: Option<&str>) -> Option<&Path> {
+    spec.map(str::trim)
+        .filter(|spec| FileTypeProfile::builtin(spec).is_none())
+        .map(Path::new)
+}
+
+fn file_modified(path: Option<&Path>) -> Option<SystemTime> {
+    std::fs::metadata(path?).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// 按设置加载的识别配置，记录配置来源，来源改变时重新加载
+///
+/// 界面每帧都需要识别配置，缓存加载结果避免反复读取配置文件。
+/// 配置来源是 TOML 文件时同时记录文件的修改时间，用户在外部编辑文件后
+/// 通过 [`is_stale`](Self::is_stale) 发现变化，再用 [`reload`](Self::reload) 重新读取。
+pub struct LoadedProfile {
+    spec: Option<String>,
+    modified: Option<SystemTime>,
+    profile: FileTypeProfile,
+}
+
+impl LoadedProfile {
+    pub fn load(spec: Option<&str>) -> Self {
+        Self {
+            spec: spec.map(str::to_string),
+            modified: file_modified(profile_file_path(spec)),
+            profile: FileTypeProfile::resolve_or_builtin(spec),
+        }
+    }
+
+    /// 重新读取配置来源，无需重启程序即可使用修改后的配置文件
+    pub fn reload(&mut self) {
+        *self = Self::load(self.spec.as_deref());
+    }
+
+    /// 配置文件在加载后是否被修改过（内置配置永远不会过期）
+    pub fn is_stale(&self) -> bool {
+        let path = self.file_path();
+        path.is_some() && file_modified(path) != self.modified
+    }
+
+    /// 是否由指定的配置来源加载
+    pub fn is_for(&self, spec: Option<&str>) -> bool {
+        self.spec.as_deref() == spec
+    }
+
+    pub fn spec(&self) -> Option<&str> {
+        self.spec.as_deref()
+    }
+
+    pub fn profile(&self) -> &FileTypeProfile {
+        &self.profile
+    }
+
+    /// 配置来源是 TOML 文件时返回其路径，内置配置没有可写回的文件
+    pub fn file_path(&self) -> Option<&Path> {
+        profile_file_path(self.spec.as_deref())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_resolve_builtin_and_file_profiles() {
+        assert_eq!(FileTypeProfile::resolve("auxiliary_material").unwrap().name, "auxiliary_material");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tuned.toml");
+        let last_rule = FileTypeProfile::cargo_analysis().row_type_order().pop().unwrap();
+        std::fs::write(
+            &path,
+            format!(
+                "base = \"cargo_analysis\"\nname = \"调整后的配置\"\nrow_type_order = [\"{}\"]\ndefault_row_type = \"数据行\"\n",
+                last_rule
+            ),
+        )
+        .unwrap();
+
+        let profile = FileTypeProfile::resolve(path.to_str().unwrap()).unwrap();
+        assert_eq!(profile.name, "调整后的配置");
+        assert_eq!(profile.row_type_order()[0], last_rule);
+        assert_eq!(profile.default_row_type, RowType::Data);
+
+        assert!(FileTypeProfile::resolve(dir.path().join("missing.toml").to_str().unwrap()).is_err());
+        std::fs::write(&path, "base = \"unknown\"").unwrap();
+        assert!(FileTypeProfile::load_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_loaded_profile_uses_configured_spec() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tuned.toml");
+        std::fs::write(&path, "base = \"cargo_analysis\"\nname = \"调整后的配置\"\n").unwrap();
+        let spec = path.to_str().unwrap();
+
+        let loaded = LoadedProfile::load(Some(spec));
+        assert_eq!(loaded.profile().name, "调整后的配置");
+        assert!(loaded.is_for(Some(spec)));
+        assert!(!loaded.is_for(None));
+
+        // 未指定或无法加载时与处理器一样回退到内置配置
+        let builtin = FileTypeProfile::cargo_analysis().name;
+        assert_eq!(LoadedProfile::load(None).profile().name, builtin);
+        let missing = dir.path().join("missing.toml");
+        assert_eq!(FileTypeProfile::resolve_or_builtin(missing.to_str()).name, builtin);
+    }
+
+    #[test]
+    fn test_reload_modified_profile_changes_results() {
+        use crate::engine::RowTypeIdentifier;
+        use crate::models::{CellData, CellStyle, RowData};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tuned.toml");
+        std::fs::write(&path, "base = \"cargo_analysis\"\n").unwrap();
+        let mut loaded = LoadedProfile::load(path.to_str());
+        assert!(!loaded.is_stale());
+
+        // 只在A列有说明文字的备注行没有规则匹配
+        let note = RowData::new(
+            0,
+            vec![CellData {
+                column_index: 0,
+                content: "备注：以上价格均含税".to_string(),
+                style: CellStyle {
+                    background_color: Some(RgbColor::new(255, 242, 204)),
+                    ..Default::default()
+                },
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            }],
+        );
+        let identify = |loaded: &LoadedProfile| {
+            RowTypeIdentifier::new(loaded.profile().clone()).identify_row(&note).row_type
+        };
+        assert_eq!(identify(&loaded), RowType::Unknown);
+
+        // 外部修改配置文件后发现变化，重新加载后按新配置识别
+        std::fs::write(&path, "base = \"cargo_analysis\"\ndefault_row_type = \"数据行\"\n").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + std::time::Duration::from_secs(10))
+            .unwrap();
+        assert!(loaded.is_stale());
+        assert_eq!(identify(&loaded), RowType::Unknown);
+
+        loaded.reload();
+        assert!(!loaded.is_stale());
+        assert_eq!(identify(&loaded), RowType::Data);
+
+        // 内置配置没有文件，不会过期
+        assert!(!LoadedProfile::load(Some("cargo_analysis")).is_stale());
+    }
+
+    #[test]
+    fn test_save_row_type_order_to_profile_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tuned.toml");
+        std::fs::write(&path, "base = \"cargo_analysis\"\nname = \"调整后的配置\"\ndefault_row_type = \"数据行\"\n").unwrap();
+
+        let mut order = FileTypeProfile::cargo_analysis().row_type_order();
+        order.reverse();
+        FileTypeProfile::save_row_type_order(&path, &order).unwrap();
+
+        // 写回后重新加载得到新的顺序，其他设置不变
+        let profile = FileTypeProfile::load_file(&path).unwrap();
+        assert_eq!(profile.row_type_order(), order);
+        assert_eq!(profile.name, "调整后的配置");
+        assert_eq!(profile.default_row_type, RowType::Data);
+
+        let spec = path.to_str().unwrap();
+        assert_eq!(LoadedProfile::load(Some(spec)).file_path(), Some(path.as_path()));
+        assert_eq!(LoadedProfile::load(Some("cargo_analysis")).file_path(), None);
+        assert_eq!(LoadedProfile::load(None).file_path(), None);
+    }
+
     #[test]
     fn test_cargo_analysis_profile_creation() {
         let profile = FileTypeProfile::cargo_analysis();
@@ -286,13 +648,40 @@ mod tests {
         assert_eq!(profile.name, "cargo_analysis");
         assert_eq!(profile.description, "货物分析表配置");
 
-        // 应该有7种行类型
-        assert_eq!(profile.row_type_definitions.len(), 7);
+        // 7种行类型定义加上数据行兜底
+        assert_eq!(profile.row_type_definitions.len(), 8);
 
         // 验证配置
         assert!(profile.validate().is_ok());
     }
 
+    #[test]
+    fn test_note_row_is_not_data() {
+        use crate::engine::RowTypeIdentifier;
+        use crate::models::{CellData, CellStyle, RowData};
+
+        let cell = |column_index: usize, content: &str| CellData {
+            column_index,
+            content: content.to_string(),
+            style: CellStyle {
+                background_color: Some(RgbColor::new(255, 242, 204)),
+                ..Default::default()
+            },
+            merge_info: None,
+            comment: None,
+            raw_content: None,
+        };
+        let identifier = RowTypeIdentifier::new(FileTypeProfile::cargo_analysis());
+
+        // 只在A列有说明文字的备注行保持未知
+        let note = RowData::new(20, vec![cell(0, "备注：以上价格均含税，运费另计"), cell(1, ""), cell(2, "")]);
+        assert_eq!(identifier.identify_row(&note).row_type, RowType::Unknown);
+
+        // 同样底色但数量列有内容的行仍兜底为数据行
+        let data = RowData::new(21, vec![cell(0, ""), cell(1, ""), cell(2, "12")]);
+        assert_eq!(identifier.identify_row(&data).row_type, RowType::Data);
+    }
+
     #[test]
     fn test_profile_priority_sorting() {
         let profile = FileTypeProfile::cargo_analysis();
@@ -364,4 +753,36 @@ mod tests {
         assert_eq!(profile.row_type_definitions[0].priority, 5);
         assert_eq!(profile.row_type_definitions[1].priority, 3);
     }
+
+    #[test]
+    fn test_move_row_type_updates_priority() {
+        let mut profile = FileTypeProfile::cargo_analysis();
+        let last = profile.row_type_definitions.len() - 1;
+        assert_eq!(profile.row_type_definitions[last].row_type, RowType::Data);
+
+        // 把数据行移到最前面
+        profile.move_row_type(last, 0);
+
+        assert_eq!(profile.row_type_definitions[0].row_type, RowType::Data);
+        assert_eq!(profile.row_type_definitions[0].priority, 9);
+        assert_eq!(profile.row_type_definitions[1].row_type, RowType::ProjectNumber);
+        assert_eq!(profile.row_type_definitions[1].priority, 8);
+        assert_eq!(profile.row_type_definitions[last].priority, 0);
+
+        // 仍然保持降序
+        let priorities: Vec<u8> = profile.row_type_definitions.iter().map(|d| d.priority).collect();
+        assert!(priorities.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn test_apply_row_type_order_round_trip() {
+        let mut edited = FileTypeProfile::cargo_analysis();
+        edited.move_row_type(4, 3);
+        let order = edited.row_type_order();
+
+        let mut restored = FileTypeProfile::cargo_analysis();
+        restored.apply_row_type_order(&order);
+
+        assert_eq!(restored.row_type_order(), order);
+    }
 }
@@ -0,0 +1,59 @@
+// Markdown 导出
+use crate::models::RowIdentificationResult;
+
+/// 将识别结果渲染为 GitHub 风格的 Markdown 表格
+///
+/// 行号按 Excel 习惯从1开始显示，数值列右对齐，文本列左对齐。
+pub fn to_markdown_table(results: &[RowIdentificationResult]) -> String {
+    let mut table = String::from("| 行号 | 行类型 | 匹配规则 | 置信度 |\n");
+    table.push_str("| ---: | :--- | :--- | ---: |\n");
+
+    for result in results {
+        table.push_str(&format!(
+            "| {} | {} | {} | {:.2} |\n",
+            result.row_index + 1,
+            escape_cell(result.row_type.display_name()),
+            escape_cell(&result.matched_rule),
+            result.confidence
+        ));
+    }
+
+    table
+}
+
+/// 转义单元格中会破坏表格结构的字符
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RowType;
+
+    #[test]
+    fn test_to_markdown_table() {
+        let results = vec![
+            RowIdentificationResult {
+                row_index: 0,
+                row_type: RowType::Header,
+                matched_rule: "gray_background".to_string(),
+                confidence: 1.0,
+            },
+            RowIdentificationResult {
+                row_index: 1,
+                row_type: RowType::Data,
+                matched_rule: "a|b".to_string(),
+                confidence: 0.5,
+            },
+        ];
+
+        let table = to_markdown_table(&results);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines[0], "| 行号 | 行类型 | 匹配规则 | 置信度 |");
+        assert_eq!(lines[1], "| ---: | :--- | :--- | ---: |");
+        assert_eq!(lines[2], "| 1 | 表头行 | gray_background | 1.00 |");
+        assert_eq!(lines[3], "| 2 | 数据行 | a\\|b | 0.50 |");
+    }
+}
@@ -6,14 +6,29 @@ pub mod file_type_profile;
 pub mod row_identifier;
 pub mod excel_extractor;
 pub mod identification_error;
+pub mod output_name;
+pub mod markdown;
+pub mod sample_verifier;
+pub mod text_normalization;
+pub mod duplicates;
+pub mod sheet_compare;
 
 pub use recognition_rule::RecognitionRule;
 pub use rules::{
-    ColorRule, TextPattern, TextPatternRule, 
+    ColorMatchMode, ColorRule, FontColorRule, TextPattern, TextPatternRule, 
     MergeRequirement, MergeStateRule,
-    CompositeLogic, CompositeRule,
+    CompositeLogic, CompositeRule, NotRule,
+    SequenceIndexRule, HasCommentRule, DominantColorRule, DefaultRule, MergedTextRule, MultiColorRule, NumericRangeRule,
 };
-pub use file_type_profile::{FileTypeProfile, RowTypeDefinition};
+pub use file_type_profile::{FileTypeProfile, LoadedProfile, RowTypeDefinition};
 pub use row_identifier::RowTypeIdentifier;
-pub use excel_extractor::ExcelExtractor;
+pub use excel_extractor::{ExcelExtractor, ExtractOptions};
 pub use identification_error::{IdentificationError, IdentificationResult};
+pub use output_name::{
+    format_output_name, next_available_path, validate_output_name_pattern, ConflictStrategy, OutputNameContext,
+};
+pub use markdown::to_markdown_table;
+pub use text_normalization::{parse_localized_number, TextNormalization};
+pub use duplicates::{find_duplicate_rows, find_duplicate_rows_df, remove_duplicate_rows, DuplicateReport};
+pub use sheet_compare::{compare_files, compare_worksheets, write_diff_workbook, CellChange, CellChangeKind, SheetComparison};
+pub use sample_verifier::{verify_samples, RowMismatch, SampleReport, VerificationReport};
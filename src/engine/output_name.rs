@@ -0,0 +1,274 @@
+// Output File Name Pattern
+use crate::error::{AppError, Result};
+use chrono::Local;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// 输出文件已存在时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictStrategy {
+    /// 直接覆盖已有文件
+    #[default]
+    Overwrite,
+    /// 自动追加序号，如 `result (1).xlsx`
+    AppendSuffix,
+}
+
+impl ConflictStrategy {
+    pub const ALL: [ConflictStrategy; 2] = [ConflictStrategy::Overwrite, ConflictStrategy::AppendSuffix];
+
+    /// 保存在配置选项中的值
+    pub fn key(&self) -> &'static str {
+        match self {
+            ConflictStrategy::Overwrite => "overwrite",
+            ConflictStrategy::AppendSuffix => "append_suffix",
+        }
+    }
+
+    /// 解析配置选项中的值，无法识别时使用默认策略
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|s| s.key() == key)
+            .unwrap_or_default()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictStrategy::Overwrite => "覆盖已有文件",
+            ConflictStrategy::AppendSuffix => "自动追加序号",
+        }
+    }
+
+    /// 按策略得到最终写入的路径
+    pub fn resolve(&self, path: PathBuf) -> PathBuf {
+        self.resolve_with(path, |p| p.exists())
+    }
+
+    /// 按策略得到最终写入的路径，并跳过 `reserved` 中已分配给其他文件的路径
+    ///
+    /// 并行处理在写出前为所有文件分配路径，此时前面的文件尚未写出，
+    /// 只检查文件是否存在会让模式不含 `{name}` 的文件分到同一个路径。
+    /// 返回的路径会加入 `reserved`。
+    pub fn resolve_reserved(&self, path: PathBuf, reserved: &mut HashSet<PathBuf>) -> PathBuf {
+        let resolved = self.resolve_with(path, |p| p.exists() || reserved.contains(p));
+        reserved.insert(resolved.clone());
+        resolved
+    }
+
+    fn resolve_with(&self, path: PathBuf, taken: impl Fn(&Path) -> bool) -> PathBuf {
+        match self {
+            ConflictStrategy::Overwrite => path,
+            ConflictStrategy::AppendSuffix => {
+                if !taken(&path) {
+                    return path;
+                }
+                let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+                let stem = path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let ext = path
+                    .extension()
+                    .map(|e| e.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                next_free_path(&dir, &stem, &ext, taken)
+            }
+        }
+    }
+}
+
+/// 在目录中找到第一个不存在的文件路径
+///
+/// 依次尝试 `stem.ext`、`stem (1).ext`、`stem (2).ext`……；`ext` 不含点，为空时不加扩展名。
+pub fn next_available_path(dir: &Path, stem: &str, ext: &str) -> PathBuf {
+    next_free_path(dir, stem, ext, |p| p.exists())
+}
+
+/// 同 [`next_available_path`]，由 `taken` 判断路径是否已被占用
+fn next_free_path(dir: &Path, stem: &str, ext: &str, taken: impl Fn(&Path) -> bool) -> PathBuf {
+    let file_name = |suffix: String| {
+        if ext.is_empty() {
+            format!("{}{}", stem, suffix)
+        } else {
+            format!("{}{}.{}", stem, suffix, ext)
+        }
+    };
+
+    let mut candidate = dir.join(file_name(String::new()));
+    let mut n = 1;
+    while taken(&candidate) {
+        candidate = dir.join(file_name(format!(" ({})", n)));
+        n += 1;
+    }
+    candidate
+}
+
+/// 输出文件名模式中可用的上下文信息
+#[derive(Debug, Clone)]
+pub struct OutputNameContext {
+    /// 原始文件名（不含扩展名）
+    pub name: String,
+    /// 日期（YYYYMMDD）
+    pub date: String,
+    /// 处理器 ID
+    pub processor: String,
+}
+
+impl OutputNameContext {
+    /// 根据输入文件创建上下文，日期取当前本地日期
+    pub fn from_input(input_path: &Path, processor_id: &str) -> Self {
+        Self {
+            name: input_path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "unknown".to_string()),
+            date: Local::now().format("%Y%m%d").to_string(),
+            processor: processor_id.to_string(),
+        }
+    }
+}
+
+/// 展开输出文件名模式
+///
+/// 支持的占位符：
+/// - `{name}` - 原始文件名（不含扩展名）
+/// - `{date}` - 处理日期（YYYYMMDD）
+/// - `{processor}` - 处理器 ID
+///
+/// 未知的占位符原样保留。结果始终是单个文件名：路径分隔符等文件名中不允许的字符
+/// （无论来自模式本身还是占位符的值）替换为 `_`，输出不会落到输出目录之外。
+///
+/// # 示例
+///
+/// `{date}_{name}.xlsx` + `货物分析表.xlsx` → `20240101_货物分析表.xlsx`
+pub fn format_output_name(pattern: &str, ctx: &OutputNameContext) -> String {
+    let name = pattern
+        .replace("{name}", &ctx.name)
+        .replace("{date}", &ctx.date)
+        .replace("{processor}", &ctx.processor);
+    sanitize_file_name(&name)
+}
+
+/// 把文件名中不允许的字符替换为 `_`，`.`、`..` 和空名称整体替换为 `_`
+fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    match sanitized.trim() {
+        "" | "." | ".." => "_".to_string(),
+        _ => sanitized,
+    }
+}
+
+/// 校验输出文件名模式
+///
+/// 不含 `{name}` 的模式会让批量中的每个文件得到同一个输出名，
+/// 只有冲突策略为追加序号时才允许，否则后处理的文件会覆盖先前的输出。
+pub fn validate_output_name_pattern(pattern: &str, strategy: ConflictStrategy) -> Result<()> {
+    if !pattern.contains("{name}") && strategy != ConflictStrategy::AppendSuffix {
+        return Err(AppError::config_error(format!(
+            "输出文件名模式 \"{}\" 不含 {{name}}，批量处理时所有文件会写到同一个输出文件；请加入 {{name}} 或将冲突策略设为「{}」",
+            pattern,
+            ConflictStrategy::AppendSuffix.label()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> OutputNameContext {
+        OutputNameContext {
+            name: "货物分析表".to_string(),
+            date: "20240315".to_string(),
+            processor: "cargo_analysis".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_output_name_patterns() {
+        let ctx = ctx();
+
+        assert_eq!(format_output_name("{name}_processed.xlsx", &ctx), "货物分析表_processed.xlsx");
+        assert_eq!(format_output_name("{date}_{name}.xlsx", &ctx), "20240315_货物分析表.xlsx");
+        assert_eq!(format_output_name("fixed.xlsx", &ctx), "fixed.xlsx");
+    }
+
+    #[test]
+    fn test_format_output_name_stays_in_output_dir() {
+        let mut ctx = ctx();
+
+        // 模式中的路径分隔符、上级目录和绝对路径都不会离开输出目录
+        assert_eq!(
+            format_output_name("{processor}/{name}-{date}.xlsx", &ctx),
+            "cargo_analysis_货物分析表-20240315.xlsx"
+        );
+        assert_eq!(format_output_name("../{name}.xlsx", &ctx), ".._货物分析表.xlsx");
+        assert_eq!(format_output_name("/etc/{name}", &ctx), "_etc_货物分析表");
+        assert_eq!(format_output_name("C:\\{name}.xlsx", &ctx), "C__货物分析表.xlsx");
+        assert_eq!(format_output_name("..", &ctx), "_");
+
+        // 占位符的值同样会被清理
+        ctx.processor = "../x".to_string();
+        assert_eq!(format_output_name("{processor}.xlsx", &ctx), ".._x.xlsx");
+        for name in ["{processor}/{name}.xlsx", "../{name}.xlsx", "/etc/{name}"] {
+            let file_name = format_output_name(name, &ctx);
+            assert_eq!(Path::new(&file_name).components().count(), 1, "{}", file_name);
+        }
+    }
+
+    #[test]
+    fn test_validate_output_name_pattern_requires_name() {
+        assert!(validate_output_name_pattern("{date}_{name}.xlsx", ConflictStrategy::Overwrite).is_ok());
+        assert!(validate_output_name_pattern("{date}.xlsx", ConflictStrategy::AppendSuffix).is_ok());
+
+        let err = validate_output_name_pattern("{date}.xlsx", ConflictStrategy::Overwrite).unwrap_err();
+        assert!(err.is_config_error());
+        assert!(err.to_string().contains("{name}"));
+    }
+
+    #[test]
+    fn test_next_available_path_skips_existing_numbers() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("result.xlsx"), b"").unwrap();
+        std::fs::write(dir.path().join("result (1).xlsx"), b"").unwrap();
+
+        assert_eq!(
+            next_available_path(dir.path(), "result", "xlsx"),
+            dir.path().join("result (2).xlsx")
+        );
+        assert_eq!(
+            next_available_path(dir.path(), "other", "xlsx"),
+            dir.path().join("other.xlsx")
+        );
+        assert_eq!(
+            ConflictStrategy::AppendSuffix.resolve(dir.path().join("result.xlsx")),
+            dir.path().join("result (2).xlsx")
+        );
+        assert_eq!(
+            ConflictStrategy::Overwrite.resolve(dir.path().join("result.xlsx")),
+            dir.path().join("result.xlsx")
+        );
+    }
+
+    #[test]
+    fn test_format_output_name_unknown_token() {
+        assert_eq!(format_output_name("{name}_{unknown}.xlsx", &ctx()), "货物分析表_{unknown}.xlsx");
+    }
+
+    #[test]
+    fn test_context_from_input() {
+        let ctx = OutputNameContext::from_input(Path::new("/data/a.b.xlsx"), "cargo_analysis");
+        assert_eq!(ctx.name, "a.b");
+        assert_eq!(ctx.processor, "cargo_analysis");
+        assert_eq!(ctx.date.len(), 8);
+    }
+}
@@ -50,6 +50,20 @@ pub trait RecognitionRule: Send + Sync {
     /// * `true` - 规则匹配，该行符合此规则定义的条件
     /// * `false` - 规则不匹配，该行不符合此规则定义的条件
     fn matches(&self, row_data: &RowData) -> bool;
+
+    /// 结合上下文评估规则是否匹配
+    ///
+    /// 部分规则需要参考前一个数据行才能判断（例如序号是否连续）。
+    /// 默认实现忽略上下文，直接调用 [`matches`](Self::matches)。
+    ///
+    /// # Arguments
+    ///
+    /// * `row_data` - 要评估的行数据
+    /// * `previous_data_row` - 之前最近一个被识别为数据行的行（如有）
+    fn matches_in_context(&self, row_data: &RowData, previous_data_row: Option<&RowData>) -> bool {
+        let _ = previous_data_row;
+        self.matches(row_data)
+    }
     
     /// 获取规则的置信度
     /// 
@@ -62,6 +76,34 @@ pub trait RecognitionRule: Send + Sync {
     fn confidence(&self) -> f32 {
         1.0
     }
+
+    /// 规则在上下文中匹配给定行时的置信度
+    ///
+    /// 部分规则的置信度取决于该行满足了哪些条件（例如按权重组合的规则），
+    /// 这些条件应与 [`matches_in_context`](Self::matches_in_context) 使用相同的上下文评估。
+    /// 默认实现与行无关，直接返回 [`confidence`](Self::confidence)。
+    fn confidence_for(&self, row_data: &RowData, previous_data_row: Option<&RowData>) -> f32 {
+        let _ = (row_data, previous_data_row);
+        self.confidence()
+    }
+
+    /// 说明规则对该行检查了什么（哪个单元格、实际的颜色或文本）
+    ///
+    /// 用于识别过程说明日志。默认实现只给出规则名称，
+    /// 基于单元格内容或颜色的规则应返回实际检查的值。
+    fn explain(&self, row_data: &RowData) -> String {
+        let _ = row_data;
+        format!("规则 {}", self.name())
+    }
+
+    /// 在上下文中说明规则对该行检查了什么
+    ///
+    /// 组合类规则用它按 [`matches_in_context`](Self::matches_in_context) 的结果标注子规则。
+    /// 默认实现忽略上下文，直接调用 [`explain`](Self::explain)。
+    fn explain_in_context(&self, row_data: &RowData, previous_data_row: Option<&RowData>) -> String {
+        let _ = previous_data_row;
+        self.explain(row_data)
+    }
     
     /// 克隆规则到Box中
     /// 
@@ -1,5 +1,6 @@
 // Row Type Identifier - Main API
-use crate::engine::FileTypeProfile;
+use crate::engine::{FileTypeProfile, RowTypeDefinition};
+use std::collections::HashMap;
 use crate::models::{
     IdentificationStatistics, RowData, RowIdentificationResult, RowType, WorksheetData,
 };
@@ -10,6 +11,10 @@ pub struct RowTypeIdentifier {
     profile: FileTypeProfile,
     /// 是否启用调试模式
     debug_mode: bool,
+    /// 是否把每行的规则评估过程写入日志面板
+    explain_mode: bool,
+    /// 接受匹配所需的最低置信度
+    min_confidence: f32,
 }
 
 impl RowTypeIdentifier {
@@ -22,6 +27,8 @@ impl RowTypeIdentifier {
         Self {
             profile,
             debug_mode: false,
+            explain_mode: false,
+            min_confidence: 0.0,
         }
     }
 
@@ -35,10 +42,41 @@ impl RowTypeIdentifier {
         self
     }
 
+    /// 启用识别过程说明
+    ///
+    /// 开启后每识别一行，都会按评估顺序把每条规则是否匹配及其检查的单元格、
+    /// 颜色或文本以调试级别写入日志，便于排查识别错误。逐行输出较多，默认关闭。
+    ///
+    /// # Arguments
+    ///
+    /// * `explain` - 是否启用识别过程说明
+    pub fn with_explain(mut self, explain: bool) -> Self {
+        self.explain_mode = explain;
+        self
+    }
+
+    /// 获取当前使用的文件类型配置
+    pub fn profile(&self) -> &FileTypeProfile {
+        &self.profile
+    }
+
+    /// 设置接受匹配所需的最低置信度
+    ///
+    /// 置信度低于该阈值的规则即使匹配也视为不匹配，
+    /// 交由后续定义继续判断，全部落空时返回Unknown。
+    ///
+    /// # Arguments
+    ///
+    /// * `min_confidence` - 最低置信度，范围[0.0, 1.0]
+    pub fn with_min_confidence(mut self, min_confidence: f32) -> Self {
+        self.min_confidence = min_confidence.clamp(0.0, 1.0);
+        self
+    }
+
     /// 识别单行的类型
     ///
     /// 该方法遍历所有行类型定义，按优先级顺序评估规则，
-    /// 返回第一个匹配的行类型。
+    /// 返回第一个匹配且置信度不低于阈值的行类型。
     ///
     /// # Arguments
     ///
@@ -48,9 +86,33 @@ impl RowTypeIdentifier {
     ///
     /// 识别结果，包含行类型、匹配的规则名称和置信度
     pub fn identify_row(&self, row_data: &RowData) -> RowIdentificationResult {
+        self.identify_row_in_context(row_data, None)
+    }
+
+    /// 结合上下文识别单行的类型
+    ///
+    /// 与 [`identify_row`](Self::identify_row) 相同，但会把之前最近的数据行
+    /// 传给规则，供需要上下文的规则（如序号连续性检查）使用。
+    ///
+    /// # Arguments
+    ///
+    /// * `row_data` - 行数据
+    /// * `previous_data_row` - 之前最近一个被识别为数据行的行（如有）
+    pub fn identify_row_in_context(
+        &self,
+        row_data: &RowData,
+        previous_data_row: Option<&RowData>,
+    ) -> RowIdentificationResult {
         if self.debug_mode {
             println!("Identifying row {}", row_data.row_index);
         }
+        if self.explain_mode {
+            crate::log_debug!(
+                "识别第 {} 行: 按优先级评估 {} 条规则",
+                row_data.row_index + 1,
+                self.profile.row_type_definitions.len()
+            );
+        }
 
         // 遍历所有行类型定义（已按优先级排序）
         for definition in &self.profile.row_type_definitions {
@@ -64,35 +126,88 @@ impl RowTypeIdentifier {
             }
 
             // 评估规则
-            if definition.rule.matches(row_data) {
+            if definition.rule.matches_in_context(row_data, previous_data_row) {
+                let confidence = definition.rule.confidence_for(row_data, previous_data_row);
+
+                // 置信度不足时视为不匹配，交给下一个定义
+                if confidence < self.min_confidence {
+                    self.explain_rule(
+                        row_data,
+                        previous_data_row,
+                        definition,
+                        &format!("✗ 匹配但置信度 {:.2} 低于阈值 {:.2}", confidence, self.min_confidence),
+                    );
+                    if self.debug_mode {
+                        println!(
+                            "    ✗ Rule matched but confidence {:.2} is below threshold {:.2}",
+                            confidence, self.min_confidence
+                        );
+                    }
+                    continue;
+                }
+
                 if self.debug_mode {
                     println!("    ✓ Rule matched!");
                 }
+                self.explain_rule(row_data, previous_data_row, definition, "✓ 匹配");
+                if self.explain_mode {
+                    crate::log_debug!("  → {}", definition.row_type.display_name());
+                }
 
                 return RowIdentificationResult {
                     row_index: row_data.row_index,
                     row_type: definition.row_type.clone(),
                     matched_rule: definition.rule.name().to_string(),
-                    confidence: definition.rule.confidence(),
+                    confidence,
                 };
-            } else if self.debug_mode {
-                println!("    ✗ Rule did not match");
+            } else {
+                self.explain_rule(row_data, previous_data_row, definition, "✗ 不匹配");
+                if self.debug_mode {
+                    println!("    ✗ Rule did not match");
+                }
             }
         }
 
-        // 没有规则匹配，返回Unknown类型
+        // 没有规则匹配，返回配置的默认类型（默认为Unknown）
         if self.debug_mode {
-            println!("  No rules matched, returning Unknown");
+            println!("  No rules matched, returning {:?}", self.profile.default_row_type);
+        }
+        if self.explain_mode {
+            crate::log_debug!(
+                "  → 没有规则匹配，归为 {}",
+                self.profile.default_row_type.display_name()
+            );
         }
 
         RowIdentificationResult {
             row_index: row_data.row_index,
-            row_type: RowType::Unknown,
-            matched_rule: "none".to_string(),
+            row_type: self.profile.default_row_type.clone(),
+            matched_rule: RowIdentificationResult::UNMATCHED_RULE.to_string(),
             confidence: 0.0,
         }
     }
 
+    /// 识别过程说明模式下记录单条规则的评估结果
+    fn explain_rule(
+        &self,
+        row_data: &RowData,
+        previous_data_row: Option<&RowData>,
+        definition: &RowTypeDefinition,
+        outcome: &str,
+    ) {
+        if !self.explain_mode {
+            return;
+        }
+        crate::log_debug!(
+            "  [优先级 {}] {} ({}): {} — {}",
+            definition.priority,
+            definition.rule.name(),
+            definition.row_type.display_name(),
+            outcome,
+            definition.rule.explain_in_context(row_data, previous_data_row)
+        );
+    }
+
     /// 批量识别所有行的类型
     ///
     /// # Arguments
@@ -111,11 +226,18 @@ impl RowTypeIdentifier {
             );
         }
 
-        worksheet_data
-            .rows
-            .iter()
-            .map(|row| self.identify_row(row))
-            .collect()
+        let mut previous_data_row: Option<&RowData> = None;
+        let mut results = Vec::with_capacity(worksheet_data.rows.len());
+
+        for row in &worksheet_data.rows {
+            let result = self.identify_row_in_context(row, previous_data_row);
+            if result.row_type == RowType::Data {
+                previous_data_row = Some(row);
+            }
+            results.push(result);
+        }
+
+        results
     }
 
     /// 获取识别统计信息
@@ -131,6 +253,31 @@ impl RowTypeIdentifier {
         IdentificationStatistics::from_results(results)
     }
 
+    /// 统计每个行类型定义的规则命中的行数
+    ///
+    /// 以规则名称为键，包含所有定义（从未命中的规则计数为0），
+    /// 用于调整配置：从不命中的规则通常意味着配置有误。
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - `identify_all_rows` 返回的识别结果
+    pub fn rule_match_counts(&self, results: &[RowIdentificationResult]) -> HashMap<String, usize> {
+        let mut counts: HashMap<String, usize> = self
+            .profile
+            .row_type_definitions
+            .iter()
+            .map(|def| (def.rule.name().to_string(), 0))
+            .collect();
+
+        for result in results {
+            if let Some(count) = counts.get_mut(&result.matched_rule) {
+                *count += 1;
+            }
+        }
+
+        counts
+    }
+
     /// 检查是否有多个相同优先级的规则匹配同一行
     ///
     /// 该方法用于调试和验证配置，会记录警告信息
@@ -216,6 +363,8 @@ mod tests {
                     start_row: 11,
                     end_row: 11,
                 }),
+                comment: None,
+                raw_content: None,
             }],
         };
 
@@ -239,6 +388,8 @@ mod tests {
                     ..Default::default()
                 },
                 merge_info: None,
+                comment: None,
+                raw_content: None,
             }],
         };
 
@@ -261,6 +412,8 @@ mod tests {
                     ..Default::default()
                 },
                 merge_info: None,
+                comment: None,
+                raw_content: None,
             }],
         };
 
@@ -273,16 +426,19 @@ mod tests {
         let profile = FileTypeProfile::cargo_analysis();
         let identifier = RowTypeIdentifier::new(profile);
 
+        // 数据列有内容的行会被兜底规则识别为数据行，只有底色、没有内容的行才是Unknown
         let row = RowData {
             row_index: 0,
             cells: vec![CellData {
                 column_index: 0,
-                content: "Test".to_string(),
+                content: String::new(),
                 style: CellStyle {
                     background_color: Some(RgbColor::new(100, 100, 100)), // 未定义的颜色
                     ..Default::default()
                 },
                 merge_info: None,
+                comment: None,
+                raw_content: None,
             }],
         };
 
@@ -291,6 +447,45 @@ mod tests {
         assert_eq!(result.confidence, 0.0);
     }
 
+    #[test]
+    fn test_tinted_data_row_falls_back_to_data() {
+        let identifier = RowTypeIdentifier::new(FileTypeProfile::cargo_analysis());
+
+        // 接近白色的浅色底纹，白色规则不匹配
+        let row = RowData::new(
+            5,
+            vec![
+                CellData {
+                    column_index: 0,
+                    content: "3".to_string(),
+                    style: CellStyle {
+                        background_color: Some(RgbColor::new(250, 250, 240)),
+                        ..Default::default()
+                    },
+                    merge_info: None,
+                    comment: None,
+                    raw_content: None,
+                },
+                CellData {
+                    column_index: 1,
+                    content: "电缆".to_string(),
+                    style: CellStyle {
+                        background_color: Some(RgbColor::new(250, 250, 240)),
+                        ..Default::default()
+                    },
+                    merge_info: None,
+                    comment: None,
+                    raw_content: None,
+                },
+            ],
+        );
+
+        let result = identifier.identify_row(&row);
+        assert_eq!(result.row_type, RowType::Data);
+        assert_eq!(result.matched_rule, "non_empty_fallback");
+        assert!(result.confidence < 1.0);
+    }
+
     #[test]
     fn test_identify_all_rows() {
         let profile = FileTypeProfile::cargo_analysis();
@@ -309,6 +504,8 @@ mod tests {
                             ..Default::default()
                         },
                         merge_info: None,
+                        comment: None,
+                        raw_content: None,
                     }],
                 },
                 RowData {
@@ -318,6 +515,8 @@ mod tests {
                         content: "1".to_string(),
                         style: CellStyle::default(),
                         merge_info: None,
+                        comment: None,
+                        raw_content: None,
                     }],
                 },
             ],
@@ -329,6 +528,55 @@ mod tests {
         assert_eq!(results[1].row_type, RowType::Data);
     }
 
+    #[test]
+    fn test_rule_match_counts() {
+        let profile = FileTypeProfile::cargo_analysis();
+        let identifier = RowTypeIdentifier::new(profile);
+
+        let data_row = |row_index: usize, content: &str| {
+            RowData::new(
+                row_index,
+                vec![CellData {
+                    column_index: 0,
+                    content: content.to_string(),
+                    style: CellStyle::default(),
+                    merge_info: None,
+                    comment: None,
+                    raw_content: None,
+                }],
+            )
+        };
+        let worksheet = WorksheetData {
+            name: "Test".to_string(),
+            rows: vec![
+                RowData::new(
+                    0,
+                    vec![CellData {
+                        column_index: 0,
+                        content: "序号".to_string(),
+                        style: CellStyle {
+                            background_color: Some(RgbColor::new(217, 217, 217)),
+                            ..Default::default()
+                        },
+                        merge_info: None,
+                        comment: None,
+                        raw_content: None,
+                    }],
+                ),
+                data_row(1, "1"),
+                data_row(2, "2"),
+            ],
+        };
+
+        let results = identifier.identify_all_rows(&worksheet);
+        let counts = identifier.rule_match_counts(&results);
+
+        assert_eq!(counts.len(), 8);
+        assert_eq!(counts["gray_background"], 1);
+        assert_eq!(counts["white_or_no_background"], 2);
+        assert_eq!(counts["project_number_rule"], 0);
+    }
+
     #[test]
     fn test_get_statistics() {
         let profile = FileTypeProfile::cargo_analysis();
@@ -364,9 +612,123 @@ mod tests {
         let stats = identifier.get_statistics(&results);
         assert_eq!(stats.total_rows, 4);
         assert_eq!(stats.unknown_count, 1);
+        assert_eq!(stats.defaulted_count, 0);
         assert_eq!(stats.success_rate, 0.75);
     }
 
+    #[test]
+    fn test_unmatched_rows_use_profile_default_row_type() {
+        // 置信度不足导致唯一的规则不生效，行落入默认类型
+        let profile = single_rule_profile(0.4).with_default_row_type(RowType::Data);
+        let identifier = RowTypeIdentifier::new(profile).with_min_confidence(0.6);
+
+        let result = identifier.identify_row(&simple_row());
+        assert_eq!(result.row_type, RowType::Data);
+        assert!(result.is_unmatched());
+
+        let worksheet = WorksheetData {
+            name: "Sheet1".to_string(),
+            rows: vec![simple_row(), simple_row()],
+        };
+        let stats = identifier.get_statistics(&identifier.identify_all_rows(&worksheet));
+        assert_eq!(stats.unknown_count, 0);
+        assert_eq!(stats.defaulted_count, 2);
+        assert_eq!(stats.row_type_counts.get(&RowType::Data), Some(&2));
+        assert_eq!(stats.success_rate, 1.0);
+    }
+
+    /// 始终匹配但置信度可配置的测试规则
+    #[derive(Clone)]
+    struct FixedConfidenceRule {
+        name: String,
+        confidence: f32,
+    }
+
+    impl crate::engine::RecognitionRule for FixedConfidenceRule {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn matches(&self, _row_data: &RowData) -> bool {
+            true
+        }
+
+        fn confidence(&self) -> f32 {
+            self.confidence
+        }
+
+        fn clone_box(&self) -> Box<dyn crate::engine::RecognitionRule> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn single_rule_profile(confidence: f32) -> FileTypeProfile {
+        let mut profile = FileTypeProfile::new("test".to_string(), "test".to_string());
+        profile.add_row_type(crate::engine::RowTypeDefinition::new(
+            RowType::Header,
+            "Header".to_string(),
+            "Heuristic header".to_string(),
+            Box::new(FixedConfidenceRule {
+                name: "heuristic".to_string(),
+                confidence,
+            }),
+            5,
+        ));
+        profile
+    }
+
+    fn simple_row() -> RowData {
+        RowData {
+            row_index: 0,
+            cells: vec![CellData {
+                column_index: 0,
+                content: "Test".to_string(),
+                style: CellStyle::default(),
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_min_confidence_rejects_low_confidence_match() {
+        let identifier = RowTypeIdentifier::new(single_rule_profile(0.4)).with_min_confidence(0.6);
+
+        let result = identifier.identify_row(&simple_row());
+        assert_eq!(result.row_type, RowType::Unknown);
+        assert_eq!(result.matched_rule, "none");
+    }
+
+    #[test]
+    fn test_min_confidence_accepts_match_at_threshold() {
+        let identifier = RowTypeIdentifier::new(single_rule_profile(0.6)).with_min_confidence(0.6);
+
+        let result = identifier.identify_row(&simple_row());
+        assert_eq!(result.row_type, RowType::Header);
+        assert_eq!(result.confidence, 0.6);
+    }
+
+    #[test]
+    fn test_min_confidence_defers_to_next_definition() {
+        let mut profile = single_rule_profile(0.3);
+        profile.add_row_type(crate::engine::RowTypeDefinition::new(
+            RowType::Data,
+            "Data".to_string(),
+            "Reliable data".to_string(),
+            Box::new(FixedConfidenceRule {
+                name: "reliable".to_string(),
+                confidence: 0.9,
+            }),
+            1,
+        ));
+        let identifier = RowTypeIdentifier::new(profile).with_min_confidence(0.5);
+
+        let result = identifier.identify_row(&simple_row());
+        assert_eq!(result.row_type, RowType::Data);
+        assert_eq!(result.matched_rule, "reliable");
+    }
+
     #[test]
     fn test_debug_mode() {
         let profile = FileTypeProfile::cargo_analysis();
@@ -379,10 +741,55 @@ mod tests {
                 content: "Test".to_string(),
                 style: CellStyle::default(),
                 merge_info: None,
+                comment: None,
+                raw_content: None,
             }],
         };
 
         // 调试模式应该输出日志（这里只是确保不会崩溃）
         let _result = identifier.identify_row(&row);
     }
+
+    fn cabinet_row(row_index: usize, content: &str) -> RowData {
+        RowData::new(
+            row_index,
+            vec![CellData {
+                column_index: 1,
+                content: content.to_string(),
+                style: CellStyle::default(),
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_explain_mode_logs_rule_evaluation() {
+        let identifier = RowTypeIdentifier::new(FileTypeProfile::cargo_analysis()).with_explain(true);
+
+        let result = identifier.identify_row(&cabinet_row(4172, "柜号: 解释测试-7731"));
+        assert_eq!(result.row_type, RowType::CabinetNumber);
+
+        let messages: Vec<String> = crate::logger::LOGGER
+            .get_entries()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert!(messages.iter().any(|m| m.contains("识别第 4173 行")));
+        // 文本规则给出实际检查的单元格内容
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("cabinet_text") && m.contains("✓ 匹配") && m.contains("解释测试-7731")));
+        // 颜色规则给出实际底色
+        assert!(messages.iter().any(|m| m.contains("✗ 不匹配") && m.contains("RGB(255, 255, 255)")));
+
+        // 未开启时不写入说明
+        let quiet = RowTypeIdentifier::new(FileTypeProfile::cargo_analysis());
+        quiet.identify_row(&cabinet_row(0, "柜号: 解释测试-9902"));
+        assert!(!crate::logger::LOGGER
+            .get_entries()
+            .iter()
+            .any(|e| e.message.contains("解释测试-9902")));
+    }
 }
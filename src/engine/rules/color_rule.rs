@@ -2,10 +2,73 @@
 use crate::engine::RecognitionRule;
 use crate::models::{RgbColor, RowData};
 
+/// 颜色比较方式
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ColorMatchMode {
+    /// 精确匹配RGB值
+    #[default]
+    Exact,
+    /// 每个RGB通道的差值不超过给定容差
+    Tolerance(u8),
+    /// 感知色差（CIE76 ΔE）不超过给定阈值
+    Perceptual(f64),
+    /// 转为 HSL 后比较：色相相差不超过 `hue_deg` 度，饱和度和亮度相差不超过给定比例（0.0 - 1.0）
+    ///
+    /// 手工重新填色时常见整体变亮或变暗，RGB 容差会把这类变化和色相偏移一样对待。
+    Hsl { hue_deg: f32, sat: f32, light: f32 },
+}
+
+impl ColorMatchMode {
+    /// 按当前方式比较两个颜色
+    pub fn colors_match(&self, a: &RgbColor, b: &RgbColor) -> bool {
+        match *self {
+            ColorMatchMode::Exact => a == b,
+            ColorMatchMode::Tolerance(tolerance) => {
+                a.r.abs_diff(b.r) <= tolerance
+                    && a.g.abs_diff(b.g) <= tolerance
+                    && a.b.abs_diff(b.b) <= tolerance
+            }
+            ColorMatchMode::Perceptual(threshold) => a.delta_e76(b) <= threshold,
+            ColorMatchMode::Hsl { hue_deg, sat, light } => {
+                let (h1, s1, l1) = rgb_to_hsl(a);
+                let (h2, s2, l2) = rgb_to_hsl(b);
+                // 灰色（饱和度为 0）没有色相，只比较饱和度和亮度
+                let hue_diff = (h1 - h2).abs();
+                let hue_ok = s1 == 0.0 || s2 == 0.0 || hue_diff.min(360.0 - hue_diff) <= hue_deg;
+                hue_ok && (s1 - s2).abs() <= sat && (l1 - l2).abs() <= light
+            }
+        }
+    }
+}
+
+/// RGB 转 HSL，返回 (色相 0-360 度, 饱和度 0-1, 亮度 0-1)
+fn rgb_to_hsl(color: &RgbColor) -> (f32, f32, f32) {
+    let r = color.r as f32 / 255.0;
+    let g = color.g as f32 / 255.0;
+    let b = color.b as f32 / 255.0;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (hue, saturation, lightness)
+}
+
 /// 基于RGB颜色的识别规则
 /// 
 /// 该规则通过检查单元格的背景颜色来判断行类型。
-/// 颜色匹配是精确的，不使用容差。
+/// 默认精确匹配颜色，可通过 [`ColorRule::with_match_mode`] 改用容差或感知色差比较。
 #[derive(Debug, Clone)]
 pub struct ColorRule {
     /// 规则名称
@@ -14,6 +77,8 @@ pub struct ColorRule {
     pub target_color: RgbColor,
     /// 要检查的列索引 (None表示检查第一个非空单元格)
     pub column_index: Option<usize>,
+    /// 颜色比较方式
+    pub match_mode: ColorMatchMode,
 }
 
 impl ColorRule {
@@ -29,8 +94,33 @@ impl ColorRule {
             name,
             target_color,
             column_index,
+            match_mode: ColorMatchMode::Exact,
         }
     }
+
+    /// 创建按 HSL 容差比较的颜色规则
+    ///
+    /// # Arguments
+    ///
+    /// * `hue_deg` - 允许的色相差（度）
+    /// * `sat` - 允许的饱和度差（0.0 - 1.0）
+    /// * `light` - 允许的亮度差（0.0 - 1.0）
+    pub fn with_hsl_tolerance(
+        name: String,
+        target_color: RgbColor,
+        column_index: Option<usize>,
+        hue_deg: f32,
+        sat: f32,
+        light: f32,
+    ) -> Self {
+        Self::new(name, target_color, column_index).with_match_mode(ColorMatchMode::Hsl { hue_deg, sat, light })
+    }
+
+    /// 设置颜色比较方式
+    pub fn with_match_mode(mut self, match_mode: ColorMatchMode) -> Self {
+        self.match_mode = match_mode;
+        self
+    }
 }
 
 impl RecognitionRule for ColorRule {
@@ -55,8 +145,28 @@ impl RecognitionRule for ColorRule {
         // 获取单元格的背景颜色
         let cell_color = cell.style.background_color.unwrap_or(RgbColor::white());
         
-        // 精确匹配颜色（不使用容差）
-        cell_color == self.target_color
+        self.match_mode.colors_match(&cell_color, &self.target_color)
+    }
+
+    fn explain(&self, row_data: &RowData) -> String {
+        let cell = match self.column_index {
+            Some(col_idx) => row_data.get_cell(col_idx),
+            None => row_data.first_non_empty_cell(),
+        };
+        let target = &self.target_color;
+        match cell {
+            Some(cell) => {
+                let color = cell.style.background_color.unwrap_or(RgbColor::white());
+                format!(
+                    "列 {} 底色 RGB({}, {}, {})，目标 RGB({}, {}, {})",
+                    cell.column_index, color.r, color.g, color.b, target.r, target.g, target.b
+                )
+            }
+            None => match self.column_index {
+                Some(col_idx) => format!("列 {} 没有单元格", col_idx),
+                None => "没有非空单元格".to_string(),
+            },
+        }
     }
     
     fn clone_box(&self) -> Box<dyn RecognitionRule> {
@@ -88,6 +198,8 @@ mod tests {
                         ..Default::default()
                     },
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -118,6 +230,8 @@ mod tests {
                         ..Default::default()
                     },
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -141,6 +255,8 @@ mod tests {
                     content: "".to_string(), // 空单元格
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
                 CellData {
                     column_index: 1,
@@ -150,10 +266,92 @@ mod tests {
                         ..Default::default()
                     },
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
         
         assert!(rule.matches(&row));
     }
+
+    fn colored_row(color: RgbColor) -> RowData {
+        RowData::new(
+            0,
+            vec![CellData {
+                column_index: 0,
+                content: "Test".to_string(),
+                style: CellStyle {
+                    background_color: Some(color),
+                    ..Default::default()
+                },
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_color_rule_perceptual_vs_exact() {
+        let target = RgbColor::new(198, 239, 206);
+        // 主题着色后的近似颜色，ΔE 约 1.1
+        let row = colored_row(RgbColor::new(200, 240, 205));
+
+        let exact = ColorRule::new("exact".to_string(), target, Some(0));
+        assert!(!exact.matches(&row));
+
+        let perceptual = ColorRule::new("perceptual".to_string(), target, Some(0))
+            .with_match_mode(ColorMatchMode::Perceptual(2.3));
+        assert!(perceptual.matches(&row));
+
+        // 明显不同的颜色（灰色）在感知模式下仍不匹配
+        assert!(!perceptual.matches(&colored_row(RgbColor::new(217, 217, 217))));
+    }
+
+    #[test]
+    fn test_color_rule_tolerance_mode() {
+        let rule = ColorRule::new("tolerance".to_string(), RgbColor::new(198, 239, 206), Some(0))
+            .with_match_mode(ColorMatchMode::Tolerance(2));
+
+        assert!(rule.matches(&colored_row(RgbColor::new(200, 240, 205))));
+        assert!(!rule.matches(&colored_row(RgbColor::new(201, 239, 206))));
+    }
+
+    #[test]
+    fn test_hsl_tolerance_accepts_lightness_shift() {
+        let target = RgbColor::new(198, 239, 206);
+        // 同一色相和饱和度，亮度从约 0.86 降到 0.80
+        let darker = colored_row(RgbColor::new(175, 233, 187));
+
+        let exact = ColorRule::new("exact".to_string(), target, Some(0));
+        assert!(!exact.matches(&darker));
+        let rgb_tolerance = exact.clone().with_match_mode(ColorMatchMode::Tolerance(10));
+        assert!(!rgb_tolerance.matches(&darker));
+
+        let hsl = ColorRule::with_hsl_tolerance("hsl".to_string(), target, Some(0), 5.0, 0.05, 0.1);
+        assert!(hsl.matches(&darker));
+        assert!(hsl.matches(&colored_row(target)));
+
+        // 亮度相同但色相不同（浅蓝）不匹配
+        assert!(!hsl.matches(&colored_row(RgbColor::new(198, 206, 239))));
+    }
+
+    #[test]
+    fn test_rgb_to_hsl() {
+        let (h, s, l) = rgb_to_hsl(&RgbColor::new(255, 0, 0));
+        assert_eq!((h, s, l), (0.0, 1.0, 0.5));
+        let (h, _, _) = rgb_to_hsl(&RgbColor::new(0, 0, 255));
+        assert!((h - 240.0).abs() < 1e-3);
+        // 灰色没有饱和度
+        let (_, s, l) = rgb_to_hsl(&RgbColor::new(217, 217, 217));
+        assert_eq!(s, 0.0);
+        assert!((l - 217.0 / 255.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_delta_e76_identical_colors_is_zero() {
+        let color = RgbColor::new(217, 217, 217);
+        assert!(color.delta_e76(&color) < 1e-9);
+    }
 }
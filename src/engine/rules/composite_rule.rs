@@ -9,12 +9,15 @@ pub enum CompositeLogic {
     And,
     /// 至少一个规则匹配 (OR)
     Or,
+    /// 至少 N 个规则匹配
+    AtLeast(usize),
 }
 
 /// 组合多个规则的复合规则
 /// 
-/// 该规则允许将多个识别规则组合在一起，使用AND或OR逻辑。
+/// 该规则允许将多个识别规则组合在一起，使用AND、OR或“至少 N 个”逻辑。
 /// 支持嵌套组合，可以创建复杂的识别条件。
+/// 设置子规则权重后，匹配时的置信度为已满足子规则的权重占比。
 #[derive(Clone)]
 pub struct CompositeRule {
     /// 规则名称
@@ -23,6 +26,8 @@ pub struct CompositeRule {
     pub rules: Vec<Box<dyn RecognitionRule>>,
     /// 组合逻辑
     pub logic: CompositeLogic,
+    /// 子规则权重（与 `rules` 一一对应，缺少的按 1.0 计），为空表示不加权
+    pub weights: Vec<f32>,
 }
 
 impl CompositeRule {
@@ -38,7 +43,12 @@ impl CompositeRule {
         rules: Vec<Box<dyn RecognitionRule>>,
         logic: CompositeLogic,
     ) -> Self {
-        Self { name, rules, logic }
+        Self {
+            name,
+            rules,
+            logic,
+            weights: Vec::new(),
+        }
     }
     
     /// 创建AND组合规则
@@ -60,6 +70,48 @@ impl CompositeRule {
     pub fn or(name: String, rules: Vec<Box<dyn RecognitionRule>>) -> Self {
         Self::new(name, rules, CompositeLogic::Or)
     }
+
+    /// 创建“至少 N 个子规则匹配”的组合规则
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 规则名称
+    /// * `rules` - 子规则列表
+    /// * `n` - 至少需要匹配的子规则数
+    pub fn at_least(name: String, rules: Vec<Box<dyn RecognitionRule>>, n: usize) -> Self {
+        Self::new(name, rules, CompositeLogic::AtLeast(n))
+    }
+
+    /// 设置子规则权重，匹配时的置信度为已满足子规则的权重占比
+    ///
+    /// 权重按顺序对应子规则，缺少的按 1.0 计，多余的忽略。
+    pub fn with_weights(mut self, weights: Vec<f32>) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// 第 `index` 个子规则的权重
+    fn weight(&self, index: usize) -> f32 {
+        self.weights.get(index).copied().unwrap_or(1.0)
+    }
+
+    /// 满足条件的子规则权重占比（总权重不为正时为 0）
+    fn weighted_fraction(&self, satisfied: impl Fn(&dyn RecognitionRule) -> bool) -> f32 {
+        let (hit, total) = self
+            .rules
+            .iter()
+            .enumerate()
+            .fold((0.0f32, 0.0f32), |(hit, total), (i, rule)| {
+                let weight = self.weight(i);
+                let hit = if satisfied(rule.as_ref()) { hit + weight } else { hit };
+                (hit, total + weight)
+            });
+        if total > 0.0 {
+            hit / total
+        } else {
+            0.0
+        }
+    }
 }
 
 impl std::fmt::Debug for CompositeRule {
@@ -68,6 +120,7 @@ impl std::fmt::Debug for CompositeRule {
             .field("name", &self.name)
             .field("logic", &self.logic)
             .field("rules_count", &self.rules.len())
+            .field("weights", &self.weights)
             .finish()
     }
 }
@@ -87,6 +140,31 @@ impl RecognitionRule for CompositeRule {
                 // 至少一个规则匹配
                 self.rules.iter().any(|rule| rule.matches(row_data))
             }
+            CompositeLogic::AtLeast(n) => {
+                // 满足的规则数不少于 n
+                self.rules.iter().filter(|rule| rule.matches(row_data)).count() >= n
+            }
+        }
+    }
+
+    fn matches_in_context(&self, row_data: &RowData, previous_data_row: Option<&RowData>) -> bool {
+        // 将上下文传递给子规则
+        match self.logic {
+            CompositeLogic::And => self
+                .rules
+                .iter()
+                .all(|rule| rule.matches_in_context(row_data, previous_data_row)),
+            CompositeLogic::Or => self
+                .rules
+                .iter()
+                .any(|rule| rule.matches_in_context(row_data, previous_data_row)),
+            CompositeLogic::AtLeast(n) => {
+                self.rules
+                    .iter()
+                    .filter(|rule| rule.matches_in_context(row_data, previous_data_row))
+                    .count()
+                    >= n
+            }
         }
     }
     
@@ -108,7 +186,48 @@ impl RecognitionRule for CompositeRule {
                     .map(|r| r.confidence())
                     .fold(0.0f32, |a, b| a.max(b))
             }
+            CompositeLogic::AtLeast(n) => {
+                // 不看具体行时，取匹配时至少能达到的权重占比（权重最小的 n 个子规则）
+                let mut weights: Vec<f32> = (0..self.rules.len()).map(|i| self.weight(i)).collect();
+                weights.sort_by(|a, b| a.total_cmp(b));
+                let total: f32 = weights.iter().sum();
+                if total > 0.0 {
+                    weights.iter().take(n).sum::<f32>() / total
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    fn confidence_for(&self, row_data: &RowData, previous_data_row: Option<&RowData>) -> f32 {
+        // 未加权的 AND/OR 沿用与行无关的置信度
+        if self.weights.is_empty() && !matches!(self.logic, CompositeLogic::AtLeast(_)) {
+            return self.confidence();
         }
+        // 与 matches_in_context 使用相同的上下文判断子规则是否满足
+        self.weighted_fraction(|rule| rule.matches_in_context(row_data, previous_data_row))
+    }
+
+    fn explain(&self, row_data: &RowData) -> String {
+        self.explain_in_context(row_data, None)
+    }
+
+    fn explain_in_context(&self, row_data: &RowData, previous_data_row: Option<&RowData>) -> String {
+        let parts: Vec<String> = self
+            .rules
+            .iter()
+            .map(|rule| {
+                let mark = if rule.matches_in_context(row_data, previous_data_row) { "✓" } else { "✗" };
+                format!(
+                    "{} {}: {}",
+                    mark,
+                    rule.name(),
+                    rule.explain_in_context(row_data, previous_data_row)
+                )
+            })
+            .collect();
+        format!("{:?}[{}]", self.logic, parts.join("; "))
     }
     
     fn clone_box(&self) -> Box<dyn RecognitionRule> {
@@ -153,6 +272,8 @@ mod tests {
                         ..Default::default()
                     },
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -191,6 +312,8 @@ mod tests {
                         ..Default::default()
                     },
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -227,12 +350,16 @@ mod tests {
                     content: "A".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
                 CellData {
                     column_index: 1,
                     content: "柜号: 1-1".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -269,12 +396,16 @@ mod tests {
                     content: "A".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
                 CellData {
                     column_index: 1,
                     content: "Other text".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -324,10 +455,132 @@ mod tests {
                     content: "项目编号".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
         
         assert!(outer_or.matches(&row));
     }
+
+    fn letter_rules() -> Vec<Box<dyn RecognitionRule>> {
+        ["A", "B", "C"]
+            .iter()
+            .map(|letter| {
+                Box::new(TextPatternRule::new(
+                    format!("has_{}", letter),
+                    0,
+                    TextPattern::Contains(letter.to_string()),
+                    true,
+                )) as Box<dyn RecognitionRule>
+            })
+            .collect()
+    }
+
+    fn text_row(content: &str) -> RowData {
+        RowData::new(
+            0,
+            vec![CellData {
+                column_index: 0,
+                content: content.to_string(),
+                style: CellStyle::default(),
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_at_least_two_of_three() {
+        let rule = CompositeRule::at_least("two_of_three".to_string(), letter_rules(), 2);
+
+        assert!(rule.matches(&text_row("AB")));
+        assert!(rule.matches(&text_row("BC")));
+        assert!(rule.matches(&text_row("ABC")));
+        assert!(!rule.matches(&text_row("C")));
+        assert!(!rule.matches(&text_row("xyz")));
+        assert!(rule.matches_in_context(&text_row("AC"), None));
+    }
+
+    #[test]
+    fn test_at_least_exact_boundary() {
+        // 恰好 N 个匹配即满足，少一个则不满足
+        let rule = CompositeRule::at_least("exactly".to_string(), letter_rules(), 3);
+        assert!(rule.matches(&text_row("ABC")));
+        assert!(!rule.matches(&text_row("AB")));
+
+        // N 为 0 时总是匹配，N 大于子规则数时永不匹配
+        assert!(CompositeRule::at_least("zero".to_string(), letter_rules(), 0).matches(&text_row("xyz")));
+        assert!(!CompositeRule::at_least("four".to_string(), letter_rules(), 4).matches(&text_row("ABC")));
+    }
+
+    #[test]
+    fn test_weighted_confidence() {
+        let rule = CompositeRule::at_least("weighted".to_string(), letter_rules(), 2)
+            .with_weights(vec![3.0, 1.0, 1.0]);
+
+        // A 和 B 满足：(3 + 1) / 5
+        assert!((rule.confidence_for(&text_row("AB"), None) - 0.8).abs() < 1e-6);
+        // B 和 C 满足：(1 + 1) / 5
+        assert!((rule.confidence_for(&text_row("BC"), None) - 0.4).abs() < 1e-6);
+        assert!((rule.confidence_for(&text_row("ABC"), None) - 1.0).abs() < 1e-6);
+        // 与行无关时取最低可能值
+        assert!((rule.confidence() - 0.4).abs() < 1e-6);
+
+        // 未加权时按满足的子规则数计
+        let unweighted = CompositeRule::at_least("unweighted".to_string(), letter_rules(), 2);
+        assert!((unweighted.confidence_for(&text_row("AB"), None) - 2.0 / 3.0).abs() < 1e-6);
+
+        // 未加权的 AND 保持原有置信度
+        let and = CompositeRule::and("and".to_string(), letter_rules());
+        assert_eq!(and.confidence_for(&text_row("ABC"), None), and.confidence());
+
+        // 全部权重为 0 时置信度为 0
+        let zero = CompositeRule::at_least("zero".to_string(), letter_rules(), 1).with_weights(vec![0.0; 3]);
+        assert_eq!(zero.confidence_for(&text_row("ABC"), None), 0.0);
+    }
+
+    #[test]
+    fn test_weighted_confidence_uses_context() {
+        use crate::engine::rules::SequenceIndexRule;
+
+        let rule = CompositeRule::at_least(
+            "seq_or_text".to_string(),
+            vec![
+                Box::new(SequenceIndexRule::new("seq".to_string(), 0, true)),
+                Box::new(TextPatternRule::new(
+                    "has_A".to_string(),
+                    1,
+                    TextPattern::Contains("A".to_string()),
+                    true,
+                )),
+            ],
+            1,
+        )
+        .with_weights(vec![3.0, 1.0]);
+
+        let cell = |column_index: usize, content: &str| CellData {
+            column_index,
+            content: content.to_string(),
+            style: CellStyle::default(),
+            merge_info: None,
+            comment: None,
+            raw_content: None,
+        };
+        let row = RowData::new(5, vec![cell(0, "3"), cell(1, "A")]);
+        let in_sequence = RowData::new(4, vec![cell(0, "2"), cell(1, "")]);
+        let out_of_sequence = RowData::new(4, vec![cell(0, "5"), cell(1, "")]);
+
+        // 序号连续时两条子规则都满足
+        assert!((rule.confidence_for(&row, Some(&in_sequence)) - 1.0).abs() < 1e-6);
+
+        // 序号不连续时序号规则在上下文中不满足，置信度只计文本规则的权重
+        assert!(rule.matches_in_context(&row, Some(&out_of_sequence)));
+        assert!((rule.confidence_for(&row, Some(&out_of_sequence)) - 0.25).abs() < 1e-6);
+        assert!(rule
+            .explain_in_context(&row, Some(&out_of_sequence))
+            .contains("✗ seq"));
+    }
 }
@@ -0,0 +1,111 @@
+// Fallback Recognition Rule
+use crate::engine::RecognitionRule;
+use crate::models::RowData;
+
+/// 兜底识别规则
+///
+/// 只要行内（或指定列中）有任意非空单元格即匹配，不检查颜色。
+/// 放在最低优先级，避免数据行因为底色偏差落入Unknown。
+#[derive(Debug, Clone)]
+pub struct DefaultRule {
+    /// 规则名称
+    pub name: String,
+    /// 需要有内容的列 (None表示任意列)
+    pub columns: Option<Vec<usize>>,
+    /// 匹配时报告的置信度
+    pub confidence: f32,
+}
+
+impl DefaultRule {
+    /// 兜底匹配的默认置信度，低于颜色等明确规则
+    pub const DEFAULT_CONFIDENCE: f32 = 0.5;
+
+    /// 创建新的兜底规则，匹配任意有内容的行
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 规则名称
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            columns: None,
+            confidence: Self::DEFAULT_CONFIDENCE,
+        }
+    }
+
+    /// 只在指定列中有内容时匹配
+    pub fn with_columns(mut self, columns: Vec<usize>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// 设置匹配时的置信度
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = confidence.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl RecognitionRule for DefaultRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, row_data: &RowData) -> bool {
+        match &self.columns {
+            Some(columns) => columns
+                .iter()
+                .filter_map(|&col| row_data.get_cell(col))
+                .any(|cell| !cell.is_empty()),
+            None => row_data.first_non_empty_cell().is_some(),
+        }
+    }
+
+    fn confidence(&self) -> f32 {
+        self.confidence
+    }
+
+    fn clone_box(&self) -> Box<dyn RecognitionRule> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellData, CellStyle};
+
+    fn row(contents: &[&str]) -> RowData {
+        let cells = contents
+            .iter()
+            .enumerate()
+            .map(|(column_index, content)| CellData {
+                column_index,
+                content: content.to_string(),
+                style: CellStyle::default(),
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            })
+            .collect();
+        RowData::new(0, cells)
+    }
+
+    #[test]
+    fn test_matches_any_row_with_content() {
+        let rule = DefaultRule::new("fallback".to_string());
+
+        assert!(rule.matches(&row(&["", "电缆"])));
+        assert!(!rule.matches(&row(&["", "  "])));
+        assert!(!rule.matches(&row(&[])));
+        assert_eq!(rule.confidence(), DefaultRule::DEFAULT_CONFIDENCE);
+    }
+
+    #[test]
+    fn test_matches_only_expected_columns() {
+        let rule = DefaultRule::new("fallback".to_string()).with_columns(vec![1, 2]);
+
+        assert!(rule.matches(&row(&["", "电缆"])));
+        assert!(!rule.matches(&row(&["备注"])));
+    }
+}
@@ -0,0 +1,133 @@
+// Dominant Row Color Recognition Rule
+use crate::engine::rules::ColorMatchMode;
+use crate::engine::RecognitionRule;
+use crate::models::{RgbColor, RowData};
+
+/// 默认的通道容差
+pub const DEFAULT_DOMINANT_COLOR_TOLERANCE: u8 = 8;
+
+/// 基于行主色的识别规则
+///
+/// 当行中超过 `min_fraction` 比例的单元格与目标颜色相同（按 `match_mode` 比较）时匹配。
+/// 相比只检查一个单元格，对整行高亮的行更稳健。没有背景色的单元格视为白色。
+#[derive(Debug, Clone)]
+pub struct DominantColorRule {
+    /// 规则名称
+    pub name: String,
+    /// 目标RGB颜色
+    pub target_color: RgbColor,
+    /// 同色单元格需超过的比例 (0.0 - 1.0)
+    pub min_fraction: f64,
+    /// 颜色比较方式（默认按通道容差比较）
+    pub match_mode: ColorMatchMode,
+}
+
+impl DominantColorRule {
+    /// 创建新的行主色规则
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 规则名称
+    /// * `target_color` - 要匹配的目标颜色
+    /// * `min_fraction` - 同色单元格需超过的比例，超出 0.0 - 1.0 时截断
+    pub fn new(name: String, target_color: RgbColor, min_fraction: f64) -> Self {
+        Self {
+            name,
+            target_color,
+            min_fraction: min_fraction.clamp(0.0, 1.0),
+            match_mode: ColorMatchMode::Tolerance(DEFAULT_DOMINANT_COLOR_TOLERANCE),
+        }
+    }
+
+    /// 设置颜色比较方式
+    pub fn with_match_mode(mut self, match_mode: ColorMatchMode) -> Self {
+        self.match_mode = match_mode;
+        self
+    }
+
+    /// 行中与目标颜色相同的单元格比例（空行为 0）
+    pub fn matching_fraction(&self, row_data: &RowData) -> f64 {
+        if row_data.cells.is_empty() {
+            return 0.0;
+        }
+
+        let matching = row_data
+            .cells
+            .iter()
+            .filter(|cell| {
+                let color = cell.style.background_color.unwrap_or(RgbColor::white());
+                self.match_mode.colors_match(&color, &self.target_color)
+            })
+            .count();
+
+        matching as f64 / row_data.cells.len() as f64
+    }
+}
+
+impl RecognitionRule for DominantColorRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, row_data: &RowData) -> bool {
+        self.matching_fraction(row_data) > self.min_fraction
+    }
+
+    fn clone_box(&self) -> Box<dyn RecognitionRule> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellData, CellStyle};
+
+    const PINK: RgbColor = RgbColor::new(255, 199, 206);
+
+    /// 前 `pink` 个单元格为粉色（略有偏差），其余无背景色
+    fn row_with_pink(pink: usize, total: usize) -> RowData {
+        let cells = (0..total)
+            .map(|i| CellData {
+                column_index: i,
+                content: format!("C{}", i),
+                style: CellStyle {
+                    background_color: (i < pink).then(|| RgbColor::new(255, 197 + (i % 3) as u8, 206)),
+                    ..Default::default()
+                },
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            })
+            .collect();
+        RowData::new(0, cells)
+    }
+
+    #[test]
+    fn test_dominant_color_threshold() {
+        let row = row_with_pink(6, 10);
+
+        let half = DominantColorRule::new("pink_half".to_string(), PINK, 0.5);
+        assert!(half.matches(&row));
+
+        let most = DominantColorRule::new("pink_most".to_string(), PINK, 0.7);
+        assert!(!most.matches(&row));
+    }
+
+    #[test]
+    fn test_dominant_color_exact_mode_rejects_variants() {
+        let row = row_with_pink(6, 10);
+        let rule = DominantColorRule::new("pink_exact".to_string(), PINK, 0.5)
+            .with_match_mode(ColorMatchMode::Exact);
+
+        // 10 个单元格中只有 2 个与目标颜色完全相同
+        assert!((rule.matching_fraction(&row) - 0.2).abs() < 1e-9);
+        assert!(!rule.matches(&row));
+    }
+
+    #[test]
+    fn test_dominant_color_empty_row() {
+        let rule = DominantColorRule::new("pink".to_string(), PINK, 0.0);
+        assert!(!rule.matches(&RowData::new(0, Vec::new())));
+    }
+}
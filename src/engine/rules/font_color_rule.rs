@@ -0,0 +1,135 @@
+// Font Color Recognition Rule
+use crate::engine::{ColorMatchMode, RecognitionRule};
+use crate::models::{RgbColor, RowData};
+
+/// 基于字体颜色的识别规则
+///
+/// 与 [`ColorRule`](crate::engine::ColorRule) 相同，但检查单元格的字体颜色，
+/// 用于识别深色底白字这类靠字体颜色区分的表头行。没有字体颜色的单元格视为黑色。
+#[derive(Debug, Clone)]
+pub struct FontColorRule {
+    /// 规则名称
+    pub name: String,
+    /// 目标字体颜色
+    pub target_color: RgbColor,
+    /// 要检查的列索引 (None表示检查第一个非空单元格)
+    pub column_index: Option<usize>,
+    /// 颜色比较方式
+    pub match_mode: ColorMatchMode,
+}
+
+impl FontColorRule {
+    /// 创建新的字体颜色规则
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 规则名称
+    /// * `target_color` - 要匹配的字体颜色
+    /// * `column_index` - 要检查的列索引，None表示检查第一个非空单元格
+    pub fn new(name: String, target_color: RgbColor, column_index: Option<usize>) -> Self {
+        Self {
+            name,
+            target_color,
+            column_index,
+            match_mode: ColorMatchMode::Exact,
+        }
+    }
+
+    /// 设置颜色比较方式
+    pub fn with_match_mode(mut self, match_mode: ColorMatchMode) -> Self {
+        self.match_mode = match_mode;
+        self
+    }
+}
+
+impl RecognitionRule for FontColorRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, row_data: &RowData) -> bool {
+        let cell = match self.column_index {
+            Some(col_idx) => row_data.get_cell(col_idx),
+            None => row_data.first_non_empty_cell(),
+        };
+
+        match cell {
+            Some(cell) => {
+                let font_color = cell.style.font_color.unwrap_or(RgbColor::black());
+                self.match_mode.colors_match(&font_color, &self.target_color)
+            }
+            None => false,
+        }
+    }
+
+    fn explain(&self, row_data: &RowData) -> String {
+        let cell = match self.column_index {
+            Some(col_idx) => row_data.get_cell(col_idx),
+            None => row_data.first_non_empty_cell(),
+        };
+        let target = &self.target_color;
+        match cell {
+            Some(cell) => {
+                let color = cell.style.font_color.unwrap_or(RgbColor::black());
+                format!(
+                    "列 {} 字体颜色 RGB({}, {}, {})，目标 RGB({}, {}, {})",
+                    cell.column_index, color.r, color.g, color.b, target.r, target.g, target.b
+                )
+            }
+            None => match self.column_index {
+                Some(col_idx) => format!("列 {} 没有单元格", col_idx),
+                None => "没有非空单元格".to_string(),
+            },
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn RecognitionRule> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellData, CellStyle};
+
+    fn row_with_font(font_color: Option<RgbColor>) -> RowData {
+        RowData::new(
+            0,
+            vec![CellData {
+                column_index: 0,
+                content: "序号".to_string(),
+                style: CellStyle {
+                    background_color: Some(RgbColor::new(31, 78, 121)),
+                    font_color,
+                    ..Default::default()
+                },
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_white_font_matches() {
+        let rule = FontColorRule::new("white_font".to_string(), RgbColor::white(), Some(0));
+        assert!(rule.matches(&row_with_font(Some(RgbColor::white()))));
+
+        // 容差内的近白色
+        assert!(!rule.matches(&row_with_font(Some(RgbColor::new(250, 250, 250)))));
+        let tolerant = rule.clone().with_match_mode(ColorMatchMode::Tolerance(8));
+        assert!(tolerant.matches(&row_with_font(Some(RgbColor::new(250, 250, 250)))));
+    }
+
+    #[test]
+    fn test_black_font_does_not_match() {
+        let rule = FontColorRule::new("white_font".to_string(), RgbColor::white(), None)
+            .with_match_mode(ColorMatchMode::Tolerance(8));
+
+        assert!(!rule.matches(&row_with_font(Some(RgbColor::black()))));
+        // 没有字体颜色视为黑色
+        assert!(!rule.matches(&row_with_font(None)));
+        assert!(rule.explain(&row_with_font(None)).contains("RGB(0, 0, 0)"));
+    }
+}
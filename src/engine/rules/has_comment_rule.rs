@@ -0,0 +1,117 @@
+// Comment-based Recognition Rule
+use crate::engine::RecognitionRule;
+use crate::models::RowData;
+
+/// 基于单元格批注的识别规则
+/// 
+/// 该规则检查单元格是否带有批注，可选地要求批注包含指定文本。
+#[derive(Debug, Clone)]
+pub struct HasCommentRule {
+    /// 规则名称
+    pub name: String,
+    /// 要检查的列索引 (None表示任意单元格)
+    pub column_index: Option<usize>,
+    /// 批注需包含的文本 (None表示只要有批注即可)
+    pub contains: Option<String>,
+}
+
+impl HasCommentRule {
+    /// 创建新的批注规则
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - 规则名称
+    /// * `column_index` - 要检查的列索引，None表示检查行内任意单元格
+    /// * `contains` - 批注需包含的文本，None表示不限制内容
+    pub fn new(name: String, column_index: Option<usize>, contains: Option<String>) -> Self {
+        Self {
+            name,
+            column_index,
+            contains,
+        }
+    }
+
+    fn comment_matches(&self, comment: &str) -> bool {
+        match &self.contains {
+            Some(text) => comment.contains(text.as_str()),
+            None => true,
+        }
+    }
+}
+
+impl RecognitionRule for HasCommentRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, row_data: &RowData) -> bool {
+        match self.column_index {
+            Some(col_idx) => row_data
+                .get_cell(col_idx)
+                .and_then(|cell| cell.comment.as_deref())
+                .is_some_and(|comment| self.comment_matches(comment)),
+            None => row_data
+                .cells
+                .iter()
+                .filter_map(|cell| cell.comment.as_deref())
+                .any(|comment| self.comment_matches(comment)),
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn RecognitionRule> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellData, CellStyle};
+
+    fn row_with_comment(comment: Option<&str>) -> RowData {
+        RowData::new(
+            0,
+            vec![
+                CellData {
+                    column_index: 0,
+                    content: "1".to_string(),
+                    style: CellStyle::default(),
+                    merge_info: None,
+                    comment: None,
+                    raw_content: None,
+                },
+                CellData {
+                    column_index: 1,
+                    content: "名称".to_string(),
+                    style: CellStyle::default(),
+                    merge_info: None,
+                    comment: comment.map(|c| c.to_string()),
+                    raw_content: None,
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn test_any_cell_with_comment() {
+        let rule = HasCommentRule::new("has_comment".to_string(), None, None);
+
+        assert!(rule.matches(&row_with_comment(Some("备注"))));
+        assert!(!rule.matches(&row_with_comment(None)));
+    }
+
+    #[test]
+    fn test_comment_in_column_containing_text() {
+        let rule = HasCommentRule::new(
+            "spare_note".to_string(),
+            Some(1),
+            Some("备用".to_string()),
+        );
+
+        assert!(rule.matches(&row_with_comment(Some("含备用件"))));
+        assert!(!rule.matches(&row_with_comment(Some("其他说明"))));
+
+        let wrong_column = HasCommentRule::new("col0".to_string(), Some(0), None);
+        assert!(!wrong_column.matches(&row_with_comment(Some("含备用件"))));
+    }
+}
@@ -107,6 +107,8 @@ mod tests {
                         start_row: 0,
                         end_row: 0,
                     }),
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -127,6 +129,8 @@ mod tests {
                         start_row: 0,
                         end_row: 0,
                     }),
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -154,6 +158,8 @@ mod tests {
                         start_row: 0,
                         end_row: 0,
                     }),
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -174,6 +180,8 @@ mod tests {
                         start_row: 0,
                         end_row: 0,
                     }),
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -196,12 +204,16 @@ mod tests {
                     content: "Cell 1".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
                 CellData {
                     column_index: 1,
                     content: "Cell 2".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -222,6 +234,8 @@ mod tests {
                         start_row: 0,
                         end_row: 0,
                     }),
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -245,6 +259,8 @@ mod tests {
                     content: "Normal Cell".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
                 CellData {
                     column_index: 1,
@@ -256,6 +272,8 @@ mod tests {
                         start_row: 0,
                         end_row: 0,
                     }),
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
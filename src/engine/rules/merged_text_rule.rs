@@ -0,0 +1,173 @@
+// Merged Cell Text Recognition Rule
+use crate::engine::rules::text_pattern_rule::RegexCache;
+use crate::engine::{RecognitionRule, TextPattern};
+use crate::models::{CellData, RowData};
+
+/// 基于合并单元格文本的识别规则
+///
+/// 只有当单元格属于至少跨 `min_columns` 列的合并区域，且其内容符合文本模式时才匹配。
+/// 例如柜号行通常是一个横跨多列、内容为 "柜号:1-1" 的合并单元格，
+/// 比单独检查颜色或文本更可靠。
+#[derive(Debug, Clone)]
+pub struct MergedTextRule {
+    /// 规则名称
+    pub name: String,
+    /// 要检查的列索引 (None表示行内任意单元格)
+    pub column_index: Option<usize>,
+    /// 合并区域至少跨越的列数
+    pub min_columns: usize,
+    /// 文本模式（创建后不可修改，已编译的正则表达式随之固定）
+    pattern: TextPattern,
+    /// 是否区分大小写（通过 `with_case_sensitive` 设置）
+    case_sensitive: bool,
+    /// 已编译的正则表达式
+    regex_cache: RegexCache,
+}
+
+impl MergedTextRule {
+    /// 创建新的合并单元格文本规则（不区分大小写，检查行内任意单元格）
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 规则名称
+    /// * `min_columns` - 合并区域至少跨越的列数
+    /// * `pattern` - 文本模式
+    pub fn new(name: String, min_columns: usize, pattern: TextPattern) -> Self {
+        Self {
+            name,
+            column_index: None,
+            min_columns,
+            pattern,
+            case_sensitive: false,
+            regex_cache: RegexCache::default(),
+        }
+    }
+
+    /// 只检查指定列的单元格
+    pub fn with_column(mut self, column_index: usize) -> Self {
+        self.column_index = Some(column_index);
+        self
+    }
+
+    /// 设置是否区分大小写
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self.regex_cache = RegexCache::default();
+        self
+    }
+
+    /// 文本模式
+    pub fn pattern(&self) -> &TextPattern {
+        &self.pattern
+    }
+
+    /// 是否区分大小写
+    pub fn case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    fn cell_matches(&self, cell: &CellData) -> bool {
+        let merged_enough = cell
+            .merge_info
+            .as_ref()
+            .is_some_and(|merge| merge.column_span() >= self.min_columns);
+        if !merged_enough {
+            return false;
+        }
+
+        self.regex_cache
+            .is_match(&self.pattern, &cell.content, self.case_sensitive, &self.name)
+    }
+}
+
+impl RecognitionRule for MergedTextRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, row_data: &RowData) -> bool {
+        match self.column_index {
+            Some(col_idx) => row_data
+                .get_cell(col_idx)
+                .is_some_and(|cell| self.cell_matches(cell)),
+            None => row_data.cells.iter().any(|cell| self.cell_matches(cell)),
+        }
+    }
+
+    fn explain(&self, row_data: &RowData) -> String {
+        let merged: Vec<String> = row_data
+            .cells
+            .iter()
+            .filter(|cell| self.column_index.map_or(true, |col| cell.column_index == col))
+            .filter_map(|cell| {
+                cell.merge_info.as_ref().map(|merge| {
+                    format!("列 {} 合并 {} 列 \"{}\"", cell.column_index, merge.column_span(), cell.content)
+                })
+            })
+            .collect();
+        if merged.is_empty() {
+            format!("没有合并单元格，要求至少 {} 列，模式 {:?}", self.min_columns, self.pattern)
+        } else {
+            format!(
+                "{}；要求至少 {} 列，模式 {:?}",
+                merged.join("，"),
+                self.min_columns,
+                self.pattern
+            )
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn RecognitionRule> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellStyle, MergeInfo};
+
+    fn cell(column_index: usize, content: &str, merge_cols: Option<(usize, usize)>) -> CellData {
+        CellData {
+            column_index,
+            content: content.to_string(),
+            style: CellStyle::default(),
+            merge_info: merge_cols.map(|(start_col, end_col)| MergeInfo {
+                start_col,
+                end_col,
+                start_row: 3,
+                end_row: 3,
+            }),
+            comment: None,
+            raw_content: None,
+        }
+    }
+
+    fn cabinet_rule() -> MergedTextRule {
+        MergedTextRule::new("merged_cabinet".to_string(), 3, TextPattern::Contains("柜号".to_string()))
+    }
+
+    #[test]
+    fn test_matches_merged_cabinet_cell() {
+        let row = RowData::new(3, vec![cell(0, "", None), cell(1, "柜号:1-1", Some((1, 5)))]);
+
+        assert!(cabinet_rule().matches(&row));
+        assert!(cabinet_rule().with_column(1).matches(&row));
+        assert!(!cabinet_rule().with_column(0).matches(&row));
+    }
+
+    #[test]
+    fn test_requires_merge_of_min_columns() {
+        // 未合并的同样文本不匹配
+        let unmerged = RowData::new(3, vec![cell(1, "柜号:1-1", None)]);
+        assert!(!cabinet_rule().matches(&unmerged));
+
+        // 合并列数不足
+        let narrow = RowData::new(3, vec![cell(1, "柜号:1-1", Some((1, 2)))]);
+        assert!(!cabinet_rule().matches(&narrow));
+
+        // 合并足够宽但文本不符
+        let other = RowData::new(3, vec![cell(1, "备注", Some((1, 5)))]);
+        assert!(!cabinet_rule().matches(&other));
+    }
+}
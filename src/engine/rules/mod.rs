@@ -1,10 +1,28 @@
 // Recognition Rules Module
 pub mod color_rule;
+pub mod font_color_rule;
 pub mod text_pattern_rule;
 pub mod merge_state_rule;
 pub mod composite_rule;
+pub mod not_rule;
+pub mod sequence_index_rule;
+pub mod has_comment_rule;
+pub mod dominant_color_rule;
+pub mod default_rule;
+pub mod merged_text_rule;
+pub mod multi_color_rule;
+pub mod numeric_range_rule;
 
-pub use color_rule::ColorRule;
+pub use color_rule::{ColorMatchMode, ColorRule};
+pub use font_color_rule::FontColorRule;
 pub use text_pattern_rule::{TextPattern, TextPatternRule};
 pub use merge_state_rule::{MergeRequirement, MergeStateRule};
 pub use composite_rule::{CompositeLogic, CompositeRule};
+pub use not_rule::NotRule;
+pub use sequence_index_rule::SequenceIndexRule;
+pub use has_comment_rule::HasCommentRule;
+pub use dominant_color_rule::DominantColorRule;
+pub use default_rule::DefaultRule;
+pub use merged_text_rule::MergedTextRule;
+pub use multi_color_rule::MultiColorRule;
+pub use numeric_range_rule::NumericRangeRule;
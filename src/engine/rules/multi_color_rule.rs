@@ -0,0 +1,153 @@
+// Multi-Color Recognition Rule
+use crate::engine::RecognitionRule;
+use crate::models::{RgbColor, RowData};
+
+/// 匹配多个背景颜色之一的识别规则
+///
+/// 不同版本的模板可能用几种相近的颜色标记同一类行（例如两三种粉色的合计行）。
+/// 与其用 OR 组合多个 [`ColorRule`](crate::engine::ColorRule)，
+/// 该规则在单元格背景颜色等于任意一个目标颜色时匹配。没有背景色的单元格视为白色。
+#[derive(Debug, Clone)]
+pub struct MultiColorRule {
+    /// 规则名称
+    pub name: String,
+    /// 目标RGB颜色列表
+    pub target_colors: Vec<RgbColor>,
+    /// 要检查的列索引 (None表示检查第一个非空单元格)
+    pub column_index: Option<usize>,
+}
+
+impl MultiColorRule {
+    /// 创建新的多颜色规则
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 规则名称
+    /// * `target_colors` - 要匹配的颜色列表，匹配其中任意一个即可
+    /// * `column_index` - 要检查的列索引，None表示检查第一个非空单元格
+    pub fn new(name: String, target_colors: Vec<RgbColor>, column_index: Option<usize>) -> Self {
+        Self {
+            name,
+            target_colors,
+            column_index,
+        }
+    }
+
+    fn cell_color(&self, row_data: &RowData) -> Option<RgbColor> {
+        let cell = match self.column_index {
+            Some(col_idx) => row_data.get_cell(col_idx),
+            None => row_data.first_non_empty_cell(),
+        }?;
+        Some(cell.style.background_color.unwrap_or(RgbColor::white()))
+    }
+}
+
+impl RecognitionRule for MultiColorRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, row_data: &RowData) -> bool {
+        self.cell_color(row_data)
+            .is_some_and(|color| self.target_colors.contains(&color))
+    }
+
+    fn confidence(&self) -> f32 {
+        1.0
+    }
+
+    fn explain(&self, row_data: &RowData) -> String {
+        let targets = self
+            .target_colors
+            .iter()
+            .map(|c| format!("RGB({}, {}, {})", c.r, c.g, c.b))
+            .collect::<Vec<_>>()
+            .join(" / ");
+        match self.cell_color(row_data) {
+            Some(color) => format!("底色 RGB({}, {}, {})，目标 {}", color.r, color.g, color.b, targets),
+            None => match self.column_index {
+                Some(col_idx) => format!("列 {} 没有单元格", col_idx),
+                None => "没有非空单元格".to_string(),
+            },
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn RecognitionRule> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellData, CellStyle};
+
+    fn row_with_background(background_color: Option<RgbColor>) -> RowData {
+        RowData::new(
+            0,
+            vec![
+                CellData {
+                    column_index: 0,
+                    content: "".to_string(),
+                    style: CellStyle::default(),
+                    merge_info: None,
+                    comment: None,
+                    raw_content: None,
+                },
+                CellData {
+                    column_index: 1,
+                    content: "合计".to_string(),
+                    style: CellStyle {
+                        background_color,
+                        ..Default::default()
+                    },
+                    merge_info: None,
+                    comment: None,
+                    raw_content: None,
+                },
+            ],
+        )
+    }
+
+    fn pink_rule() -> MultiColorRule {
+        MultiColorRule::new(
+            "total_pink".to_string(),
+            vec![
+                RgbColor::new(255, 199, 206),
+                RgbColor::new(255, 204, 204),
+                RgbColor::new(252, 228, 214),
+            ],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_matches_one_of_the_colors() {
+        let rule = pink_rule();
+        assert!(rule.matches(&row_with_background(Some(RgbColor::new(255, 204, 204)))));
+        assert!(rule.matches(&row_with_background(Some(RgbColor::new(252, 228, 214)))));
+        assert_eq!(rule.confidence(), 1.0);
+    }
+
+    #[test]
+    fn test_no_color_matches() {
+        let rule = pink_rule();
+        assert!(!rule.matches(&row_with_background(Some(RgbColor::new(217, 217, 217)))));
+        assert!(!rule.matches(&row_with_background(Some(RgbColor::new(255, 204, 205)))));
+
+        // 指定列没有单元格
+        let missing = MultiColorRule::new("missing".to_string(), vec![RgbColor::white()], Some(5));
+        assert!(!missing.matches(&row_with_background(None)));
+    }
+
+    #[test]
+    fn test_no_background_treated_as_white() {
+        let rule = MultiColorRule::new(
+            "white_or_gray".to_string(),
+            vec![RgbColor::new(217, 217, 217), RgbColor::white()],
+            None,
+        );
+        assert!(rule.matches(&row_with_background(None)));
+        assert!(!pink_rule().matches(&row_with_background(None)));
+    }
+}
@@ -0,0 +1,136 @@
+// Negation Recognition Rule
+use crate::engine::RecognitionRule;
+use crate::models::RowData;
+
+/// 取反规则
+///
+/// 内部规则不匹配时匹配，用于在组合规则中表达 “粉色底但不含「总计」” 这类条件：
+///
+/// ```ignore
+/// CompositeRule::and(
+///     "subtotal".to_string(),
+///     vec![Box::new(pink_rule), Box::new(NotRule::new("not_grand_total".to_string(), Box::new(grand_total_text)))],
+/// )
+/// ```
+#[derive(Clone)]
+pub struct NotRule {
+    /// 规则名称
+    pub name: String,
+    /// 被取反的规则
+    pub inner: Box<dyn RecognitionRule>,
+}
+
+impl NotRule {
+    /// 创建新的取反规则
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 规则名称
+    /// * `inner` - 被取反的规则
+    pub fn new(name: String, inner: Box<dyn RecognitionRule>) -> Self {
+        Self { name, inner }
+    }
+}
+
+impl std::fmt::Debug for NotRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotRule")
+            .field("name", &self.name)
+            .field("inner", &self.inner.name())
+            .finish()
+    }
+}
+
+impl RecognitionRule for NotRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, row_data: &RowData) -> bool {
+        !self.inner.matches(row_data)
+    }
+
+    fn matches_in_context(&self, row_data: &RowData, previous_data_row: Option<&RowData>) -> bool {
+        !self.inner.matches_in_context(row_data, previous_data_row)
+    }
+
+    fn confidence(&self) -> f32 {
+        1.0 - self.inner.confidence()
+    }
+
+    fn explain(&self, row_data: &RowData) -> String {
+        self.explain_in_context(row_data, None)
+    }
+
+    fn explain_in_context(&self, row_data: &RowData, previous_data_row: Option<&RowData>) -> String {
+        let mark = if self.inner.matches_in_context(row_data, previous_data_row) { "✓" } else { "✗" };
+        format!(
+            "Not[{} {}: {}]",
+            mark,
+            self.inner.name(),
+            self.inner.explain_in_context(row_data, previous_data_row)
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn RecognitionRule> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::rules::{ColorRule, CompositeRule, TextPattern, TextPatternRule};
+    use crate::models::{CellData, CellStyle, RgbColor};
+
+    const PINK: RgbColor = RgbColor::new(255, 204, 253);
+
+    fn row(content: &str, color: RgbColor) -> RowData {
+        RowData::new(
+            0,
+            vec![CellData {
+                column_index: 0,
+                content: content.to_string(),
+                style: CellStyle {
+                    background_color: Some(color),
+                    ..Default::default()
+                },
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            }],
+        )
+    }
+
+    fn pink_rule() -> Box<dyn RecognitionRule> {
+        Box::new(ColorRule::new("pink".to_string(), PINK, Some(0)))
+    }
+
+    #[test]
+    fn test_not_inverts_color_rule() {
+        let rule = NotRule::new("not_pink".to_string(), pink_rule());
+
+        assert!(!rule.matches(&row("小计", PINK)));
+        assert!(rule.matches(&row("小计", RgbColor::white())));
+        assert_eq!(rule.confidence(), 0.0);
+        assert!(rule.explain(&row("小计", PINK)).starts_with("Not[✓ pink"));
+    }
+
+    #[test]
+    fn test_not_inside_and() {
+        let grand_total = TextPatternRule::new(
+            "grand_total_text".to_string(),
+            0,
+            TextPattern::Contains("总计".to_string()),
+            true,
+        );
+        let subtotal = CompositeRule::and(
+            "pink_not_grand_total".to_string(),
+            vec![pink_rule(), Box::new(NotRule::new("not_grand_total".to_string(), Box::new(grand_total)))],
+        );
+
+        assert!(subtotal.matches(&row("小计", PINK)));
+        assert!(!subtotal.matches(&row("总计", PINK)));
+        assert!(!subtotal.matches(&row("小计", RgbColor::white())));
+    }
+}
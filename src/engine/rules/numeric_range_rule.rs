@@ -0,0 +1,159 @@
+// Numeric Range Recognition Rule
+use crate::engine::RecognitionRule;
+use crate::models::RowData;
+use std::str::FromStr;
+
+/// 解析时去掉的货币符号
+const CURRENCY_SYMBOLS: &[char] = &['¥', '￥', '$', '€', '£'];
+
+/// 基于数值范围的识别规则
+///
+/// 检查指定列的数值是否落在 `[min, max]` 范围内（`inclusive` 为 false 时不含端点），
+/// 例如首列序号为 1 - 9999 的是数据行，数值很大的是合计行。
+/// 单元格内容去除首尾空白、千分位逗号和货币符号后解析，不是数字时不匹配。
+#[derive(Debug, Clone)]
+pub struct NumericRangeRule {
+    /// 规则名称
+    pub name: String,
+    /// 要检查的列索引
+    pub column_index: usize,
+    /// 下限
+    pub min: f64,
+    /// 上限
+    pub max: f64,
+    /// 是否包含端点
+    pub inclusive: bool,
+}
+
+impl NumericRangeRule {
+    /// 创建新的数值范围规则
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 规则名称
+    /// * `column_index` - 要检查的列索引
+    /// * `min` - 下限
+    /// * `max` - 上限
+    /// * `inclusive` - 是否包含端点
+    pub fn new(name: String, column_index: usize, min: f64, max: f64, inclusive: bool) -> Self {
+        Self {
+            name,
+            column_index,
+            min,
+            max,
+            inclusive,
+        }
+    }
+
+    /// 读取指定列的数值
+    fn value_of(&self, row_data: &RowData) -> Option<f64> {
+        let content = row_data.get_cell(self.column_index)?.content.trim();
+        let cleaned: String = content
+            .chars()
+            .filter(|c| *c != ',' && !CURRENCY_SYMBOLS.contains(c))
+            .collect();
+        f64::from_str(cleaned.trim()).ok().filter(|value| value.is_finite())
+    }
+
+    /// 数值是否在范围内
+    fn in_range(&self, value: f64) -> bool {
+        if self.inclusive {
+            self.min <= value && value <= self.max
+        } else {
+            self.min < value && value < self.max
+        }
+    }
+}
+
+impl RecognitionRule for NumericRangeRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, row_data: &RowData) -> bool {
+        self.value_of(row_data).is_some_and(|value| self.in_range(value))
+    }
+
+    fn explain(&self, row_data: &RowData) -> String {
+        let (open, close) = if self.inclusive { ('[', ']') } else { ('(', ')') };
+        match row_data.get_cell(self.column_index) {
+            Some(cell) => match self.value_of(row_data) {
+                Some(value) => format!(
+                    "列 {} 数值 {}，范围 {}{}, {}{}",
+                    self.column_index, value, open, self.min, self.max, close
+                ),
+                None => format!("列 {} 内容 \"{}\" 不是数字", self.column_index, cell.content),
+            },
+            None => format!("列 {} 没有单元格", self.column_index),
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn RecognitionRule> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellData, CellStyle};
+
+    fn row_with_first_cell(content: &str) -> RowData {
+        RowData::new(
+            0,
+            vec![CellData {
+                column_index: 0,
+                content: content.to_string(),
+                style: CellStyle::default(),
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_in_range() {
+        let rule = NumericRangeRule::new("index".to_string(), 0, 1.0, 9999.0, true);
+
+        assert!(rule.matches(&row_with_first_cell("3")));
+        assert!(rule.matches(&row_with_first_cell(" 42.5 ")));
+        assert!(rule.matches(&row_with_first_cell("1,200")));
+        assert!(rule.matches(&row_with_first_cell("¥1,200.50")));
+        assert!(rule.matches(&row_with_first_cell("$ 88")));
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let rule = NumericRangeRule::new("index".to_string(), 0, 1.0, 9999.0, true);
+
+        assert!(!rule.matches(&row_with_first_cell("0")));
+        assert!(!rule.matches(&row_with_first_cell("-5")));
+        assert!(!rule.matches(&row_with_first_cell("1,250,000")));
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let inclusive = NumericRangeRule::new("inclusive".to_string(), 0, 1.0, 10.0, true);
+        assert!(inclusive.matches(&row_with_first_cell("1")));
+        assert!(inclusive.matches(&row_with_first_cell("10")));
+
+        let exclusive = NumericRangeRule::new("exclusive".to_string(), 0, 1.0, 10.0, false);
+        assert!(!exclusive.matches(&row_with_first_cell("1")));
+        assert!(!exclusive.matches(&row_with_first_cell("10.0")));
+        assert!(exclusive.matches(&row_with_first_cell("1.01")));
+        assert!(exclusive.matches(&row_with_first_cell("9.99")));
+    }
+
+    #[test]
+    fn test_non_numeric_does_not_match() {
+        let rule = NumericRangeRule::new("any".to_string(), 0, f64::MIN, f64::MAX, true);
+
+        assert!(!rule.matches(&row_with_first_cell("")));
+        assert!(!rule.matches(&row_with_first_cell("合计")));
+        assert!(!rule.matches(&row_with_first_cell("NaN")));
+        assert!(!rule.matches(&row_with_first_cell("inf")));
+        assert!(!rule.matches(&RowData::new(0, Vec::new())));
+        assert!(rule.explain(&row_with_first_cell("合计")).contains("不是数字"));
+    }
+}
@@ -0,0 +1,143 @@
+// Sequence Index Recognition Rule
+use crate::engine::{parse_localized_number, RecognitionRule};
+use crate::models::RowData;
+
+/// 基于序号列的识别规则
+/// 
+/// 数据行通常在首列带有递增序号（1, 2, 3...），据此可以与标签行区分。
+/// 该规则检查指定列是否为正整数；开启连续性检查后，
+/// 还要求序号比上一个数据行的序号大1（序号为1时视为重新开始计数）。
+#[derive(Debug, Clone)]
+pub struct SequenceIndexRule {
+    /// 规则名称
+    pub name: String,
+    /// 要检查的列索引
+    pub column_index: usize,
+    /// 是否检查序号与上一个数据行连续
+    pub check_sequence: bool,
+}
+
+impl SequenceIndexRule {
+    /// 创建新的序号规则
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - 规则名称
+    /// * `column_index` - 要检查的列索引
+    /// * `check_sequence` - 是否检查序号连续性
+    pub fn new(name: String, column_index: usize, check_sequence: bool) -> Self {
+        Self {
+            name,
+            column_index,
+            check_sequence,
+        }
+    }
+
+    /// 读取指定列的序号
+    /// 
+    /// 接受 "3"、"3.0" 和全角 "３" 这类写法，非正整数返回None。
+    /// 带单位的写法（如 "1号"）通常是标签而不是序号，不视为序号。
+    fn index_of(&self, row_data: &RowData) -> Option<u64> {
+        let content = row_data.get_cell(self.column_index)?.content.trim();
+        if let Ok(value) = content.parse::<u64>() {
+            return (value > 0).then_some(value);
+        }
+
+        if !content.ends_with(|c: char| c.is_ascii_digit() || ('０'..='９').contains(&c)) {
+            return None;
+        }
+        let value = parse_localized_number(content)?;
+        if value >= 1.0 && value.fract() == 0.0 && value <= u64::MAX as f64 {
+            Some(value as u64)
+        } else {
+            None
+        }
+    }
+}
+
+impl RecognitionRule for SequenceIndexRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn matches(&self, row_data: &RowData) -> bool {
+        self.index_of(row_data).is_some()
+    }
+
+    fn matches_in_context(&self, row_data: &RowData, previous_data_row: Option<&RowData>) -> bool {
+        let index = match self.index_of(row_data) {
+            Some(i) => i,
+            None => return false,
+        };
+
+        if !self.check_sequence || index == 1 {
+            return true;
+        }
+
+        // 上一个数据行没有序号时无法判断连续性，只要求当前为正整数
+        match previous_data_row.and_then(|prev| self.index_of(prev)) {
+            Some(prev) => index == prev + 1,
+            None => true,
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn RecognitionRule> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellData, CellStyle};
+
+    fn row_with_first_cell(content: &str) -> RowData {
+        RowData::new(
+            0,
+            vec![CellData {
+                column_index: 0,
+                content: content.to_string(),
+                style: CellStyle::default(),
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            }],
+        )
+    }
+
+    #[test]
+    fn test_valid_index_matches() {
+        let rule = SequenceIndexRule::new("index_rule".to_string(), 0, false);
+
+        assert!(rule.matches(&row_with_first_cell("3")));
+        assert!(rule.matches(&row_with_first_cell(" 12 ")));
+        assert!(rule.matches(&row_with_first_cell("4.0")));
+        assert!(rule.matches(&row_with_first_cell("１２")));
+        // 带单位的是标签
+        assert!(!rule.matches(&row_with_first_cell("1号")));
+    }
+
+    #[test]
+    fn test_non_numeric_cell_does_not_match() {
+        let rule = SequenceIndexRule::new("index_rule".to_string(), 0, false);
+
+        assert!(!rule.matches(&row_with_first_cell("序号")));
+        assert!(!rule.matches(&row_with_first_cell("")));
+        assert!(!rule.matches(&row_with_first_cell("0")));
+        assert!(!rule.matches(&row_with_first_cell("-1")));
+        assert!(!rule.matches(&row_with_first_cell("1.5")));
+    }
+
+    #[test]
+    fn test_sequence_check_uses_previous_data_row() {
+        let rule = SequenceIndexRule::new("index_rule".to_string(), 0, true);
+        let previous = row_with_first_cell("2");
+
+        assert!(rule.matches_in_context(&row_with_first_cell("3"), Some(&previous)));
+        assert!(!rule.matches_in_context(&row_with_first_cell("5"), Some(&previous)));
+        // 序号为1表示重新开始计数
+        assert!(rule.matches_in_context(&row_with_first_cell("1"), Some(&previous)));
+        // 没有上一个数据行时只检查是否为正整数
+        assert!(rule.matches_in_context(&row_with_first_cell("7"), None));
+    }
+}
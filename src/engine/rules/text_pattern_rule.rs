@@ -2,32 +2,138 @@
 use crate::engine::RecognitionRule;
 use crate::models::RowData;
 use regex::Regex;
+use std::sync::OnceLock;
 
 /// 文本模式类型
 #[derive(Debug, Clone)]
 pub enum TextPattern {
-    /// 精确匹配
+    /// 精确匹配（单元格内容和目标文本都去除首尾空白后比较）
     Exact(String),
     /// 包含子串
     Contains(String),
+    /// 以指定文本开头（如项目编号前缀 “G00E-”）
+    StartsWith(String),
+    /// 以指定文本结尾（如金额单位）
+    EndsWith(String),
     /// 正则表达式
     Regex(String),
 }
 
+impl TextPattern {
+    /// 判断单元格内容是否符合模式（内容先去除首尾空白）
+    ///
+    /// 正则表达式无效时返回错误，由调用方决定如何记录。
+    /// 每次调用都会重新编译正则表达式，逐行匹配时应使用规则内的 [`RegexCache`]。
+    pub fn is_match(&self, content: &str, case_sensitive: bool) -> Result<bool, regex::Error> {
+        match self.compile_regex(case_sensitive) {
+            Some(regex) => Ok(regex?.is_match(content.trim())),
+            None => Ok(self.matches_text(content, case_sensitive)),
+        }
+    }
+
+    /// 编译正则模式（不区分大小写时加 `(?i)`），非正则模式返回 None
+    pub fn compile_regex(&self, case_sensitive: bool) -> Option<Result<Regex, regex::Error>> {
+        match self {
+            TextPattern::Regex(pattern) if case_sensitive => Some(Regex::new(pattern)),
+            TextPattern::Regex(pattern) => Some(Regex::new(&format!("(?i){}", pattern))),
+            _ => None,
+        }
+    }
+
+    /// 非正则模式的匹配（正则模式始终返回 false）
+    fn matches_text(&self, content: &str, case_sensitive: bool) -> bool {
+        let content = content.trim();
+
+        // 根据大小写敏感设置处理内容
+        let content_to_match = if case_sensitive {
+            content.to_string()
+        } else {
+            content.to_lowercase()
+        };
+
+        // 根据模式类型进行匹配
+        match self {
+            TextPattern::Exact(target) => {
+                // 内容已去除首尾空白，目标也同样处理，避免目标带空格时永远无法匹配
+                let target = target.trim();
+                if case_sensitive {
+                    content == target
+                } else {
+                    content_to_match == target.to_lowercase()
+                }
+            }
+            TextPattern::Contains(substring) => {
+                if case_sensitive {
+                    content.contains(substring.as_str())
+                } else {
+                    content_to_match.contains(&substring.to_lowercase())
+                }
+            }
+            TextPattern::StartsWith(prefix) => {
+                if case_sensitive {
+                    content.starts_with(prefix.as_str())
+                } else {
+                    content_to_match.starts_with(&prefix.to_lowercase())
+                }
+            }
+            TextPattern::EndsWith(suffix) => {
+                if case_sensitive {
+                    content.ends_with(suffix.as_str())
+                } else {
+                    content_to_match.ends_with(&suffix.to_lowercase())
+                }
+            }
+            TextPattern::Regex(_) => false,
+        }
+    }
+}
+
+/// 规则内缓存的已编译正则表达式
+///
+/// 首次匹配时按规则的模式和大小写设置编译一次，之后逐行复用。
+/// 正则表达式无效时只报告一次，之后该规则始终不匹配。
+#[derive(Debug, Clone, Default)]
+pub struct RegexCache(OnceLock<Option<Regex>>);
+
+impl RegexCache {
+    /// 判断单元格内容是否符合模式，正则模式使用缓存的编译结果
+    pub fn is_match(&self, pattern: &TextPattern, content: &str, case_sensitive: bool, rule_name: &str) -> bool {
+        if !matches!(pattern, TextPattern::Regex(_)) {
+            return pattern.matches_text(content, case_sensitive);
+        }
+        let regex = self.0.get_or_init(|| match pattern.compile_regex(case_sensitive)? {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                // 记录错误但不崩溃
+                eprintln!("Invalid regex pattern '{:?}' in rule '{}': {}", pattern, rule_name, e);
+                None
+            }
+        });
+        regex.as_ref().is_some_and(|regex| regex.is_match(content.trim()))
+    }
+
+    /// 是否已编译过正则表达式
+    pub fn is_compiled(&self) -> bool {
+        self.0.get().is_some()
+    }
+}
+
 /// 基于文本模式的识别规则
 /// 
 /// 该规则通过检查指定列的单元格内容来判断行类型。
-/// 支持精确匹配、包含匹配和正则表达式匹配三种模式。
+/// 支持精确匹配、包含、前缀、后缀和正则表达式匹配。
 #[derive(Debug, Clone)]
 pub struct TextPatternRule {
     /// 规则名称
     pub name: String,
     /// 要检查的列索引
     pub column_index: usize,
-    /// 文本模式
-    pub pattern: TextPattern,
-    /// 是否区分大小写
-    pub case_sensitive: bool,
+    /// 文本模式（通过 `set_pattern` 修改，以便重新编译正则表达式）
+    pattern: TextPattern,
+    /// 是否区分大小写（通过 `set_case_sensitive` 修改）
+    case_sensitive: bool,
+    /// 已编译的正则表达式，修改模式或大小写设置时清空
+    regex_cache: RegexCache,
 }
 
 impl TextPatternRule {
@@ -50,8 +156,31 @@ impl TextPatternRule {
             column_index,
             pattern,
             case_sensitive,
+            regex_cache: RegexCache::default(),
         }
     }
+
+    /// 文本模式
+    pub fn pattern(&self) -> &TextPattern {
+        &self.pattern
+    }
+
+    /// 是否区分大小写
+    pub fn case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// 修改文本模式，下次匹配时重新编译正则表达式
+    pub fn set_pattern(&mut self, pattern: TextPattern) {
+        self.pattern = pattern;
+        self.regex_cache = RegexCache::default();
+    }
+
+    /// 修改是否区分大小写，下次匹配时重新编译正则表达式
+    pub fn set_case_sensitive(&mut self, case_sensitive: bool) {
+        self.case_sensitive = case_sensitive;
+        self.regex_cache = RegexCache::default();
+    }
 }
 
 impl RecognitionRule for TextPatternRule {
@@ -59,6 +188,16 @@ impl RecognitionRule for TextPatternRule {
         &self.name
     }
     
+    fn explain(&self, row_data: &RowData) -> String {
+        match row_data.get_cell(self.column_index) {
+            Some(cell) => format!(
+                "列 {} 内容 \"{}\"，模式 {:?}",
+                self.column_index, cell.content, self.pattern
+            ),
+            None => format!("列 {} 没有单元格，模式 {:?}", self.column_index, self.pattern),
+        }
+    }
+
     fn matches(&self, row_data: &RowData) -> bool {
         // 获取指定列的单元格
         let cell = match row_data.get_cell(self.column_index) {
@@ -66,50 +205,8 @@ impl RecognitionRule for TextPatternRule {
             None => return false,
         };
         
-        // 获取单元格内容并trim
-        let content = cell.content.trim();
-        
-        // 根据大小写敏感设置处理内容
-        let content_to_match = if self.case_sensitive {
-            content.to_string()
-        } else {
-            content.to_lowercase()
-        };
-        
-        // 根据模式类型进行匹配
-        match &self.pattern {
-            TextPattern::Exact(target) => {
-                if self.case_sensitive {
-                    content == target
-                } else {
-                    content_to_match == target.to_lowercase()
-                }
-            }
-            TextPattern::Contains(substring) => {
-                if self.case_sensitive {
-                    content.contains(substring)
-                } else {
-                    content_to_match.contains(&substring.to_lowercase())
-                }
-            }
-            TextPattern::Regex(pattern) => {
-                // 尝试编译正则表达式
-                let regex_result = if self.case_sensitive {
-                    Regex::new(pattern)
-                } else {
-                    Regex::new(&format!("(?i){}", pattern))
-                };
-                
-                match regex_result {
-                    Ok(regex) => regex.is_match(content),
-                    Err(e) => {
-                        // 记录错误但不崩溃
-                        eprintln!("Invalid regex pattern '{}' in rule '{}': {}", pattern, self.name, e);
-                        false
-                    }
-                }
-            }
-        }
+        self.regex_cache
+            .is_match(&self.pattern, &cell.content, self.case_sensitive, &self.name)
     }
     
     fn clone_box(&self) -> Box<dyn RecognitionRule> {
@@ -139,12 +236,16 @@ mod tests {
                     content: "A".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
                 CellData {
                     column_index: 1,
                     content: "柜号:".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -160,12 +261,16 @@ mod tests {
                     content: "A".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
                 CellData {
                     column_index: 1,
                     content: "柜号：".to_string(), // 不同的冒号
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -190,6 +295,8 @@ mod tests {
                     content: "  小计: 100  ".to_string(), // 带空格
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -214,6 +321,8 @@ mod tests {
                     content: "G00E-500009085-00011".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -228,6 +337,8 @@ mod tests {
                     content: "Invalid".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -252,6 +363,8 @@ mod tests {
                     content: "Test".to_string(),
                     style: CellStyle::default(),
                     merge_info: None,
+                    comment: None,
+                    raw_content: None,
                 },
             ],
         };
@@ -259,4 +372,147 @@ mod tests {
         // 无效的正则应该返回false而不是崩溃
         assert!(!rule.matches(&row));
     }
+
+    #[test]
+    fn test_exact_match_trims_target() {
+        let rule = TextPatternRule::new(
+            "padded_exact".to_string(),
+            0,
+            TextPattern::Exact(" 柜号: ".to_string()),
+            true,
+        );
+
+        let row = |content: &str| {
+            RowData::new(
+                0,
+                vec![CellData {
+                    column_index: 0,
+                    content: content.to_string(),
+                    style: CellStyle::default(),
+                    merge_info: None,
+                    comment: None,
+                    raw_content: None,
+                }],
+            )
+        };
+
+        assert!(rule.matches(&row("柜号:")));
+        assert!(rule.matches(&row("  柜号:  ")));
+        assert!(!rule.matches(&row("柜号: A01")));
+    }
+
+    #[test]
+    fn test_starts_with() {
+        let pattern = TextPattern::StartsWith("G00E-".to_string());
+
+        assert_eq!(pattern.is_match("G00E-2024-001", true), Ok(true));
+        assert_eq!(pattern.is_match("  G00E-2024-001", true), Ok(true));
+        assert_eq!(pattern.is_match("g00e-2024-001", true), Ok(false));
+        assert_eq!(pattern.is_match("项目 G00E-2024-001", true), Ok(false));
+
+        assert_eq!(pattern.is_match(" g00e-2024-001 ", false), Ok(true));
+        assert_eq!(pattern.is_match("xG00E-2024", false), Ok(false));
+    }
+
+    #[test]
+    fn test_ends_with() {
+        let pattern = TextPattern::EndsWith("RMB".to_string());
+
+        assert_eq!(pattern.is_match("合计 1,200 RMB", true), Ok(true));
+        assert_eq!(pattern.is_match("合计 1,200 RMB  ", true), Ok(true));
+        assert_eq!(pattern.is_match("合计 1,200 rmb", true), Ok(false));
+        assert_eq!(pattern.is_match("RMB 合计", true), Ok(false));
+
+        assert_eq!(pattern.is_match("合计 1,200 rmb\n", false), Ok(true));
+        assert_eq!(pattern.is_match("合计 1,200", false), Ok(false));
+    }
+
+    #[test]
+    fn test_cached_regex_matches_uncached_on_10k_rows() {
+        use crate::engine::{FileTypeProfile, RowTypeDefinition, RowTypeIdentifier};
+        use crate::models::{RowType, WorksheetData};
+
+        let pattern = TextPattern::Regex(r"^g00e-\d{4}-\d{3}$".to_string());
+        let contents = ["G00E-2024-001", "g00e-2024-002", "电缆", " G00E-2024-003 ", "G00E-24-1", ""];
+        let worksheet = WorksheetData {
+            name: "Sheet1".to_string(),
+            rows: (0..10_000)
+                .map(|i| {
+                    RowData::new(
+                        i,
+                        vec![CellData {
+                            column_index: 0,
+                            content: contents[i % contents.len()].to_string(),
+                            style: CellStyle::default(),
+                            merge_info: None,
+                            comment: None,
+                            raw_content: None,
+                        }],
+                    )
+                })
+                .collect(),
+        };
+
+        let rule = TextPatternRule::new("project".to_string(), 0, pattern.clone(), false);
+        assert!(!rule.regex_cache.is_compiled());
+
+        // 与每行重新编译的结果一致
+        let expected: Vec<bool> = worksheet
+            .rows
+            .iter()
+            .map(|row| pattern.is_match(&row.cells[0].content, false).unwrap())
+            .collect();
+        let actual: Vec<bool> = worksheet.rows.iter().map(|row| rule.matches(row)).collect();
+        assert_eq!(actual, expected);
+        assert!(rule.regex_cache.is_compiled());
+        assert_eq!(actual.iter().filter(|m| **m).count(), 5_001);
+
+        // 整表识别结果同样一致
+        let mut profile = FileTypeProfile::new("regex".to_string(), String::new());
+        profile.add_row_type(RowTypeDefinition::new(
+            RowType::ProjectNumber,
+            "项目编号".to_string(),
+            String::new(),
+            Box::new(rule),
+            10,
+        ));
+        let results = RowTypeIdentifier::new(profile).identify_all_rows(&worksheet);
+        let identified: Vec<bool> = results.iter().map(|r| r.row_type == RowType::ProjectNumber).collect();
+        assert_eq!(identified, expected);
+    }
+
+    #[test]
+    fn test_setters_reset_compiled_regex() {
+        let row = RowData::new(
+            0,
+            vec![CellData {
+                column_index: 0,
+                content: "Project-7".to_string(),
+                style: CellStyle::default(),
+                merge_info: None,
+                comment: None,
+                raw_content: None,
+            }],
+        );
+
+        let mut rule = TextPatternRule::new(
+            "project".to_string(),
+            0,
+            TextPattern::Regex(r"^project-\d+$".to_string()),
+            true,
+        );
+        assert!(!rule.matches(&row));
+        assert!(rule.regex_cache.is_compiled());
+
+        // 修改大小写设置后按新设置重新编译
+        rule.set_case_sensitive(false);
+        assert!(!rule.regex_cache.is_compiled());
+        assert!(rule.matches(&row));
+        assert!(!rule.case_sensitive());
+
+        // 修改模式后同样重新编译
+        rule.set_pattern(TextPattern::Regex(r"^task-\d+$".to_string()));
+        assert!(!rule.matches(&row));
+        assert!(matches!(rule.pattern(), TextPattern::Regex(p) if p == r"^task-\d+$"));
+    }
 }
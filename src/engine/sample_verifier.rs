@@ -0,0 +1,211 @@
+// 标注样本验证
+//
+// 样本目录中的每个工作表 `<名称>.xlsx` 配有标注文件 `<名称>.json`，
+// 记录每一行期望的行类型。用当前配置识别样本并与标注逐行比较，
+// 防止调整规则或优先级后出现识别回退。
+use crate::engine::{ExcelExtractor, IdentificationError, IdentificationResult, RowTypeIdentifier};
+use crate::models::RowType;
+use serde::Deserialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// 标注文件内容
+#[derive(Debug, Deserialize)]
+struct SampleLabels {
+    /// 工作表索引（从0开始）
+    #[serde(default)]
+    sheet: usize,
+    /// 每行期望的行类型，使用显示名称或变体名称
+    rows: Vec<String>,
+}
+
+/// 识别结果与标注不一致的行
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowMismatch {
+    /// 行号（从0开始）
+    pub row_index: usize,
+    /// 标注的行类型（样本行数多于标注时为 None）
+    pub expected: Option<RowType>,
+    /// 识别出的行类型（标注行数多于样本时为 None）
+    pub actual: Option<RowType>,
+}
+
+impl fmt::Display for RowMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |t: &Option<RowType>| t.as_ref().map_or("(缺失)".to_string(), |t| t.display_name().to_string());
+        write!(
+            f,
+            "第 {} 行: 期望 {}，实际 {}",
+            self.row_index + 1,
+            name(&self.expected),
+            name(&self.actual)
+        )
+    }
+}
+
+/// 单个样本的验证结果
+#[derive(Debug, Clone)]
+pub struct SampleReport {
+    pub sample: PathBuf,
+    pub mismatches: Vec<RowMismatch>,
+}
+
+impl SampleReport {
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// 整个样本目录的验证结果
+#[derive(Debug, Clone, Default)]
+pub struct VerificationReport {
+    pub samples: Vec<SampleReport>,
+}
+
+impl VerificationReport {
+    /// 至少有一个样本且全部通过
+    pub fn passed(&self) -> bool {
+        !self.samples.is_empty() && self.samples.iter().all(SampleReport::passed)
+    }
+
+    pub fn failed_samples(&self) -> impl Iterator<Item = &SampleReport> {
+        self.samples.iter().filter(|s| !s.passed())
+    }
+
+    /// 把结果写入日志面板
+    pub fn log(&self) {
+        let failed = self.failed_samples().count();
+        if self.passed() {
+            crate::log_info!("✓ 配置验证通过: {} 个样本", self.samples.len());
+            return;
+        }
+        if self.samples.is_empty() {
+            crate::log_warning!("未找到带标注的样本（需要同名的 .xlsx 和 .json 文件）");
+            return;
+        }
+
+        crate::log_error!("✗ 配置验证失败: {}/{} 个样本不一致", failed, self.samples.len());
+        for sample in self.failed_samples() {
+            crate::log_error!("  {}", sample.sample.display());
+            for mismatch in &sample.mismatches {
+                crate::log_error!("    {}", mismatch);
+            }
+        }
+    }
+}
+
+/// 比较标注与识别结果，返回不一致的行
+pub fn compare_rows(expected: &[RowType], actual: &[RowType]) -> Vec<RowMismatch> {
+    (0..expected.len().max(actual.len()))
+        .filter_map(|row_index| {
+            let expected = expected.get(row_index);
+            let actual = actual.get(row_index);
+            (expected != actual).then(|| RowMismatch {
+                row_index,
+                expected: expected.cloned(),
+                actual: actual.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// 验证单个样本，标注文件为同目录下同名的 `.json`
+pub fn verify_sample(sample: &Path, identifier: &RowTypeIdentifier) -> IdentificationResult<SampleReport> {
+    let label_path = sample.with_extension("json");
+    let text = std::fs::read_to_string(&label_path).map_err(|e| {
+        IdentificationError::file_read_error(format!("无法读取标注文件 {}: {}", label_path.display(), e))
+    })?;
+    let labels: SampleLabels = serde_json::from_str(&text).map_err(|e| {
+        IdentificationError::invalid_configuration(format!("标注文件格式错误 {}: {}", label_path.display(), e))
+    })?;
+
+    let worksheet = ExcelExtractor::read_worksheet(sample, labels.sheet)?;
+    let actual: Vec<RowType> = identifier
+        .identify_all_rows(&worksheet)
+        .into_iter()
+        .map(|r| r.row_type)
+        .collect();
+    let expected: Vec<RowType> = labels.rows.iter().map(|l| RowType::from_label(l)).collect();
+
+    Ok(SampleReport {
+        sample: sample.to_path_buf(),
+        mismatches: compare_rows(&expected, &actual),
+    })
+}
+
+/// 验证目录中所有带标注的样本（按文件名排序），没有标注文件的工作表会被跳过
+pub fn verify_samples(dir: &Path, identifier: &RowTypeIdentifier) -> IdentificationResult<VerificationReport> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        IdentificationError::file_read_error(format!("无法读取样本目录 {}: {}", dir.display(), e))
+    })?;
+
+    let mut samples: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xlsx"))
+                && path.with_extension("json").is_file()
+        })
+        .collect();
+    samples.sort();
+
+    let mut report = VerificationReport::default();
+    for sample in samples {
+        report.samples.push(verify_sample(&sample, identifier)?);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::FileTypeProfile;
+
+    /// 随仓库提供的标注样本目录
+    fn fixtures_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/labeled_samples")
+    }
+
+    #[test]
+    fn test_cargo_profile_matches_labeled_fixtures() {
+        let identifier = RowTypeIdentifier::new(FileTypeProfile::cargo_analysis());
+        let report = verify_samples(&fixtures_dir(), &identifier).unwrap();
+
+        assert!(!report.samples.is_empty());
+        for sample in report.failed_samples() {
+            for mismatch in &sample.mismatches {
+                eprintln!("{}: {}", sample.sample.display(), mismatch);
+            }
+        }
+        assert!(report.passed());
+    }
+
+    #[test]
+    fn test_compare_rows_reports_mismatches_and_length_difference() {
+        let expected = vec![RowType::Header, RowType::Data, RowType::SubTotal];
+        let actual = vec![RowType::Header, RowType::Unknown];
+
+        let mismatches = compare_rows(&expected, &actual);
+        assert_eq!(
+            mismatches,
+            vec![
+                RowMismatch {
+                    row_index: 1,
+                    expected: Some(RowType::Data),
+                    actual: Some(RowType::Unknown),
+                },
+                RowMismatch {
+                    row_index: 2,
+                    expected: Some(RowType::SubTotal),
+                    actual: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_row_type_from_label() {
+        assert_eq!(RowType::from_label("表头行"), RowType::Header);
+        assert_eq!(RowType::from_label("SubTotal"), RowType::SubTotal);
+        assert_eq!(RowType::from_label("备注行"), RowType::Custom("备注行".to_string()));
+    }
+}
@@ -0,0 +1,347 @@
+// 两个工作表的单元格级对比
+//
+// 核对同一份货物分析表的两个版本：按坐标列出新增、删除和修改的单元格，
+// 以及识别出的行类型发生变化的行。结果可以输出到日志，
+// 也可以在新版本的副本上用底色标出差异。
+use crate::engine::{ExcelExtractor, IdentificationError, IdentificationResult, RowTypeIdentifier};
+use crate::models::{RgbColor, RowData, RowIdentificationResult, RowType, WorksheetData};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::path::Path;
+
+/// 单元格变化类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellChangeKind {
+    /// 旧版本为空，新版本有内容
+    Added,
+    /// 旧版本有内容，新版本为空
+    Removed,
+    /// 两个版本内容不同
+    Changed,
+}
+
+impl CellChangeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CellChangeKind::Added => "新增",
+            CellChangeKind::Removed => "删除",
+            CellChangeKind::Changed => "修改",
+        }
+    }
+
+    /// 差异工作簿中标记该变化的底色
+    pub fn highlight_color(&self) -> RgbColor {
+        match self {
+            CellChangeKind::Added => RgbColor::new(198, 239, 206),
+            CellChangeKind::Removed => RgbColor::new(255, 199, 206),
+            CellChangeKind::Changed => RgbColor::new(255, 235, 156),
+        }
+    }
+}
+
+/// 单个单元格的变化
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellChange {
+    /// 行号（从0开始）
+    pub row_index: usize,
+    /// 列号（从0开始）
+    pub column_index: usize,
+    pub kind: CellChangeKind,
+    /// 旧版本内容（新增时为 None）
+    pub old: Option<String>,
+    /// 新版本内容（删除时为 None）
+    pub new: Option<String>,
+}
+
+impl fmt::Display for CellChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} 第 {} 行第 {} 列: {:?} -> {:?}",
+            self.kind.label(),
+            self.row_index + 1,
+            self.column_index + 1,
+            self.old.as_deref().unwrap_or(""),
+            self.new.as_deref().unwrap_or("")
+        )
+    }
+}
+
+/// 行类型的变化
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowTypeChange {
+    /// 行号（从0开始）
+    pub row_index: usize,
+    /// 旧版本的行类型（旧版本没有该行时为 None）
+    pub old: Option<RowType>,
+    /// 新版本的行类型（新版本没有该行时为 None）
+    pub new: Option<RowType>,
+}
+
+impl fmt::Display for RowTypeChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |t: &Option<RowType>| t.as_ref().map_or("(缺失)", |t| t.display_name()).to_string();
+        write!(f, "第 {} 行类型: {} -> {}", self.row_index + 1, name(&self.old), name(&self.new))
+    }
+}
+
+/// 两个工作表的对比结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SheetComparison {
+    /// 按行、列排序的单元格变化
+    pub cell_changes: Vec<CellChange>,
+    /// 按行排序的行类型变化
+    pub row_type_changes: Vec<RowTypeChange>,
+}
+
+impl SheetComparison {
+    pub fn is_identical(&self) -> bool {
+        self.cell_changes.is_empty() && self.row_type_changes.is_empty()
+    }
+
+    /// 指定类型的单元格变化数量
+    pub fn count(&self, kind: CellChangeKind) -> usize {
+        self.cell_changes.iter().filter(|c| c.kind == kind).count()
+    }
+
+    /// 一行摘要，如 “新增 1，删除 0，修改 2，行类型变化 1”
+    pub fn summary(&self) -> String {
+        format!(
+            "新增 {}，删除 {}，修改 {}，行类型变化 {}",
+            self.count(CellChangeKind::Added),
+            self.count(CellChangeKind::Removed),
+            self.count(CellChangeKind::Changed),
+            self.row_type_changes.len()
+        )
+    }
+
+    /// 把对比结果写入日志面板
+    pub fn log(&self) {
+        if self.is_identical() {
+            crate::log_info!("✓ 两个工作表内容一致");
+            return;
+        }
+        crate::log_info!("工作表对比: {}", self.summary());
+        for change in &self.cell_changes {
+            crate::log_info!("  {}", change);
+        }
+        for change in &self.row_type_changes {
+            crate::log_info!("  {}", change);
+        }
+    }
+}
+
+/// 按坐标比较两个工作表的单元格内容（去除首尾空白后比较，空单元格视为不存在）
+pub fn compare_cells(old: &WorksheetData, new: &WorksheetData) -> Vec<CellChange> {
+    // 完全一致时无需逐格比较
+    if new.first_difference(old).is_none() {
+        return Vec::new();
+    }
+
+    let index = |sheet: &WorksheetData| -> HashMap<usize, &RowData> {
+        sheet.rows.iter().map(|row| (row.row_index, row)).collect()
+    };
+    let old_rows = index(old);
+    let new_rows = index(new);
+    let row_indices: BTreeSet<usize> = old_rows.keys().chain(new_rows.keys()).copied().collect();
+
+    let content = |row: Option<&&RowData>, col: usize| -> Option<String> {
+        row.and_then(|row| row.get_cell(col))
+            .map(|cell| cell.content.trim())
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+    };
+
+    let mut changes = Vec::new();
+    for row_index in row_indices {
+        let old_row = old_rows.get(&row_index);
+        let new_row = new_rows.get(&row_index);
+        let width = [old_row, new_row]
+            .iter()
+            .filter_map(|row| row.map(|row| row.cells.len()))
+            .max()
+            .unwrap_or(0);
+
+        for column_index in 0..width {
+            let old = content(old_row, column_index);
+            let new = content(new_row, column_index);
+            let kind = match (&old, &new) {
+                (None, Some(_)) => CellChangeKind::Added,
+                (Some(_), None) => CellChangeKind::Removed,
+                (Some(a), Some(b)) if a != b => CellChangeKind::Changed,
+                _ => continue,
+            };
+            changes.push(CellChange {
+                row_index,
+                column_index,
+                kind,
+                old,
+                new,
+            });
+        }
+    }
+    changes
+}
+
+/// 按行号比较两次识别的行类型
+pub fn compare_row_types(old: &[RowIdentificationResult], new: &[RowIdentificationResult]) -> Vec<RowTypeChange> {
+    let old_types: HashMap<usize, &RowType> = old.iter().map(|r| (r.row_index, &r.row_type)).collect();
+    let new_types: HashMap<usize, &RowType> = new.iter().map(|r| (r.row_index, &r.row_type)).collect();
+    let row_indices: BTreeSet<usize> = old_types.keys().chain(new_types.keys()).copied().collect();
+
+    row_indices
+        .into_iter()
+        .filter_map(|row_index| {
+            let old = old_types.get(&row_index).map(|t| (*t).clone());
+            let new = new_types.get(&row_index).map(|t| (*t).clone());
+            (old != new).then_some(RowTypeChange { row_index, old, new })
+        })
+        .collect()
+}
+
+/// 对比两个工作表（单元格内容和识别出的行类型）
+pub fn compare_worksheets(
+    old: &WorksheetData,
+    new: &WorksheetData,
+    identifier: &RowTypeIdentifier,
+) -> SheetComparison {
+    SheetComparison {
+        cell_changes: compare_cells(old, new),
+        row_type_changes: compare_row_types(&identifier.identify_all_rows(old), &identifier.identify_all_rows(new)),
+    }
+}
+
+/// 读取两个文件的第一个工作表并对比
+pub fn compare_files(old: &Path, new: &Path, identifier: &RowTypeIdentifier) -> IdentificationResult<SheetComparison> {
+    let old_sheet = ExcelExtractor::read_worksheet(old, 0)?;
+    let new_sheet = ExcelExtractor::read_worksheet(new, 0)?;
+    Ok(compare_worksheets(&old_sheet, &new_sheet, identifier))
+}
+
+/// 复制新版本工作簿并用底色标出差异，删除的单元格写回旧内容
+pub fn write_diff_workbook(new: &Path, comparison: &SheetComparison, output: &Path) -> IdentificationResult<()> {
+    let mut book = umya_spreadsheet::reader::xlsx::read(new)
+        .map_err(|e| IdentificationError::file_read_error(format!("无法打开Excel文件: {}", e)))?;
+    let sheet = book
+        .get_sheet_mut(&0)
+        .ok_or_else(|| IdentificationError::worksheet_not_found("0"))?;
+
+    for change in &comparison.cell_changes {
+        let coordinate = (change.column_index as u32 + 1, change.row_index as u32 + 1);
+        let cell = sheet.get_cell_mut(coordinate);
+        if let (CellChangeKind::Removed, Some(old)) = (change.kind, &change.old) {
+            cell.set_value(old.clone());
+        }
+        cell.get_style_mut()
+            .set_background_color(format!("FF{}", change.kind.highlight_color().to_hex()));
+    }
+
+    umya_spreadsheet::writer::xlsx::write(&book, output)
+        .map_err(|e| IdentificationError::excel_data_error(format!("写入差异工作簿失败: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::FileTypeProfile;
+    use crate::models::{CellData, CellStyle};
+    use tempfile::tempdir;
+
+    fn worksheet(rows: &[&[&str]]) -> WorksheetData {
+        WorksheetData {
+            name: "Sheet1".to_string(),
+            rows: rows
+                .iter()
+                .enumerate()
+                .map(|(row_index, contents)| {
+                    let cells = contents
+                        .iter()
+                        .enumerate()
+                        .map(|(column_index, content)| CellData {
+                            column_index,
+                            content: content.to_string(),
+                            style: CellStyle::default(),
+                            merge_info: None,
+                            comment: None,
+                            raw_content: None,
+                        })
+                        .collect();
+                    RowData::new(row_index, cells)
+                })
+                .collect(),
+        }
+    }
+
+    fn result(row_index: usize, row_type: RowType) -> RowIdentificationResult {
+        RowIdentificationResult {
+            row_index,
+            row_type,
+            matched_rule: "test".to_string(),
+            confidence: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_compare_nearly_identical_worksheets() {
+        let old = worksheet(&[&["序号", "名称", "数量"], &["1", "电缆", "10"], &["2", "开关", "5"]]);
+        let new = worksheet(&[&["序号", "名称", "数量"], &["1", "电缆", "12"], &["2", "", "5", "备注"]]);
+
+        let changes = compare_cells(&old, &new);
+        let summary: Vec<(usize, usize, CellChangeKind)> =
+            changes.iter().map(|c| (c.row_index, c.column_index, c.kind)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, 2, CellChangeKind::Changed),
+                (2, 1, CellChangeKind::Removed),
+                (2, 3, CellChangeKind::Added),
+            ]
+        );
+        assert_eq!(changes[0].old.as_deref(), Some("10"));
+        assert_eq!(changes[0].new.as_deref(), Some("12"));
+
+        assert!(compare_cells(&old, &old.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_compare_row_types() {
+        let old = vec![result(0, RowType::Header), result(1, RowType::Data)];
+        let new = vec![result(0, RowType::Header), result(1, RowType::SubTotal), result(2, RowType::Data)];
+
+        let changes = compare_row_types(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].old, Some(RowType::Data));
+        assert_eq!(changes[0].new, Some(RowType::SubTotal));
+        assert_eq!(changes[1].old, None);
+    }
+
+    #[test]
+    fn test_compare_files_and_write_diff_workbook() {
+        let dir = tempdir().unwrap();
+        let write = |name: &str, quantity: &str| {
+            let path = dir.path().join(name);
+            let mut book = umya_spreadsheet::new_file();
+            let sheet = book.get_sheet_mut(&0).unwrap();
+            sheet.get_cell_mut("A1").set_value("名称");
+            sheet.get_cell_mut("B1").set_value("数量");
+            sheet.get_cell_mut("A2").set_value("电缆");
+            sheet.get_cell_mut("B2").set_value(quantity);
+            umya_spreadsheet::writer::xlsx::write(&book, &path).unwrap();
+            path
+        };
+        let old = write("old.xlsx", "10");
+        let new = write("new.xlsx", "12");
+
+        let identifier = RowTypeIdentifier::new(FileTypeProfile::cargo_analysis());
+        let comparison = compare_files(&old, &new, &identifier).unwrap();
+        assert_eq!(comparison.count(CellChangeKind::Changed), 1);
+        assert!(comparison.summary().contains("修改 1"));
+
+        let output = dir.path().join("diff.xlsx");
+        write_diff_workbook(&new, &comparison, &output).unwrap();
+        let diff = ExcelExtractor::read_worksheet(&output, 0).unwrap();
+        let cell = diff.get_row(1).unwrap().get_cell(1).unwrap();
+        assert_eq!(cell.content, "12");
+        assert_eq!(cell.style.background_color, Some(CellChangeKind::Changed.highlight_color()));
+    }
+}
@@ -0,0 +1,173 @@
+// Cell Text Normalization
+use std::borrow::Cow;
+
+/// 读取单元格时的文本规范化设置
+///
+/// 单元格常带有首尾空格、连续空白或全角/半角混用，导致文本规则匹配失败。
+/// 默认不做任何处理。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextNormalization {
+    /// 去除首尾空白
+    pub trim: bool,
+    /// 把连续空白（含换行、全角空格）合并为一个半角空格
+    pub collapse_whitespace: bool,
+    /// 全角 ASCII 字符（如 `：`、`Ａ`、`１`）转为半角
+    pub full_width_to_half: bool,
+}
+
+impl TextNormalization {
+    /// 去除首尾空白并合并连续空白
+    pub fn standard() -> Self {
+        Self {
+            trim: true,
+            collapse_whitespace: true,
+            full_width_to_half: false,
+        }
+    }
+
+    /// 在 [`standard`](Self::standard) 基础上同时转换全角字符
+    pub fn with_full_width_to_half(mut self, enabled: bool) -> Self {
+        self.full_width_to_half = enabled;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.trim || self.collapse_whitespace || self.full_width_to_half
+    }
+
+    /// 规范化文本，内容没有变化时不分配新字符串
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !self.is_enabled() {
+            return Cow::Borrowed(text);
+        }
+
+        let mut result: String = if self.full_width_to_half {
+            text.chars().map(to_half_width).collect()
+        } else {
+            text.to_string()
+        };
+
+        if self.collapse_whitespace {
+            let mut collapsed = String::with_capacity(result.len());
+            let mut in_whitespace = false;
+            for c in result.chars() {
+                if c.is_whitespace() {
+                    if !in_whitespace {
+                        collapsed.push(' ');
+                    }
+                    in_whitespace = true;
+                } else {
+                    collapsed.push(c);
+                    in_whitespace = false;
+                }
+            }
+            result = collapsed;
+        }
+
+        if self.trim {
+            result = result.trim().to_string();
+        }
+
+        if result == text {
+            Cow::Borrowed(text)
+        } else {
+            Cow::Owned(result)
+        }
+    }
+}
+
+/// 全角 ASCII（U+FF01..U+FF5E）和全角空格转为对应的半角字符
+fn to_half_width(c: char) -> char {
+    match c {
+        '\u{3000}' => ' ',
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// 数字后允许的单位最大字符数（如 “米”、“kg”、“万元”）
+const MAX_UNIT_CHARS: usize = 4;
+
+/// 解析中文表格中常见写法的数字
+///
+/// 全角数字和符号先转为半角，去除千分位逗号和数字间的空白，
+/// 并忽略不超过 [`MAX_UNIT_CHARS`] 个字符的尾部单位：
+/// `"１，２３４"` -> 1234，`"1,234.5 元"` -> 1234.5，`"12kg"` -> 12。
+/// 不是数字时返回 None。
+pub fn parse_localized_number(text: &str) -> Option<f64> {
+    let half: String = text.chars().map(to_half_width).collect();
+    let trimmed = half.trim();
+
+    // 去掉尾部单位：数字之后的非数字字符
+    let number_end = trimmed
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_ascii_digit() || *c == '.')
+        .map(|(i, c)| i + c.len_utf8())?;
+    let unit = trimmed[number_end..].trim();
+    if unit.chars().count() > MAX_UNIT_CHARS {
+        return None;
+    }
+
+    let number: String = trimmed[..number_end]
+        .chars()
+        .filter(|c| *c != ',' && !c.is_whitespace())
+        .collect();
+    number.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trim_and_collapse_whitespace() {
+        let normalization = TextNormalization::standard();
+
+        assert_eq!(normalization.apply("  柜号:  A01 \n"), "柜号: A01");
+        assert_eq!(normalization.apply("单台\t\t合计"), "单台 合计");
+        assert!(matches!(normalization.apply("小计"), Cow::Borrowed(_)));
+
+        // 只去除首尾空白时保留内部空白
+        let trim_only = TextNormalization {
+            trim: true,
+            ..Default::default()
+        };
+        assert_eq!(trim_only.apply(" a  b "), "a  b");
+    }
+
+    #[test]
+    fn test_full_width_to_half_width() {
+        let normalization = TextNormalization::standard().with_full_width_to_half(true);
+
+        assert_eq!(normalization.apply("柜号：Ａ０１"), "柜号:A01");
+        assert_eq!(normalization.apply("数量\u{3000}\u{3000}１０"), "数量 10");
+        // 中文字符不受影响
+        assert_eq!(normalization.apply("货物分析表"), "货物分析表");
+
+        // 未开启时保持原样
+        assert_eq!(TextNormalization::default().apply(" 柜号：Ａ "), " 柜号：Ａ ");
+    }
+
+    #[test]
+    fn test_parse_localized_number() {
+        // 千分位
+        assert_eq!(parse_localized_number("1,234"), Some(1234.0));
+        assert_eq!(parse_localized_number(" 1,234,567.89 "), Some(1234567.89));
+        // 全角数字和逗号
+        assert_eq!(parse_localized_number("１，２３４"), Some(1234.0));
+        assert_eq!(parse_localized_number("－３．５"), Some(-3.5));
+        // 尾部单位
+        assert_eq!(parse_localized_number("100米"), Some(100.0));
+        assert_eq!(parse_localized_number("12.5 kg"), Some(12.5));
+        assert_eq!(parse_localized_number("1,200万元"), Some(1200.0));
+        assert_eq!(parse_localized_number("1e3"), Some(1000.0));
+
+        // 非数字
+        assert_eq!(parse_localized_number(""), None);
+        assert_eq!(parse_localized_number("电缆"), None);
+        assert_eq!(parse_localized_number("NaN"), None);
+        assert_eq!(parse_localized_number("型号A1"), None);
+        assert_eq!(parse_localized_number("2024年第一季度货物清单"), None);
+    }
+}
@@ -48,6 +48,21 @@ pub enum AppError {
 
     #[error("Polars 错误: {0}")]
     PolarsError(String),
+
+    #[error("不支持的文件格式: {0}")]
+    UnsupportedFormat(PathBuf),
+
+    #[error("处理器 ID 重复: {0}")]
+    DuplicateProcessor(String),
+
+    #[error("处理超时: 超过 {} 秒", .0.as_secs())]
+    Timeout(std::time::Duration),
+
+    #[error("输入目录中没有 Excel 文件: {0}")]
+    NoExcelFiles(PathBuf),
+
+    #[error("输出文件校验失败: {0}: {1}")]
+    OutputVerification(PathBuf, String),
 }
 
 /// Result 类型别名
@@ -79,6 +94,11 @@ impl AppError {
         Self::PolarsError(msg.into())
     }
 
+    /// 创建不支持的文件格式错误
+    pub fn unsupported_format(path: impl Into<PathBuf>) -> Self {
+        Self::UnsupportedFormat(path.into())
+    }
+
     /// 检查是否为 I/O 错误
     pub fn is_io_error(&self) -> bool {
         matches!(self, Self::IoError(_))
@@ -94,6 +114,11 @@ impl AppError {
         matches!(self, Self::ProcessingError(_))
     }
 
+    /// 检查是否为不支持的文件格式错误
+    pub fn is_unsupported_format(&self) -> bool {
+        matches!(self, Self::UnsupportedFormat(_))
+    }
+
     /// 获取用户友好的错误消息
     pub fn user_message(&self) -> String {
         match self {
@@ -112,6 +137,21 @@ impl AppError {
             Self::PolarsError(msg) => format!("数据处理错误: {}", msg),
             Self::TomlDeserializeError(e) => format!("配置文件解析失败: {}", e),
             Self::TomlSerializeError(e) => format!("配置文件保存失败: {}", e),
+            Self::UnsupportedFormat(path) => format!(
+                "不支持的文件格式: {}（支持的格式: {}）",
+                path.display(),
+                crate::engine::data_engine::SUPPORTED_EXTENSIONS
+                    .iter()
+                    .map(|ext| format!(".{}", ext))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::DuplicateProcessor(id) => format!("处理器 ID 重复: {}（每个处理器的 ID 必须唯一）", id),
+            Self::Timeout(limit) => format!("处理超时: 超过 {} 秒仍未完成，已跳过该文件", limit.as_secs()),
+            Self::NoExcelFiles(path) => format!("输入目录中没有 Excel 文件: {}", path.display()),
+            Self::OutputVerification(path, reason) => {
+                format!("输出文件校验失败: {}（{}），文件可能已损坏", path.display(), reason)
+            }
         }
     }
 }
@@ -0,0 +1,185 @@
+// 旧版文本日志导入
+//
+// 早期版本只把运行日志写成 `[时间] [级别] 消息` 格式的纯文本。
+// 这里尽力从中还原每次批量处理的历史记录：以 “开始批量处理” 行开始，
+// 以 “批量处理完成” 行结束。格式不符的行会被跳过，不会中断解析。
+use crate::history::HistoryEntry;
+use crate::models::{ProcessingError, ProcessingResult};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use std::path::PathBuf;
+
+/// 旧版日志导入记录的处理器 ID
+pub const LEGACY_PROCESSOR_ID: &str = "legacy_log";
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const RUN_START_PREFIX: &str = "开始批量处理:";
+const RUN_END_PREFIX: &str = "批量处理完成:";
+
+/// 解析出的单行日志
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegacyLogLine {
+    pub timestamp: DateTime<Utc>,
+    pub level: String,
+    pub message: String,
+}
+
+/// 旧版日志的解析结果
+#[derive(Debug, Clone, Default)]
+pub struct LegacyLogImport {
+    /// 还原出的历史记录（只包含有完成行的运行）
+    pub entries: Vec<HistoryEntry>,
+    /// 成功解析的行数
+    pub parsed_lines: usize,
+    /// 格式不符而跳过的非空行数
+    pub skipped_lines: usize,
+}
+
+/// 解析 `[YYYY-MM-DD HH:MM:SS] [LEVEL] message` 格式的单行日志，时间按本地时区解释
+pub fn parse_line(line: &str) -> Option<LegacyLogLine> {
+    let rest = line.trim_end().strip_prefix('[')?;
+    let (timestamp, rest) = rest.split_once("] [")?;
+    let (level, message) = rest.split_once(']')?;
+
+    let naive = NaiveDateTime::parse_from_str(timestamp.trim(), TIMESTAMP_FORMAT).ok()?;
+    let timestamp = Local.from_local_datetime(&naive).earliest()?.with_timezone(&Utc);
+    let level = level.trim();
+    if level.is_empty() || level.contains(char::is_whitespace) {
+        return None;
+    }
+
+    Some(LegacyLogLine {
+        timestamp,
+        level: level.to_ascii_uppercase(),
+        message: message.trim().to_string(),
+    })
+}
+
+/// 正在还原的一次运行
+struct PendingRun {
+    started: DateTime<Utc>,
+    input_dir: PathBuf,
+    output_dir: PathBuf,
+    errors: Vec<String>,
+}
+
+/// 解析整个日志文本并还原历史记录
+pub fn parse_legacy_log(text: &str) -> LegacyLogImport {
+    let mut import = LegacyLogImport::default();
+    let mut pending: Option<PendingRun> = None;
+
+    for raw in text.lines() {
+        if raw.trim().is_empty() {
+            continue;
+        }
+        let Some(line) = parse_line(raw) else {
+            import.skipped_lines += 1;
+            continue;
+        };
+        import.parsed_lines += 1;
+
+        if let Some(args) = line.message.strip_prefix(RUN_START_PREFIX) {
+            // 上一次运行没有完成行时直接丢弃
+            pending = Some(PendingRun {
+                started: line.timestamp,
+                input_dir: PathBuf::from(field(args, "输入").unwrap_or_default()),
+                output_dir: PathBuf::from(field(args, "输出").unwrap_or_default()),
+                errors: Vec::new(),
+            });
+        } else if let Some(args) = line.message.strip_prefix(RUN_END_PREFIX) {
+            if let Some(run) = pending.take() {
+                import.entries.push(finish_run(run, args, line.timestamp));
+            }
+        } else if line.level == "ERROR" {
+            if let Some(run) = pending.as_mut() {
+                run.errors.push(line.message);
+            }
+        }
+    }
+
+    import
+}
+
+/// 从 `键=值, 键=值` 形式的参数中取值
+fn field<'a>(args: &'a str, key: &str) -> Option<&'a str> {
+    args.split(',').find_map(|part| {
+        let (k, v) = part.split_once('=')?;
+        (k.trim() == key).then(|| v.trim())
+    })
+}
+
+fn finish_run(run: PendingRun, args: &str, finished: DateTime<Utc>) -> HistoryEntry {
+    let count = |key| field(args, key).and_then(|v| v.parse::<usize>().ok()).unwrap_or(0);
+    let successful = count("成功");
+    let failed = count("失败");
+
+    let mut result = ProcessingResult::new(successful + failed);
+    result.successful = successful;
+    result.failed = failed;
+    result.errors = run
+        .errors
+        .into_iter()
+        .map(|message| ProcessingError::new(PathBuf::new(), message))
+        .collect();
+    result.duration = (finished - run.started).to_std().unwrap_or_default();
+
+    let mut entry = HistoryEntry::new(
+        LEGACY_PROCESSOR_ID.to_string(),
+        "旧版日志导入".to_string(),
+        run.input_dir,
+        run.output_dir,
+        result,
+    );
+    // 以开始时间作为 ID，重复导入同一份日志不会产生重复记录
+    entry.id = format!("legacy-{}", run.started.timestamp());
+    entry.timestamp = run.started;
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+[2024-03-01 09:00:00] [INFO] 应用启动
+[2024-03-01 09:00:05] [INFO] 开始批量处理: 输入=/data/in, 输出=/data/out
+[2024-03-01 09:00:06] [ERROR] 处理 b.xlsx 失败: 工作表为空
+this line is garbage
+[2024-03-01 09:00:09] [INFO] 批量处理完成: 成功=2, 失败=1, 耗时=4s
+[not a date] [INFO] 时间格式错误
+
+[2024-03-01 10:00:00] [INFO] 开始批量处理: 输入=/data/in2, 输出=/data/out2
+";
+
+    #[test]
+    fn test_parse_line() {
+        let line = parse_line("[2024-03-01 09:00:05] [warn] 注意").unwrap();
+        assert_eq!(line.level, "WARN");
+        assert_eq!(line.message, "注意");
+
+        assert!(parse_line("no brackets").is_none());
+        assert!(parse_line("[2024-03-01] [INFO] 缺少时间").is_none());
+        assert!(parse_line("[2024-03-01 09:00:05] INFO 缺少级别括号").is_none());
+    }
+
+    #[test]
+    fn test_parse_legacy_log_tolerates_malformed_lines() {
+        let import = parse_legacy_log(SAMPLE);
+
+        assert_eq!(import.parsed_lines, 5);
+        assert_eq!(import.skipped_lines, 2);
+        // 第二次运行没有完成行，不生成记录
+        assert_eq!(import.entries.len(), 1);
+
+        let entry = &import.entries[0];
+        assert_eq!(entry.processor_id, LEGACY_PROCESSOR_ID);
+        assert_eq!(entry.input_dir, PathBuf::from("/data/in"));
+        assert_eq!(entry.output_dir, PathBuf::from("/data/out"));
+        assert_eq!(entry.result.successful, 2);
+        assert_eq!(entry.result.failed, 1);
+        assert_eq!(entry.result.errors.len(), 1);
+        assert_eq!(entry.result.duration, std::time::Duration::from_secs(4));
+
+        // 重复解析得到相同的 ID
+        assert_eq!(parse_legacy_log(SAMPLE).entries[0].id, entry.id);
+    }
+}
@@ -2,8 +2,14 @@ use crate::error::{AppError, Result};
 use crate::models::ProcessingResult;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+
+/// 导入时每解析多少条记录报告一次进度（同时检查是否取消）
+const IMPORT_PROGRESS_INTERVAL: usize = 500;
 
 /// 历史记录条目
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +28,12 @@ pub struct HistoryEntry {
     pub output_dir: PathBuf,
     /// 处理结果
     pub result: ProcessingResult,
+    /// 多处理器对比运行时的分组 ID（同一次运行的记录相同）
+    #[serde(default)]
+    pub group_id: Option<String>,
+    /// 生成该记录的运行 ID（与运行期间日志中的 ID 相同）
+    #[serde(default)]
+    pub run_id: Option<String>,
 }
 
 impl HistoryEntry {
@@ -41,9 +53,23 @@ impl HistoryEntry {
             input_dir,
             output_dir,
             result,
+            group_id: None,
+            run_id: None,
         }
     }
 
+    /// 设置分组 ID
+    pub fn with_group(mut self, group_id: String) -> Self {
+        self.group_id = Some(group_id);
+        self
+    }
+
+    /// 设置运行 ID
+    pub fn with_run_id(mut self, run_id: String) -> Self {
+        self.run_id = Some(run_id);
+        self
+    }
+
     /// 检查是否成功
     pub fn is_successful(&self) -> bool {
         self.result.failed == 0
@@ -55,6 +81,71 @@ impl HistoryEntry {
     }
 }
 
+/// 匿名化路径：只保留文件名，主目录替换为 `~`，其余目录替换为 `…`
+///
+/// 例如 `/home/alice/data/a.xlsx` → `~/…/a.xlsx`，`D:\\work\\a.xlsx` → `…/a.xlsx`。
+pub fn sanitize_path(path: &Path) -> PathBuf {
+    sanitize_path_with_home(path, dirs::home_dir().as_deref())
+}
+
+fn sanitize_path_with_home(path: &Path, home: Option<&Path>) -> PathBuf {
+    let Some(file_name) = path.file_name() else {
+        return PathBuf::from("…");
+    };
+    if path.parent().map_or(true, |p| p.as_os_str().is_empty()) {
+        return PathBuf::from(file_name);
+    }
+
+    let under_home = home.is_some_and(|home| path.starts_with(home));
+    let prefix = if under_home { "~/…" } else { "…" };
+    PathBuf::from(prefix).join(file_name)
+}
+
+impl HistoryEntry {
+    /// 返回路径已匿名化的副本
+    pub fn anonymized(&self) -> Self {
+        let mut entry = self.clone();
+        entry.input_dir = sanitize_path(&entry.input_dir);
+        entry.output_dir = sanitize_path(&entry.output_dir);
+        for file in &mut entry.result.output_files {
+            *file = sanitize_path(file);
+        }
+        for error in &mut entry.result.errors {
+            error.file = sanitize_path(&error.file);
+        }
+        entry
+    }
+}
+
+/// 后台导入历史记录的事件
+#[derive(Debug)]
+pub enum ImportEvent {
+    /// 已解析的条目数 / 文件中的条目总数
+    Progress { parsed: usize, total: usize },
+    /// 解析完成，等待合并的条目
+    Finished(Result<Vec<HistoryEntry>>),
+    /// 导入已取消
+    Cancelled,
+}
+
+/// 正在后台进行的历史记录导入
+pub struct HistoryImportTask {
+    receiver: mpsc::Receiver<ImportEvent>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl HistoryImportTask {
+    /// 请求取消导入
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::SeqCst);
+    }
+
+    /// 取出下一条事件（不阻塞）
+    pub fn try_recv(&self) -> Option<ImportEvent> {
+        self.receiver.try_recv().ok()
+    }
+}
+
 /// 历史记录管理器
 pub struct HistoryManager {
     entries: Vec<HistoryEntry>,
@@ -208,7 +299,10 @@ impl HistoryManager {
         let json = serde_json::to_string_pretty(&self.entries)
             .map_err(|e| AppError::history_error(format!("序列化历史记录失败: {}", e)))?;
 
-        fs::write(&self.storage_path, json)?;
+        // 先写临时文件再重命名，避免写入中断时损坏已有的历史记录
+        let tmp_path = self.storage_path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)?;
+        fs::rename(&tmp_path, &self.storage_path)?;
 
         Ok(())
     }
@@ -252,9 +346,19 @@ impl HistoryManager {
 
     /// 导出历史记录到文件
     pub fn export_to_file(&self, path: &PathBuf) -> Result<()> {
+        self.export_entries(path, &self.entries)
+    }
+
+    /// 导出历史记录到文件，路径经过匿名化（见 [`sanitize_path`]）
+    pub fn export_anonymized(&self, path: &PathBuf) -> Result<()> {
+        let entries: Vec<HistoryEntry> = self.entries.iter().map(HistoryEntry::anonymized).collect();
+        self.export_entries(path, &entries)
+    }
+
+    fn export_entries(&self, path: &PathBuf, entries: &[HistoryEntry]) -> Result<()> {
         tracing::info!("导出历史记录到: {}", path.display());
 
-        let json = serde_json::to_string_pretty(&self.entries)
+        let json = serde_json::to_string_pretty(entries)
             .map_err(|e| AppError::history_error(format!("序列化历史记录失败: {}", e)))?;
 
         fs::write(path, json)?;
@@ -262,19 +366,94 @@ impl HistoryManager {
         Ok(())
     }
 
-    /// 从文件导入历史记录
-    pub fn import_from_file(&mut self, path: &PathBuf) -> Result<()> {
+    /// 从文件导入历史记录，返回新增的条目数
+    pub fn import_from_file(&mut self, path: &PathBuf) -> Result<usize> {
+        let entries = Self::read_import_file(path, &AtomicBool::new(false), |_, _| {})?
+            .ok_or_else(|| AppError::history_error("导入已取消"))?;
+        self.merge_imported(entries)
+    }
+
+    /// 从旧版纯文本运行日志导入历史记录（尽力解析，格式不符的行会被跳过）
+    pub fn import_legacy_log(&mut self, path: &Path) -> Result<usize> {
+        let text = fs::read_to_string(path)?;
+        let import = crate::history::legacy_log::parse_legacy_log(&text);
+        if import.skipped_lines > 0 {
+            tracing::warn!("旧版日志中有 {} 行格式不符，已跳过", import.skipped_lines);
+        }
+        self.merge_imported(import.entries)
+    }
+
+    /// 在后台线程中解析导入文件
+    ///
+    /// 解析期间通过 [`ImportEvent::Progress`] 报告进度，可随时调用
+    /// [`HistoryImportTask::cancel`] 取消。解析完成后需在调用方调用
+    /// [`HistoryManager::merge_imported`] 合并并保存。
+    pub fn start_import(path: PathBuf) -> HistoryImportTask {
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let cancel_flag = cancel.clone();
+
+        std::thread::spawn(move || {
+            let progress_sender = sender.clone();
+            let outcome = Self::read_import_file(&path, &cancel_flag, |parsed, total| {
+                let _ = progress_sender.send(ImportEvent::Progress { parsed, total });
+            });
+
+            let event = match outcome {
+                Ok(Some(entries)) => ImportEvent::Finished(Ok(entries)),
+                Ok(None) => ImportEvent::Cancelled,
+                Err(e) => ImportEvent::Finished(Err(e)),
+            };
+            let _ = sender.send(event);
+        });
+
+        HistoryImportTask { receiver, cancel }
+    }
+
+    /// 读取并逐条解析导入文件，取消时返回 `Ok(None)`
+    fn read_import_file<F>(
+        path: &PathBuf,
+        cancel: &AtomicBool,
+        progress: F,
+    ) -> Result<Option<Vec<HistoryEntry>>>
+    where
+        F: Fn(usize, usize),
+    {
         tracing::info!("从文件导入历史记录: {}", path.display());
 
         let content = fs::read_to_string(path)?;
-        let imported_entries: Vec<HistoryEntry> = serde_json::from_str(&content)
+        let raw_entries: Vec<serde_json::Value> = serde_json::from_str(&content)
             .map_err(|e| AppError::history_error(format!("反序列化历史记录失败: {}", e)))?;
 
-        // 合并导入的条目
+        let total = raw_entries.len();
+        let mut entries = Vec::with_capacity(total);
+        for (index, raw) in raw_entries.into_iter().enumerate() {
+            if index % IMPORT_PROGRESS_INTERVAL == 0 {
+                if cancel.load(Ordering::SeqCst) {
+                    tracing::info!("历史记录导入已取消（已解析 {}/{} 条）", index, total);
+                    return Ok(None);
+                }
+                progress(index, total);
+            }
+
+            let entry: HistoryEntry = serde_json::from_value(raw)
+                .map_err(|e| AppError::history_error(format!("第 {} 条历史记录格式错误: {}", index + 1, e)))?;
+            entries.push(entry);
+        }
+        progress(total, total);
+
+        Ok(Some(entries))
+    }
+
+    /// 合并导入的条目（跳过已存在的 ID），保存后返回新增的条目数
+    pub fn merge_imported(&mut self, imported_entries: Vec<HistoryEntry>) -> Result<usize> {
+        let mut known_ids: HashSet<String> = self.entries.iter().map(|e| e.id.clone()).collect();
+        let mut imported = 0;
+
         for entry in imported_entries {
-            // 检查是否已存在相同 ID 的条目
-            if !self.entries.iter().any(|e| e.id == entry.id) {
+            if known_ids.insert(entry.id.clone()) {
                 self.entries.push(entry);
+                imported += 1;
             }
         }
 
@@ -282,13 +461,16 @@ impl HistoryManager {
         self.entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
 
         // 限制条目数
-        self.trim_to(self.max_entries)?;
+        if self.entries.len() > self.max_entries {
+            let remove_count = self.entries.len() - self.max_entries;
+            self.entries.drain(0..remove_count);
+        }
 
         self.save()?;
 
-        tracing::info!("导入完成，当前共 {} 条历史记录", self.entries.len());
+        tracing::info!("导入完成，新增 {} 条，当前共 {} 条历史记录", imported, self.entries.len());
 
-        Ok(())
+        Ok(imported)
     }
 }
 
@@ -427,4 +609,78 @@ mod tests {
         let failed = manager.get_failed_entries();
         assert_eq!(failed.len(), 1);
     }
+
+    fn write_large_export(path: &PathBuf, count: usize) -> Vec<HistoryEntry> {
+        let entries: Vec<HistoryEntry> = (0..count).map(|_| create_test_entry()).collect();
+        fs::write(path, serde_json::to_string(&entries).unwrap()).unwrap();
+        entries
+    }
+
+    #[test]
+    fn test_import_large_file_in_background() {
+        let dir = tempdir().unwrap();
+        let export_path = dir.path().join("export.json");
+        write_large_export(&export_path, 5_000);
+
+        let mut manager = HistoryManager {
+            entries: Vec::new(),
+            storage_path: dir.path().join("history.json"),
+            max_entries: 10_000,
+        };
+        manager.add_entry(create_test_entry()).unwrap();
+
+        let task = HistoryManager::start_import(export_path.clone());
+        let mut last_progress = 0;
+        let entries = loop {
+            match task.receiver.recv().unwrap() {
+                ImportEvent::Progress { parsed, total } => {
+                    assert_eq!(total, 5_000);
+                    last_progress = parsed;
+                }
+                ImportEvent::Finished(result) => break result.unwrap(),
+                ImportEvent::Cancelled => panic!("导入不应被取消"),
+            }
+        };
+        assert_eq!(last_progress, 5_000);
+
+        assert_eq!(manager.merge_imported(entries).unwrap(), 5_000);
+        assert_eq!(manager.entry_count(), 5_001);
+
+        // 再次导入同一文件不会产生重复条目
+        assert_eq!(manager.import_from_file(&export_path).unwrap(), 0);
+        assert_eq!(manager.entry_count(), 5_001);
+        assert_eq!(HistoryManager::load_from_file(&manager.storage_path).unwrap().len(), 5_001);
+    }
+
+    #[test]
+    fn test_sanitize_path_under_home() {
+        let home = PathBuf::from("/home/alice");
+        let path = home.join("项目").join("货物分析表.xlsx");
+
+        let sanitized = sanitize_path_with_home(&path, Some(&home));
+        assert_eq!(sanitized, PathBuf::from("~/…").join("货物分析表.xlsx"));
+        assert!(!sanitized.to_string_lossy().contains("alice"));
+
+        assert_eq!(
+            sanitize_path_with_home(Path::new("/srv/internal/out"), Some(&home)),
+            PathBuf::from("…").join("out")
+        );
+        assert_eq!(sanitize_path_with_home(Path::new("a.xlsx"), Some(&home)), PathBuf::from("a.xlsx"));
+
+        if let Some(real_home) = dirs::home_dir() {
+            let sanitized = sanitize_path(&real_home.join("data").join("a.xlsx"));
+            assert_eq!(sanitized, PathBuf::from("~/…").join("a.xlsx"));
+        }
+    }
+
+    #[test]
+    fn test_import_cancelled_before_parsing() {
+        let dir = tempdir().unwrap();
+        let export_path = dir.path().join("export.json");
+        write_large_export(&export_path, 10);
+
+        let cancel = AtomicBool::new(true);
+        let outcome = HistoryManager::read_import_file(&export_path, &cancel, |_, _| {}).unwrap();
+        assert!(outcome.is_none());
+    }
 }
@@ -1,5 +1,6 @@
 // History 模块
 pub mod manager;
+pub mod legacy_log;
 
 #[allow(unused_imports)]
-pub use manager::{HistoryEntry, HistoryManager};
+pub use manager::{HistoryEntry, HistoryImportTask, HistoryManager, ImportEvent};
@@ -1,9 +1,14 @@
 // 日志系统
 use chrono::Local;
-use std::fs::OpenOptions;
-use std::io::Write;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 日志文件缓冲的最长刷新间隔
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
 
 /// 日志级别
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,23 +61,129 @@ impl LogEntry {
     }
 }
 
+/// 日志文件的缓冲写入器
+///
+/// 文件在第一次写入时打开并保持打开，写入先进入缓冲区，
+/// 遇到警告/错误、距上次刷新超过 [`LOG_FLUSH_INTERVAL`] 时写入磁盘；
+/// 之后没有新日志时由后台计时器（见 [`Logger::start_flush_timer`]）或释放时写入。
+#[derive(Default)]
+struct LogFileWriter {
+    writer: Option<BufWriter<File>>,
+    last_flush: Option<Instant>,
+    /// 缓冲区中是否有尚未写入磁盘的内容
+    pending: bool,
+}
+
+impl LogFileWriter {
+    fn write(&mut self, path: &PathBuf, entry: &LogEntry) {
+        if self.writer.is_none() {
+            match OpenOptions::new().create(true).append(true).open(path) {
+                Ok(file) => self.writer = Some(BufWriter::new(file)),
+                Err(_) => return,
+            }
+        }
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+        if writeln!(writer, "{}", entry.format()).is_err() {
+            // 写入失败时丢弃写入器，下次重新打开
+            self.writer = None;
+            return;
+        }
+        self.pending = true;
+
+        if matches!(entry.level, LogLevel::Warning | LogLevel::Error) {
+            self.flush();
+        } else {
+            self.flush_if_due(Instant::now());
+        }
+    }
+
+    /// 有未写入的内容且距上次刷新超过间隔时写入磁盘
+    fn flush_if_due(&mut self, now: Instant) {
+        let due = self
+            .last_flush
+            .map_or(true, |t| now.saturating_duration_since(t) >= LOG_FLUSH_INTERVAL);
+        if self.pending && due {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(writer) = self.writer.as_mut() {
+            let _ = writer.flush();
+        }
+        self.pending = false;
+        self.last_flush = Some(Instant::now());
+    }
+}
+
+impl Drop for LogFileWriter {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
 /// 日志管理器
 pub struct Logger {
     entries: Arc<Mutex<Vec<LogEntry>>>,
     max_entries: usize,
     log_file: Option<PathBuf>,
+    /// 日志文件的缓冲写入器（各克隆共享）
+    file_writer: Arc<Mutex<LogFileWriter>>,
+    /// 是否同时输出到控制台（默认仅调试构建开启）
+    console_output: Arc<AtomicBool>,
+    /// 控制台输出的替代目标（测试时捕获输出用）
+    console_sink: Option<Arc<Mutex<Vec<String>>>>,
 }
 
 impl Logger {
     pub fn new(max_entries: usize) -> Self {
-        Self {
+        let logger = Self {
             entries: Arc::new(Mutex::new(Vec::new())),
             max_entries,
             log_file: Self::get_log_file_path().ok(),
+            file_writer: Arc::new(Mutex::new(LogFileWriter::default())),
+            console_output: Arc::new(AtomicBool::new(cfg!(debug_assertions))),
+            console_sink: None,
+        };
+        logger.start_flush_timer();
+        logger
+    }
+
+    /// 启动后台计时器，定期把缓冲中的日志写入文件
+    ///
+    /// 这样最后几条普通日志不必等下一条日志或退出才落盘；
+    /// 日志管理器（及其所有克隆）释放后计时器自动结束。
+    fn start_flush_timer(&self) {
+        let writer = Arc::downgrade(&self.file_writer);
+        let spawned = std::thread::Builder::new()
+            .name("log-flush".to_string())
+            .spawn(move || loop {
+                std::thread::sleep(LOG_FLUSH_INTERVAL);
+                let Some(writer) = writer.upgrade() else {
+                    break;
+                };
+                if let Ok(mut writer) = writer.lock() {
+                    writer.flush_if_due(Instant::now());
+                }
+            });
+        if spawned.is_err() {
+            // 无法启动计时器时仍会在下一条日志、警告和退出时刷新
+            eprintln!("无法启动日志刷新计时器");
         }
     }
 
-    fn get_log_file_path() -> Result<PathBuf, std::io::Error> {
+    /// 设置是否把日志同时输出到控制台（不影响文件和内存日志）
+    pub fn set_console_output(&self, enabled: bool) {
+        self.console_output.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn console_output(&self) -> bool {
+        self.console_output.load(Ordering::Relaxed)
+    }
+
+    fn log_dir() -> Result<PathBuf, std::io::Error> {
         let log_dir = dirs::data_dir()
             .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "无法获取数据目录"))?
             .join("IntegratedPower")
@@ -80,6 +191,12 @@ impl Logger {
 
         std::fs::create_dir_all(&log_dir)?;
 
+        Ok(log_dir)
+    }
+
+    fn get_log_file_path() -> Result<PathBuf, std::io::Error> {
+        let log_dir = Self::log_dir()?;
+
         let log_file = log_dir.join(format!(
             "app_{}.log",
             Local::now().format("%Y%m%d")
@@ -101,19 +218,24 @@ impl Logger {
             }
         }
 
-        // 写入文件
+        // 写入文件（缓冲，警告和错误立即刷新）
         if let Some(log_file) = &self.log_file {
-            if let Ok(mut file) = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(log_file)
-            {
-                let _ = writeln!(file, "{}", entry.format());
+            if let Ok(mut writer) = self.file_writer.lock() {
+                writer.write(log_file, &entry);
             }
         }
 
         // 同时输出到控制台
-        println!("{}", entry.format());
+        if self.console_output() {
+            match &self.console_sink {
+                Some(sink) => {
+                    if let Ok(mut lines) = sink.lock() {
+                        lines.push(entry.format());
+                    }
+                }
+                None => println!("{}", entry.format()),
+            }
+        }
     }
 
     pub fn debug(&self, message: impl Into<String>) {
@@ -132,6 +254,13 @@ impl Logger {
         self.log(LogLevel::Error, message);
     }
 
+    /// 把缓冲中的日志写入文件，应用退出前调用
+    pub fn flush(&self) {
+        if let Ok(mut writer) = self.file_writer.lock() {
+            writer.flush();
+        }
+    }
+
     pub fn get_entries(&self) -> Vec<LogEntry> {
         self.entries.lock().unwrap().clone()
     }
@@ -142,6 +271,21 @@ impl Logger {
         }
     }
 
+    /// 只保留满足条件的内存日志（不影响日志文件）
+    pub fn retain<F>(&self, keep: F)
+    where
+        F: FnMut(&LogEntry) -> bool,
+    {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(keep);
+        }
+    }
+
+    /// 清除指定级别的内存日志
+    pub fn clear_level(&self, level: LogLevel) {
+        self.retain(|e| e.level != level);
+    }
+
     pub fn get_log_file_path_str(&self) -> Option<String> {
         self.log_file.as_ref().map(|p| p.display().to_string())
     }
@@ -153,10 +297,149 @@ impl Clone for Logger {
             entries: Arc::clone(&self.entries),
             max_entries: self.max_entries,
             log_file: self.log_file.clone(),
+            file_writer: Arc::clone(&self.file_writer),
+            console_output: Arc::clone(&self.console_output),
+            console_sink: self.console_sink.clone(),
         }
     }
 }
 
+/// 错误报告记录器
+///
+/// 通过全局 `LOGGER` 记录（日志面板、应用日志文件、控制台各一份，不重复输出），
+/// 另外把警告和错误追加到单独的报告文件 `errors.log`，便于反馈问题时附带。
+#[derive(Debug, Clone)]
+pub struct ErrorLogger {
+    report_file: Option<PathBuf>,
+}
+
+impl ErrorLogger {
+    pub fn new() -> Self {
+        Self {
+            report_file: Logger::log_dir().ok().map(|dir| dir.join("errors.log")),
+        }
+    }
+
+    /// 使用指定的报告文件（None 表示不写报告文件）
+    pub fn with_report_file(report_file: Option<PathBuf>) -> Self {
+        Self { report_file }
+    }
+
+    /// 记录错误，`context` 描述出错时正在执行的操作
+    pub fn log_error(&self, context: &str, error: impl std::fmt::Display) {
+        let message = format!("{}: {}", context, error);
+        LOGGER.error(message.clone());
+        self.append_report(LogLevel::Error, &message);
+    }
+
+    pub fn log_warning(&self, message: impl Into<String>) {
+        let message = message.into();
+        LOGGER.warning(message.clone());
+        self.append_report(LogLevel::Warning, &message);
+    }
+
+    /// 记录普通信息（只进入全局日志，不写报告文件）
+    pub fn log_info(&self, message: impl Into<String>) {
+        LOGGER.info(message);
+    }
+
+    pub fn report_file(&self) -> Option<&PathBuf> {
+        self.report_file.as_ref()
+    }
+
+    fn append_report(&self, level: LogLevel, message: &str) {
+        if let Some(report_file) = &self.report_file {
+            if let Ok(mut file) = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(report_file)
+            {
+                let _ = writeln!(file, "{}", LogEntry::new(level, message.to_string()).format());
+            }
+        }
+    }
+}
+
+impl Default for ErrorLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 把运行中的 tracing 日志转发到全局 `LOGGER`
+///
+/// 队列任务在 `info_span!("run", run_id = ..)` 中执行，引擎的逐文件日志通过 tracing 输出；
+/// 事件所在的 span 带有 `run_id` 字段时，转发到日志面板并在消息前加上 `[run_id]`。
+/// 不在运行中的 tracing 日志只输出到控制台，避免与直接写入 `LOGGER` 的日志重复。
+pub struct RunLogLayer;
+
+/// 记录在 span 扩展中的运行 ID
+struct RunId(String);
+
+/// 提取 span/事件中的 `run_id` 和 `message` 字段
+#[derive(Default)]
+struct RunFieldVisitor {
+    run_id: Option<String>,
+    message: Option<String>,
+}
+
+impl tracing::field::Visit for RunFieldVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.record(field, format!("{:?}", value));
+    }
+}
+
+impl RunFieldVisitor {
+    fn record(&mut self, field: &tracing::field::Field, value: String) {
+        match field.name() {
+            "run_id" => self.run_id = Some(value),
+            "message" => self.message = Some(value),
+            _ => {}
+        }
+    }
+}
+
+impl<S> tracing_subscriber::Layer<S> for RunLogLayer
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        id: &tracing::span::Id,
+        ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut visitor = RunFieldVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(run_id), Some(span)) = (visitor.run_id, ctx.span(id)) {
+            span.extensions_mut().insert(RunId(run_id));
+        }
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        let Some(mut scope) = ctx.event_scope(event) else {
+            return;
+        };
+        let Some(run_id) = scope.find_map(|span| span.extensions().get::<RunId>().map(|r| r.0.clone())) else {
+            return;
+        };
+
+        let mut visitor = RunFieldVisitor::default();
+        event.record(&mut visitor);
+        let level = match *event.metadata().level() {
+            tracing::Level::ERROR => LogLevel::Error,
+            tracing::Level::WARN => LogLevel::Warning,
+            tracing::Level::INFO => LogLevel::Info,
+            _ => LogLevel::Debug,
+        };
+        LOGGER.log(level, format!("[{}] {}", run_id, visitor.message.unwrap_or_default()));
+    }
+}
+
 // 全局日志实例
 lazy_static::lazy_static! {
     pub static ref LOGGER: Logger = Logger::new(1000);
@@ -190,3 +473,158 @@ macro_rules! log_error {
         $crate::logger::LOGGER.error(format!($($arg)*))
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_console_output_toggle() {
+        let sink = Arc::new(Mutex::new(Vec::new()));
+        let logger = Logger {
+            entries: Arc::new(Mutex::new(Vec::new())),
+            max_entries: 10,
+            log_file: None,
+            file_writer: Default::default(),
+            console_output: Arc::new(AtomicBool::new(false)),
+            console_sink: Some(sink.clone()),
+        };
+
+        logger.info("关闭时");
+        assert!(sink.lock().unwrap().is_empty());
+
+        logger.set_console_output(true);
+        logger.info("开启时");
+
+        let lines = sink.lock().unwrap();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("开启时"));
+        // 内存日志不受开关影响
+        assert_eq!(logger.get_entries().len(), 2);
+    }
+
+    #[test]
+    fn test_clear_level_removes_only_debug() {
+        let logger = Logger {
+            entries: Arc::new(Mutex::new(Vec::new())),
+            max_entries: 10,
+            log_file: None,
+            file_writer: Default::default(),
+            console_output: Arc::new(AtomicBool::new(false)),
+            console_sink: None,
+        };
+        logger.debug("调试1");
+        logger.info("信息");
+        logger.debug("调试2");
+        logger.error("错误");
+
+        logger.clear_level(LogLevel::Debug);
+
+        let levels: Vec<LogLevel> = logger.get_entries().iter().map(|e| e.level).collect();
+        assert_eq!(levels, vec![LogLevel::Info, LogLevel::Error]);
+    }
+
+    #[test]
+    fn test_log_file_is_buffered_and_flushed() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let logger = Logger {
+            entries: Arc::new(Mutex::new(Vec::new())),
+            max_entries: 10,
+            log_file: Some(path.clone()),
+            file_writer: Default::default(),
+            console_output: Arc::new(AtomicBool::new(false)),
+            console_sink: None,
+        };
+        let read = || std::fs::read_to_string(&path).unwrap_or_default();
+
+        // 第一条立即刷新，随后的普通日志先留在缓冲区
+        logger.info("第一条");
+        assert!(read().contains("第一条"));
+        logger.info("缓冲中");
+
+        // 警告会连同之前缓冲的日志一起写入
+        logger.warning("警告");
+        let content = read();
+        assert!(content.contains("缓冲中"));
+        assert!(content.contains("[WARN] 警告"));
+
+        logger.debug("退出前");
+        logger.flush();
+        assert!(read().contains("退出前"));
+        // 内存日志不受影响
+        assert_eq!(logger.get_entries().len(), 4);
+    }
+
+    #[test]
+    fn test_buffered_log_flushed_by_timer_without_new_entry() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let entry = |message: &str| LogEntry::new(LogLevel::Info, message.to_string());
+        let read = || std::fs::read_to_string(&path).unwrap_or_default();
+
+        let mut writer = LogFileWriter::default();
+        writer.write(&path, &entry("第一条"));
+        writer.write(&path, &entry("最后一条"));
+        assert!(!read().contains("最后一条"));
+
+        // 之后没有新日志，计时器到期时也会写入
+        writer.flush_if_due(Instant::now());
+        assert!(!read().contains("最后一条"));
+        writer.flush_if_due(Instant::now() + LOG_FLUSH_INTERVAL);
+        assert!(read().contains("最后一条"));
+
+        // 释放写入器时写入剩余内容
+        writer.write(&path, &entry("释放前"));
+        assert!(!read().contains("释放前"));
+        drop(writer);
+        assert!(read().contains("释放前"));
+    }
+
+    #[test]
+    fn test_run_log_layer_forwards_events_in_run_span() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let subscriber = tracing_subscriber::registry().with(RunLogLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("run", run_id = %"ab12cd34");
+            span.in_scope(|| {
+                tracing::info!("run-layer-marker 成功处理");
+                tracing::warn!("run-layer-marker 跳过");
+            });
+            tracing::info!("run-layer-marker 运行之外");
+        });
+
+        let forwarded: Vec<LogEntry> = LOGGER
+            .get_entries()
+            .into_iter()
+            .filter(|e| e.message.contains("run-layer-marker"))
+            .collect();
+        assert_eq!(forwarded.len(), 2);
+        assert_eq!(forwarded[0].message, "[ab12cd34] run-layer-marker 成功处理");
+        assert_eq!(forwarded[0].level, LogLevel::Info);
+        assert_eq!(forwarded[1].level, LogLevel::Warning);
+    }
+
+    #[test]
+    fn test_error_logger_pushes_into_global_logger() {
+        let dir = tempdir().unwrap();
+        let report = dir.path().join("errors.log");
+        let logger = ErrorLogger::with_report_file(Some(report.clone()));
+
+        logger.log_error("测试操作", "error-logger-marker");
+
+        let entries = LOGGER.get_entries();
+        let matching: Vec<_> = entries
+            .iter()
+            .filter(|e| e.message.contains("error-logger-marker"))
+            .collect();
+        assert_eq!(matching.len(), 1);
+        assert_eq!(matching[0].level, LogLevel::Error);
+        assert_eq!(matching[0].message, "测试操作: error-logger-marker");
+
+        let content = std::fs::read_to_string(&report).unwrap();
+        assert!(content.contains("error-logger-marker"));
+    }
+}
@@ -6,27 +6,42 @@ mod history;
 mod logger;
 mod models;
 mod processor;
+mod resources;
+mod tasks;
 mod ui;
 
 use app::IntegratedPowerApp;
 
 fn main() -> Result<(), eframe::Error> {
-    // 初始化 tracing 日志输出（控制台），确保现有 tracing 宏生效
-    let _ = tracing_subscriber::fmt::try_init();
+    // 初始化 tracing 日志输出（控制台），队列任务运行中的日志同时转发到日志面板
+    {
+        use tracing_subscriber::layer::SubscriberExt;
+        use tracing_subscriber::util::SubscriberInitExt;
+        use tracing_subscriber::Layer;
 
-    // 配置窗口选项
+        let _ = tracing_subscriber::registry()
+            .with(tracing_subscriber::fmt::layer().with_filter(tracing_subscriber::filter::LevelFilter::INFO))
+            .with(logger::RunLogLayer)
+            .try_init();
+    }
+
+    // 配置窗口选项（恢复上次关闭时的尺寸和位置）
+    let geometry = config::ConfigManager::load()
+        .map(|manager| manager.get_config().startup_window_geometry())
+        .unwrap_or_default();
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1200.0, 800.0])
-            .with_min_inner_size([800.0, 600.0])
-            .with_title("IntegratedPower"),
+        viewport: geometry.apply(egui::ViewportBuilder::default().with_title(models::APP_TITLE)),
         ..Default::default()
     };
 
     // 启动应用
-    eframe::run_native(
-        "IntegratedPower",
+    let result = eframe::run_native(
+        models::APP_TITLE,
         options,
         Box::new(|cc| Ok(Box::new(IntegratedPowerApp::new(cc)))),
-    )
+    );
+
+    // 日志文件带缓冲，退出前写入剩余内容
+    logger::LOGGER.flush();
+    result
 }
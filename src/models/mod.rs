@@ -4,12 +4,18 @@ pub mod result;
 pub mod state;
 pub mod processor_config;
 pub mod row_type;
+pub mod options_schema;
+pub mod worksheet_diff;
+pub mod percent;
 
 // 重新导出常用类型
-pub use progress::ProcessingProgress;
+pub use progress::{FileOutcome, ProcessingProgress, RecentOutcomes, APP_TITLE};
 pub use result::{ProcessingError, ProcessingResult, ProcessingStats};
-pub use state::{AppState, AppView, ProcessingState};
-pub use processor_config::{ProcessorConfig, ProcessorConfigs, InputType, ConfigValue};
+pub use state::{AppState, AppView, CancelToken, ProcessingState};
+pub use processor_config::{ProcessorConfig, ProcessorConfigs, InputType, ConfigValue, OutputFormat};
+pub use options_schema::{OptionKind, OptionSpec, OptionsSchema};
+pub use worksheet_diff::WorksheetDiff;
+pub use percent::{format_percentage, format_ratio};
 pub use row_type::{
     RowType, RgbColor, CellStyle, MergeInfo, CellData, RowData, 
     WorksheetData, RowIdentificationResult, IdentificationStatistics,
@@ -0,0 +1,192 @@
+// 处理器选项描述
+//
+// 每个处理器的功能选项（键、类型、标签、默认值）在此集中声明，
+// 配置界面据此统一渲染，处理器据此读取带类型的值。
+use crate::models::{ConfigValue, ProcessorConfig};
+
+/// 选项值的类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionKind {
+    Bool,
+    /// 整数，取值范围为闭区间
+    Int { min: i64, max: i64 },
+    String,
+}
+
+/// 单个选项的描述
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionSpec {
+    /// 在 `ProcessorConfig::options` 中的键
+    pub key: &'static str,
+    /// 界面显示的标签
+    pub label: &'static str,
+    pub kind: OptionKind,
+    pub default: ConfigValue,
+}
+
+impl OptionSpec {
+    pub fn bool(key: &'static str, label: &'static str, default: bool) -> Self {
+        Self {
+            key,
+            label,
+            kind: OptionKind::Bool,
+            default: ConfigValue::Bool(default),
+        }
+    }
+
+    pub fn int(key: &'static str, label: &'static str, min: i64, max: i64, default: i64) -> Self {
+        Self {
+            key,
+            label,
+            kind: OptionKind::Int { min, max },
+            default: ConfigValue::Int(default),
+        }
+    }
+
+    /// 检查值的类型与取值范围
+    pub fn validate(&self, value: &ConfigValue) -> Result<(), String> {
+        match (self.kind, value) {
+            (OptionKind::Bool, ConfigValue::Bool(_)) => Ok(()),
+            (OptionKind::String, ConfigValue::String(_)) => Ok(()),
+            (OptionKind::Int { min, max }, ConfigValue::Int(v)) => {
+                if (min..=max).contains(v) {
+                    Ok(())
+                } else {
+                    Err(format!("{} 超出范围 {}..={}: {}", self.label, min, max, v))
+                }
+            }
+            _ => Err(format!("{} 的值类型不正确: {:?}", self.label, value)),
+        }
+    }
+
+    /// 读取配置中的值；未设置或不合法时返回默认值
+    pub fn read(&self, config: &ProcessorConfig) -> ConfigValue {
+        match config.options.get(self.key) {
+            Some(value) if self.validate(value).is_ok() => value.clone(),
+            _ => self.default.clone(),
+        }
+    }
+}
+
+/// 一个处理器的全部功能选项
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OptionsSchema {
+    /// 选项分组标题
+    pub title: &'static str,
+    pub options: Vec<OptionSpec>,
+    /// 是否显示批量处理选项
+    pub batch_options: bool,
+    /// 是否显示行类型识别优先级编辑器
+    pub row_type_priority: bool,
+    /// 是否显示输出行类型筛选（多选）
+    pub row_type_filter: bool,
+    /// 选项下方的提示文字
+    pub note: Option<&'static str>,
+}
+
+impl OptionsSchema {
+    /// 获取处理器的选项描述，未知处理器返回空描述
+    pub fn for_processor(processor_id: &str) -> Self {
+        match processor_id {
+            "cargo_analysis" => Self {
+                title: "📊 分析选项",
+                options: vec![
+                    OptionSpec::bool("include_summary", "包含统计汇总", true),
+                    OptionSpec::bool("generate_charts", "生成趋势图表", true),
+                    OptionSpec::bool("export_logs", "导出详细日志", false),
+                    OptionSpec::bool("normalize_text", "读取时规范化文本（去除多余空白）", false),
+                    OptionSpec::bool("full_width_to_half", "规范化时全角字符转半角", false),
+                ],
+                batch_options: true,
+                row_type_priority: true,
+                row_type_filter: true,
+                note: None,
+            },
+            "auxiliary_material" => Self {
+                title: "🔧 处理选项",
+                options: vec![
+                    OptionSpec::bool("auto_classify", "自动分类", true),
+                    OptionSpec::bool("remove_duplicates", "去除重复项", true),
+                    OptionSpec::bool("generate_summary", "生成汇总表", false),
+                ],
+                batch_options: true,
+                row_type_priority: false,
+                row_type_filter: false,
+                note: None,
+            },
+            "excel_structure_analyzer" => Self {
+                title: "🔍 分析选项",
+                options: vec![
+                    OptionSpec::bool("analyze_colors", "分析颜色信息", false),
+                    OptionSpec::bool("colors_only", "仅分析颜色（跳过表格内容输出）", false),
+                    OptionSpec::bool("analyze_structure", "分析表格结构", true),
+                    OptionSpec::bool("detailed_output", "详细输出模式", true),
+                    OptionSpec::int(
+                        "dump_max_rows",
+                        "最多输出行数",
+                        1,
+                        100_000,
+                        crate::processor::examples::excel_structure_analyzer::DEFAULT_DUMP_MAX_ROWS as i64,
+                    ),
+                    OptionSpec::int("preview_rows", "快速预览行数（0 表示完整分析）", 0, 10_000, 0),
+                ],
+                batch_options: false,
+                row_type_priority: false,
+                row_type_filter: false,
+                note: Some("💡 提示: 分析结果将输出到日志面板"),
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// 把所有选项的默认值写入配置
+    pub fn apply_defaults(&self, config: &mut ProcessorConfig) {
+        for spec in &self.options {
+            config.options.insert(spec.key.to_string(), spec.default.clone());
+        }
+    }
+
+    /// 检查配置中的选项，返回所有不合法项的说明
+    pub fn validate(&self, config: &ProcessorConfig) -> Vec<String> {
+        self.options
+            .iter()
+            .filter_map(|spec| config.options.get(spec.key).and_then(|v| spec.validate(v).err()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_falls_back_to_default_for_invalid_values() {
+        let schema = OptionsSchema::for_processor("excel_structure_analyzer");
+        let max_rows = schema.options.iter().find(|s| s.key == "dump_max_rows").unwrap();
+
+        let mut config = ProcessorConfig::default();
+        assert_eq!(max_rows.read(&config), max_rows.default);
+
+        config.set_int("dump_max_rows".to_string(), 42);
+        assert_eq!(max_rows.read(&config), ConfigValue::Int(42));
+
+        // 超出范围和类型错误都回退到默认值
+        config.set_int("dump_max_rows".to_string(), 0);
+        assert_eq!(max_rows.read(&config), max_rows.default);
+        config.set_bool("dump_max_rows".to_string(), true);
+        assert_eq!(max_rows.read(&config), max_rows.default);
+        assert_eq!(schema.validate(&config).len(), 1);
+    }
+
+    #[test]
+    fn test_new_config_uses_schema_defaults() {
+        for id in ["cargo_analysis", "auxiliary_material", "excel_structure_analyzer"] {
+            let schema = OptionsSchema::for_processor(id);
+            let config = ProcessorConfig::new(id);
+            for spec in &schema.options {
+                assert_eq!(config.options.get(spec.key), Some(&spec.default), "{}", spec.key);
+            }
+            assert!(schema.validate(&config).is_empty());
+        }
+    }
+}
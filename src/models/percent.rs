@@ -0,0 +1,53 @@
+// 百分比格式化
+//
+// 进度条、状态栏、窗口标题和识别统计日志统一使用这里的格式：
+// 保留一位小数，小数为 0 时省略（“42%”、“42.5%”），分母为 0 或数值无效时为 “0%”。
+
+/// 格式化百分比数值（0.0 - 100.0），如 `42.5` -> `"42.5%"`
+pub fn format_percentage(value: f32) -> String {
+    if !value.is_finite() {
+        return "0%".to_string();
+    }
+    let text = format!("{:.1}", value);
+    let text = text.strip_suffix(".0").unwrap_or(&text);
+    // -0.04 之类的值四舍五入后为 "-0"
+    if text == "-0" {
+        return "0%".to_string();
+    }
+    format!("{}%", text)
+}
+
+/// 格式化比例 `numerator / denominator`，如 `(1, 3)` -> `"33.3%"`，分母为 0 时为 `"0%"`
+pub fn format_ratio(numerator: usize, denominator: usize) -> String {
+    if denominator == 0 {
+        return format_percentage(0.0);
+    }
+    format_percentage(numerator as f32 * 100.0 / denominator as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_percentage() {
+        assert_eq!(format_percentage(0.0), "0%");
+        assert_eq!(format_percentage(100.0), "100%");
+        assert_eq!(format_percentage(42.0), "42%");
+        assert_eq!(format_percentage(42.5), "42.5%");
+        assert_eq!(format_percentage(33.333), "33.3%");
+        assert_eq!(format_percentage(99.96), "100%");
+        assert_eq!(format_percentage(-0.01), "0%");
+        assert_eq!(format_percentage(f32::NAN), "0%");
+    }
+
+    #[test]
+    fn test_format_ratio() {
+        assert_eq!(format_ratio(0, 0), "0%");
+        assert_eq!(format_ratio(5, 0), "0%");
+        assert_eq!(format_ratio(3, 3), "100%");
+        assert_eq!(format_ratio(1, 3), "33.3%");
+        assert_eq!(format_ratio(1, 8), "12.5%");
+        assert_eq!(format_ratio(0, 7), "0%");
+    }
+}
@@ -1,11 +1,12 @@
 // 处理器配置模型
 use serde::{Deserialize, Serialize};
+use crate::error::AppError;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 // 使用 umya-spreadsheet 读取 sheet 列表
 
 /// 处理器配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProcessorConfig {
     /// 输入路径（文件或文件夹）
     pub input_path: Option<PathBuf>,
@@ -19,10 +20,24 @@ pub struct ProcessorConfig {
     pub selected_sheet: Option<String>,
     /// 可用的 sheet 列表（从文件中读取）
     pub available_sheets: Vec<String>,
+    /// 隐藏（hidden / veryHidden）的 sheet 名称
+    #[serde(default)]
+    pub hidden_sheets: Vec<String>,
+    /// 输出文件格式
+    #[serde(default)]
+    pub output_format: OutputFormat,
     /// 功能特定选项
     pub options: HashMap<String, ConfigValue>,
 }
 
+/// 导出文件中的处理器配置，记录所属处理器，避免导入到其他处理器
+#[derive(Serialize, Deserialize)]
+struct ExportedConfig<C> {
+    processor_id: String,
+    #[serde(flatten)]
+    config: C,
+}
+
 /// 输入类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InputType {
@@ -30,8 +45,38 @@ pub enum InputType {
     Folder,
 }
 
+/// 输出文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OutputFormat {
+    #[default]
+    Xlsx,
+    Csv,
+    Parquet,
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 3] = [OutputFormat::Xlsx, OutputFormat::Csv, OutputFormat::Parquet];
+
+    /// 文件扩展名（不含点）
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Xlsx => "xlsx",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Parquet => "parquet",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutputFormat::Xlsx => "Excel (.xlsx)",
+            OutputFormat::Csv => "CSV (.csv)",
+            OutputFormat::Parquet => "Parquet (.parquet)",
+        }
+    }
+}
+
 /// 配置值
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ConfigValue {
     Bool(bool),
     String(String),
@@ -48,6 +93,8 @@ impl Default for ProcessorConfig {
             output_filename: "output.xlsx".to_string(),
             selected_sheet: None,
             available_sheets: Vec::new(),
+            hidden_sheets: Vec::new(),
+            output_format: OutputFormat::default(),
             options: HashMap::new(),
         }
     }
@@ -64,57 +111,67 @@ impl ProcessorConfig {
                 config.input_type = InputType::File;
                 // 设置默认 sheet 名称
                 config.selected_sheet = Some("货物数据".to_string());
-                // 设置默认选项
-                config.set_bool("include_statistics".to_string(), true);
-                config.set_bool("generate_charts".to_string(), true);
-                config.set_bool("export_logs".to_string(), false);
             }
             "auxiliary_material" => {
                 config.output_filename = "辅材处理结果.xlsx".to_string();
                 config.input_type = InputType::File;
                 // 设置默认 sheet 名称
                 config.selected_sheet = Some("辅材清单".to_string());
-                // 设置默认选项
-                config.set_bool("auto_classify".to_string(), true);
-                config.set_bool("remove_duplicates".to_string(), true);
-                config.set_bool("generate_summary".to_string(), false);
             }
             "excel_structure_analyzer" => {
                 config.output_filename = "分析结果.txt".to_string();
                 config.input_type = InputType::File;
                 // Excel分析器不需要输出目录，结果直接输出到日志
                 config.output_dir = None;
-                // 设置默认选项
-                config.set_bool("analyze_structure".to_string(), true);
-                config.set_bool("analyze_colors".to_string(), false);
-                config.set_bool("detailed_output".to_string(), true);
             }
             _ => {
                 config.output_filename = "output.xlsx".to_string();
             }
         }
+
+        // 设置默认选项
+        crate::models::OptionsSchema::for_processor(processor_id).apply_defaults(&mut config);
         
         config
     }
     
     /// 从文件加载可用的 sheet 列表
-    pub fn load_sheets_from_file(&mut self) -> Result<(), String> {
+    ///
+    /// `require_selection` 为处理器能力中的 `requires_sheet_selection`：为 true 时
+    /// 多 Sheet 文件不自动选择，由用户明确选择；只有一个 Sheet 时直接选中。
+    /// 之前选中的 Sheet 不在新文件中时清除选择。
+    pub fn load_sheets_from_file(&mut self, require_selection: bool) -> Result<(), String> {
         if let Some(path) = &self.input_path {
-            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("xlsx") {
+            if let Err(e) = crate::engine::data_engine::DataEngine::validate_format(path) {
+                return Err(e.user_message());
+            }
+            if path.is_file() {
                 // 使用 umya-spreadsheet 读取 sheet 列表
                 match umya_spreadsheet::reader::xlsx::read(path) {
                     Ok(book) => {
-                        // 获取工作表名称列表
-                        let names: Vec<String> = book
-                            .get_sheet_collection()
+                        // 获取工作表名称列表及可见性
+                        let sheets = book.get_sheet_collection();
+                        self.available_sheets = sheets
                             .iter()
                             .map(|ws| ws.get_name().to_string())
                             .collect();
-                        self.available_sheets = names;
+                        self.hidden_sheets = sheets
+                            .iter()
+                            .filter(|ws| ws.get_sheet_state() != "visible" && !ws.get_sheet_state().is_empty())
+                            .map(|ws| ws.get_name().to_string())
+                            .collect();
                         
-                        // 如果当前没有选中的 sheet，选择第一个
-                        if self.selected_sheet.is_none() && !self.available_sheets.is_empty() {
-                            self.selected_sheet = Some(self.available_sheets[0].clone());
+                        if self
+                            .selected_sheet
+                            .as_ref()
+                            .is_some_and(|name| !self.available_sheets.contains(name))
+                        {
+                            self.selected_sheet = None;
+                        }
+
+                        // 如果当前没有选中的 sheet，选择第一个可见的（需要明确选择时仅限单 Sheet 文件）
+                        if self.selected_sheet.is_none() && (!require_selection || self.available_sheets.len() == 1) {
+                            self.selected_sheet = self.visible_sheets().into_iter().next();
                         }
                         
                         Ok(())
@@ -129,12 +186,214 @@ impl ProcessorConfig {
         }
     }
     
+    /// 导出当前配置到 JSON 文件，便于分享完全一致的任务配置
+    pub fn export_to_file(&self, processor_id: &str, path: &Path) -> crate::error::Result<()> {
+        let exported = ExportedConfig {
+            processor_id: processor_id.to_string(),
+            config: self,
+        };
+        let json = serde_json::to_string_pretty(&exported)
+            .map_err(|e| AppError::config_error(format!("序列化处理器配置失败: {}", e)))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// 从 JSON 文件导入配置，文件属于其他处理器时返回错误
+    pub fn import_from_file(path: &Path, processor_id: &str) -> crate::error::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let exported: ExportedConfig<Self> = serde_json::from_str(&content)
+            .map_err(|e| AppError::config_error(format!("解析处理器配置失败: {}", e)))?;
+        if exported.processor_id != processor_id {
+            return Err(AppError::config_error(format!(
+                "该配置属于处理器 {}，不能导入到 {}",
+                exported.processor_id, processor_id
+            )));
+        }
+        Ok(exported.config)
+    }
+
+    /// 批量模式下的输出文件名模式（未设置或为空时沿用输入文件名）
+    pub fn output_name_pattern(&self) -> Option<String> {
+        let pattern = self.get_string("output_name_pattern");
+        if pattern.trim().is_empty() {
+            None
+        } else {
+            Some(pattern)
+        }
+    }
+
+    /// 根据当前配置生成批量处理选项
+    pub fn batch_options(&self, processor_id: &str) -> crate::engine::data_engine::BatchOptions {
+        crate::engine::data_engine::BatchOptions {
+            continue_on_error: self.continue_on_error(),
+            output_name_pattern: self.output_name_pattern(),
+            processor_id: processor_id.to_string(),
+            preserve_input_order: self.preserve_input_order(),
+            file_timeout: self.file_timeout(),
+            fail_fast_threshold: self.fail_fast_threshold(),
+            output_format: self.output_format,
+            generate_summary: self.get_bool("generate_summary"),
+            conflict_strategy: self.conflict_strategy(),
+            mirror_input_extension: self.get_bool("mirror_input_extension"),
+            csv: self.csv_options(),
+            ..Default::default()
+        }
+    }
+
+    /// 单文件处理的输出路径（输出目录 + 输出文件名），扩展名与输出格式一致
+    pub fn single_output_path(&self) -> Option<PathBuf> {
+        let output_dir = self.output_dir.as_ref()?;
+        let file_name = self.output_filename.trim();
+        if file_name.is_empty() {
+            return None;
+        }
+        let path = output_dir.join(file_name);
+        Some(match self.output_format {
+            OutputFormat::Xlsx => path,
+            format => path.with_extension(format.extension()),
+        })
+    }
+
+    /// CSV 输出的分隔符和 BOM 设置（默认逗号分隔并写入 UTF-8 BOM）
+    pub fn csv_options(&self) -> crate::engine::data_engine::CsvOptions {
+        let defaults = crate::engine::data_engine::CsvOptions::default();
+        let delimiter = match self.get_string("csv_delimiter").as_bytes() {
+            [d] => *d,
+            _ => defaults.delimiter,
+        };
+        crate::engine::data_engine::CsvOptions {
+            delimiter,
+            utf8_bom: self.get_bool_or("csv_utf8_bom", defaults.utf8_bom),
+        }
+    }
+
+    /// 是否在 Sheet 选择器中显示隐藏的 sheet（默认不显示）
+    pub fn show_hidden_sheets(&self) -> bool {
+        self.get_bool("show_hidden_sheets")
+    }
+
+    /// 判断 sheet 是否为隐藏状态
+    pub fn is_sheet_hidden(&self, name: &str) -> bool {
+        self.hidden_sheets.iter().any(|s| s == name)
+    }
+
+    /// 获取 Sheet 选择器中应显示的 sheet 列表
+    pub fn visible_sheets(&self) -> Vec<String> {
+        let show_hidden = self.show_hidden_sheets();
+        self.available_sheets
+            .iter()
+            .filter(|name| show_hidden || !self.is_sheet_hidden(name))
+            .cloned()
+            .collect()
+    }
+
     pub fn get_bool(&self, key: &str) -> bool {
+        self.get_bool_or(key, false)
+    }
+
+    /// 获取布尔选项，未设置时返回给定的默认值
+    pub fn get_bool_or(&self, key: &str, default: bool) -> bool {
         match self.options.get(key) {
             Some(ConfigValue::Bool(v)) => *v,
-            _ => false,
+            _ => default,
         }
     }
+
+    /// 获取整数选项，未设置时返回给定的默认值
+    pub fn get_int_or(&self, key: &str, default: i64) -> i64 {
+        match self.options.get(key) {
+            Some(ConfigValue::Int(v)) => *v,
+            _ => default,
+        }
+    }
+
+    pub fn set_int(&mut self, key: String, value: i64) {
+        self.options.insert(key, ConfigValue::Int(value));
+    }
+
+    /// 用户调整后的行类型识别顺序（规则名称列表），未调整时为空
+    pub fn row_type_order(&self) -> Vec<String> {
+        self.get_string("row_type_order")
+            .split(',')
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    pub fn set_row_type_order(&mut self, order: &[String]) {
+        self.set_string("row_type_order".to_string(), order.join(","));
+    }
+
+    /// 只输出的行类型，未设置时为空（不过滤）
+    pub fn kept_row_types(&self) -> Vec<crate::models::RowType> {
+        self.get_string("kept_row_types")
+            .split(',')
+            .map(|label| label.trim())
+            .filter(|label| !label.is_empty())
+            .map(crate::models::RowType::from_label)
+            .collect()
+    }
+
+    pub fn set_kept_row_types(&mut self, row_types: &[crate::models::RowType]) {
+        let labels: Vec<&str> = row_types.iter().map(|t| t.display_name()).collect();
+        self.set_string("kept_row_types".to_string(), labels.join(","));
+    }
+
+    /// 读取单元格时的文本规范化设置
+    pub fn text_normalization(&self) -> crate::engine::TextNormalization {
+        if self.get_bool("normalize_text") {
+            crate::engine::TextNormalization::standard()
+                .with_full_width_to_half(self.get_bool("full_width_to_half"))
+        } else {
+            crate::engine::TextNormalization::default()
+        }
+    }
+
+    /// 结构分析器输出到日志的最大行数
+    pub fn dump_max_rows(&self) -> usize {
+        let default = crate::processor::examples::excel_structure_analyzer::DEFAULT_DUMP_MAX_ROWS as i64;
+        self.get_int_or("dump_max_rows", default).max(1) as usize
+    }
+
+    /// 结构分析器的快速预览行数，0 或未设置时为 None（完整分析）
+    pub fn preview_rows(&self) -> Option<usize> {
+        match self.get_int_or("preview_rows", 0) {
+            rows if rows > 0 => Some(rows as usize),
+            _ => None,
+        }
+    }
+
+    /// 单个文件的处理时限（选项值为秒数，0 或未设置表示不限时）
+    pub fn file_timeout(&self) -> Option<std::time::Duration> {
+        match self.get_int_or("file_timeout_secs", 0) {
+            secs if secs > 0 => Some(std::time::Duration::from_secs(secs as u64)),
+            _ => None,
+        }
+    }
+
+    /// 允许的最多失败文件数，超过后中止批量处理（0 表示不限）
+    pub fn fail_fast_threshold(&self) -> Option<usize> {
+        match self.get_int_or("fail_fast_threshold", 0) {
+            threshold if threshold > 0 => Some(threshold as usize),
+            _ => None,
+        }
+    }
+
+    /// 并行处理时是否按输入顺序汇总输出（默认开启）
+    pub fn preserve_input_order(&self) -> bool {
+        self.get_bool_or("preserve_input_order", true)
+    }
+
+    /// 输出文件已存在时的处理方式（默认覆盖）
+    pub fn conflict_strategy(&self) -> crate::engine::ConflictStrategy {
+        crate::engine::ConflictStrategy::from_key(&self.get_string("conflict_strategy"))
+    }
+
+    /// 单个文件失败后是否继续处理剩余文件（默认开启）
+    pub fn continue_on_error(&self) -> bool {
+        self.get_bool_or("continue_on_error", true)
+    }
     
     pub fn set_bool(&mut self, key: String, value: bool) {
         self.options.insert(key, ConfigValue::Bool(value));
@@ -152,13 +411,70 @@ impl ProcessorConfig {
     }
 }
 
+/// 每个处理器最多保留的撤销步数
+pub const MAX_UNDO_DEPTH: usize = 50;
+
 /// 所有处理器的配置集合
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProcessorConfigs {
     pub configs: HashMap<String, ProcessorConfig>,
+    /// 各处理器配置修改前的快照（仅保存在内存中）
+    #[serde(skip)]
+    undo_stacks: HashMap<String, Vec<ProcessorConfig>>,
+    /// 修改计数，每次 `update`、`undo` 或 `mark_changed` 后加一，用于判断是否需要保存
+    #[serde(skip)]
+    revision: u64,
 }
 
 impl ProcessorConfigs {
+    /// 更新配置；内容有变化时把旧配置压入撤销栈
+    pub fn update(&mut self, processor_id: &str, config: ProcessorConfig) {
+        let current = self.get_or_create(processor_id);
+        if *current == config {
+            return;
+        }
+
+        let previous = std::mem::replace(current, config);
+        self.revision += 1;
+        let stack = self.undo_stacks.entry(processor_id.to_string()).or_default();
+        stack.push(previous);
+        if stack.len() > MAX_UNDO_DEPTH {
+            stack.remove(0);
+        }
+    }
+
+    /// 撤销最近一次修改，没有可撤销的修改时返回 false
+    pub fn undo(&mut self, processor_id: &str) -> bool {
+        let previous = match self.undo_stacks.get_mut(processor_id).and_then(|s| s.pop()) {
+            Some(previous) => previous,
+            None => return false,
+        };
+        self.configs.insert(processor_id.to_string(), previous);
+        self.revision += 1;
+        true
+    }
+
+    /// 修改计数，内容有变化时递增
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// 通过 `get_or_create` 直接修改配置后调用，记录一次修改
+    pub fn mark_changed(&mut self) {
+        self.revision += 1;
+    }
+
+    /// 把指定处理器的配置恢复为默认值（可撤销），不影响其他处理器
+    pub fn reset(&mut self, processor_id: &str) {
+        self.update(processor_id, ProcessorConfig::new(processor_id));
+    }
+
+    pub fn can_undo(&self, processor_id: &str) -> bool {
+        self.undo_stacks
+            .get(processor_id)
+            .is_some_and(|s| !s.is_empty())
+    }
+
     pub fn get_or_create(&mut self, processor_id: &str) -> &mut ProcessorConfig {
         self.configs
             .entry(processor_id.to_string())
@@ -169,3 +485,221 @@ impl ProcessorConfigs {
         self.configs.get(processor_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_undo_config_changes() {
+        let mut configs = ProcessorConfigs::default();
+        let original = configs.get_or_create("cargo_analysis").clone();
+
+        let mut edited = original.clone();
+        edited.input_path = Some(PathBuf::from("/data/a.xlsx"));
+        configs.update("cargo_analysis", edited.clone());
+
+        let mut cleared = edited.clone();
+        cleared.input_path = None;
+        configs.update("cargo_analysis", cleared);
+        // 内容未变化时不产生撤销记录
+        configs.update("cargo_analysis", configs.get("cargo_analysis").unwrap().clone());
+
+        // 两次修改计数，未变化的更新不计
+        assert_eq!(configs.revision(), 2);
+
+        assert!(configs.undo("cargo_analysis"));
+        assert_eq!(configs.get("cargo_analysis"), Some(&edited));
+        assert!(configs.undo("cargo_analysis"));
+        assert_eq!(configs.get("cargo_analysis"), Some(&original));
+        assert!(!configs.undo("cargo_analysis"));
+        assert_eq!(configs.revision(), 4);
+    }
+
+    #[test]
+    fn test_reset_restores_processor_defaults() {
+        let mut configs = ProcessorConfigs::default();
+
+        let cargo = configs.get_or_create("cargo_analysis");
+        cargo.input_path = Some(PathBuf::from("/data/a.xlsx"));
+        cargo.output_filename = "乱改.xlsx".to_string();
+        cargo.selected_sheet = None;
+        cargo.set_bool("normalize_text".to_string(), true);
+        configs.get_or_create("excel_structure_analyzer").input_path = Some(PathBuf::from("/data/b.xlsx"));
+
+        configs.reset("cargo_analysis");
+
+        let cargo = configs.get("cargo_analysis").unwrap();
+        assert_eq!(cargo, &ProcessorConfig::new("cargo_analysis"));
+        assert_eq!(cargo.output_filename, "货物分析表.xlsx");
+        assert_eq!(cargo.selected_sheet.as_deref(), Some("货物数据"));
+        // 其他处理器不受影响
+        assert_eq!(
+            configs.get("excel_structure_analyzer").unwrap().input_path,
+            Some(PathBuf::from("/data/b.xlsx"))
+        );
+        // 恢复默认可以撤销
+        assert!(configs.undo("cargo_analysis"));
+        assert_eq!(configs.get("cargo_analysis").unwrap().output_filename, "乱改.xlsx");
+    }
+
+    #[test]
+    fn test_undo_stack_depth_is_capped() {
+        let mut configs = ProcessorConfigs::default();
+        for i in 0..(MAX_UNDO_DEPTH + 10) {
+            let mut config = configs.get_or_create("p").clone();
+            config.output_filename = format!("{}.xlsx", i);
+            configs.update("p", config);
+        }
+
+        let mut undone = 0;
+        while configs.undo("p") {
+            undone += 1;
+        }
+        assert_eq!(undone, MAX_UNDO_DEPTH);
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("cargo_analysis.json");
+
+        let mut config = ProcessorConfig::new("cargo_analysis");
+        config.input_path = Some(PathBuf::from("/data/货物分析表.xlsx"));
+        config.output_dir = Some(PathBuf::from("/data/out"));
+        config.set_string("note".to_string(), "测试".to_string());
+
+        config.export_to_file("cargo_analysis", &path).unwrap();
+        let imported = ProcessorConfig::import_from_file(&path, "cargo_analysis").unwrap();
+
+        assert_eq!(imported.input_path, config.input_path);
+        assert_eq!(imported.input_type, config.input_type);
+        assert_eq!(imported.output_dir, config.output_dir);
+        assert_eq!(imported.output_filename, config.output_filename);
+        assert_eq!(imported.selected_sheet, config.selected_sheet);
+        assert!(imported.get_bool("generate_charts"));
+        assert_eq!(imported.get_string("note"), "测试");
+    }
+
+    #[test]
+    fn test_continue_on_error_defaults_to_true() {
+        let mut config = ProcessorConfig::default();
+        assert!(config.continue_on_error());
+
+        config.set_bool("continue_on_error".to_string(), false);
+        assert!(!config.continue_on_error());
+    }
+
+    #[test]
+    fn test_batch_options_from_config() {
+        let mut config = ProcessorConfig::new("cargo_analysis");
+        assert_eq!(config.output_name_pattern(), None);
+
+        config.set_string("output_name_pattern".to_string(), "  ".to_string());
+        assert_eq!(config.output_name_pattern(), None);
+
+        config.set_string("output_name_pattern".to_string(), "{date}_{name}.xlsx".to_string());
+        config.set_bool("continue_on_error".to_string(), false);
+        assert_eq!(config.batch_options("cargo_analysis").fail_fast_threshold, None);
+        config.set_int("fail_fast_threshold".to_string(), 3);
+
+        let options = config.batch_options("cargo_analysis");
+        assert_eq!(options.output_name_pattern.as_deref(), Some("{date}_{name}.xlsx"));
+        assert!(!options.continue_on_error);
+        assert_eq!(options.fail_fast_threshold, Some(3));
+        assert_eq!(options.processor_id, "cargo_analysis");
+    }
+
+    #[test]
+    fn test_hidden_sheets_filtered_by_default() {
+        let mut config = ProcessorConfig::default();
+        config.available_sheets = vec![
+            "封面".to_string(),
+            "隐藏数据".to_string(),
+            "屏柜分项表".to_string(),
+        ];
+        config.hidden_sheets = vec!["隐藏数据".to_string()];
+
+        assert_eq!(config.visible_sheets(), vec!["封面", "屏柜分项表"]);
+
+        config.set_bool("show_hidden_sheets".to_string(), true);
+        assert_eq!(config.visible_sheets().len(), 3);
+    }
+
+    #[test]
+    fn test_load_sheets_detects_hidden_sheets() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sheets.xlsx");
+
+        let mut book = umya_spreadsheet::new_file();
+        book.new_sheet("隐藏数据")
+            .unwrap()
+            .set_sheet_state("hidden".to_string());
+        book.new_sheet("屏柜分项表").unwrap();
+        umya_spreadsheet::writer::xlsx::write(&book, &path).unwrap();
+
+        let mut config = ProcessorConfig::default();
+        config.input_path = Some(path);
+        config.load_sheets_from_file(false).unwrap();
+
+        assert_eq!(config.available_sheets.len(), 3);
+        assert_eq!(config.hidden_sheets, vec!["隐藏数据"]);
+        assert_eq!(config.visible_sheets(), vec!["Sheet1", "屏柜分项表"]);
+    }
+
+    #[test]
+    fn test_load_sheets_keeps_sheet_choice_for_processors_that_require_it() {
+        use crate::processor::ProcessorCapabilities;
+
+        let dir = tempdir().unwrap();
+        let multi = dir.path().join("multi.xlsx");
+        let mut book = umya_spreadsheet::new_file();
+        book.new_sheet("屏柜分项表").unwrap();
+        umya_spreadsheet::writer::xlsx::write(&book, &multi).unwrap();
+        let single = dir.path().join("single.xlsx");
+        umya_spreadsheet::writer::xlsx::write(&umya_spreadsheet::new_file(), &single).unwrap();
+
+        let capabilities = ProcessorCapabilities::log_only();
+        let mut config = ProcessorConfig::new("excel_structure_analyzer");
+        config.input_path = Some(multi.clone());
+        config.load_sheets_from_file(capabilities.requires_sheet_selection).unwrap();
+
+        // 多 Sheet 文件不自动选择，开始处理前必须由用户选择
+        assert_eq!(config.selected_sheet, None);
+        assert!(capabilities.needs_sheet_choice(&config));
+
+        // 单 Sheet 文件直接选中
+        config.input_path = Some(single);
+        config.load_sheets_from_file(capabilities.requires_sheet_selection).unwrap();
+        assert_eq!(config.selected_sheet.as_deref(), Some("Sheet1"));
+        assert!(!capabilities.needs_sheet_choice(&config));
+
+        // 不要求选择的处理器仍自动选中第一个可见 Sheet
+        let mut config = ProcessorConfig::default();
+        config.input_path = Some(multi);
+        config.load_sheets_from_file(false).unwrap();
+        assert_eq!(config.selected_sheet.as_deref(), Some("Sheet1"));
+    }
+
+    #[test]
+    fn test_import_invalid_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("broken.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let err = ProcessorConfig::import_from_file(&path, "cargo_analysis").unwrap_err();
+        assert!(err.is_config_error());
+    }
+
+    #[test]
+    fn test_import_rejects_other_processor_config() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("cargo_analysis.json");
+        ProcessorConfig::new("cargo_analysis").export_to_file("cargo_analysis", &path).unwrap();
+
+        let err = ProcessorConfig::import_from_file(&path, "excel_structure_analyzer").unwrap_err();
+        assert!(err.is_config_error());
+        assert!(err.to_string().contains("cargo_analysis"));
+    }
+}
@@ -1,4 +1,17 @@
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// 应用窗口标题
+pub const APP_TITLE: &str = "IntegratedPower";
+
+/// 单个文件的处理结果（用于进度视图中的最近完成列表）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileOutcome {
+    /// 文件名
+    pub file: String,
+    /// 是否处理成功
+    pub success: bool,
+}
 
 /// 处理进度信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +24,9 @@ pub struct ProcessingProgress {
     pub current_file: String,
     /// 进度百分比 (0.0 - 100.0)
     pub percentage: f32,
+    /// 本次进度事件对应的刚完成的文件（如有）
+    #[serde(default)]
+    pub completed: Option<FileOutcome>,
 }
 
 impl ProcessingProgress {
@@ -21,9 +37,18 @@ impl ProcessingProgress {
             processed_files: 0,
             current_file: String::new(),
             percentage: 0.0,
+            completed: None,
         }
     }
 
+    /// 记录刚完成的文件及其结果
+    pub fn mark_completed(&mut self, file: impl Into<String>, success: bool) {
+        self.completed = Some(FileOutcome {
+            file: file.into(),
+            success,
+        });
+    }
+
     /// 更新进度
     pub fn update(&mut self, processed_files: usize, current_file: String) {
         self.processed_files = processed_files;
@@ -39,6 +64,19 @@ impl ProcessingProgress {
     pub fn is_complete(&self) -> bool {
         self.processed_files >= self.total_files
     }
+
+    /// 窗口标题：处理中时附带当前百分比，空闲时为应用名称
+    pub fn window_title(&self, processing: bool) -> String {
+        if processing {
+            format!(
+                "{} — 处理中 {}",
+                APP_TITLE,
+                super::format_percentage(self.percentage.clamp(0.0, 100.0))
+            )
+        } else {
+            APP_TITLE.to_string()
+        }
+    }
 }
 
 impl Default for ProcessingProgress {
@@ -48,6 +86,90 @@ impl Default for ProcessingProgress {
             processed_files: 0,
             current_file: String::new(),
             percentage: 0.0,
+            completed: None,
+        }
+    }
+}
+
+/// 最近完成文件的滚动列表，超出容量时丢弃最早的记录
+#[derive(Debug, Clone)]
+pub struct RecentOutcomes {
+    entries: VecDeque<FileOutcome>,
+    capacity: usize,
+}
+
+impl RecentOutcomes {
+    /// 进度视图默认显示的条数
+    pub const DEFAULT_CAPACITY: usize = 10;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
         }
     }
+
+    pub fn push(&mut self, outcome: FileOutcome) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(outcome);
+    }
+
+    /// 按完成时间从新到旧遍历
+    pub fn iter_latest_first(&self) -> impl Iterator<Item = &FileOutcome> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl Default for RecentOutcomes {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(file: &str, success: bool) -> FileOutcome {
+        FileOutcome {
+            file: file.to_string(),
+            success,
+        }
+    }
+
+    #[test]
+    fn test_recent_outcomes_evicts_oldest() {
+        let mut recent = RecentOutcomes::new(2);
+        recent.push(outcome("a.xlsx", true));
+        recent.push(outcome("b.xlsx", false));
+        recent.push(outcome("c.xlsx", true));
+
+        assert_eq!(recent.len(), 2);
+        let files: Vec<&str> = recent.iter_latest_first().map(|o| o.file.as_str()).collect();
+        assert_eq!(files, vec!["c.xlsx", "b.xlsx"]);
+    }
+
+    #[test]
+    fn test_window_title() {
+        let mut progress = ProcessingProgress::new(50);
+        progress.update(21, "a.xlsx".to_string());
+
+        assert_eq!(progress.window_title(true), "IntegratedPower — 处理中 42%");
+        assert_eq!(progress.window_title(false), "IntegratedPower");
+        assert_eq!(ProcessingProgress::default().window_title(true), "IntegratedPower — 处理中 0%");
+    }
 }
@@ -13,9 +13,21 @@ pub struct ProcessingResult {
     pub failed: usize,
     /// 错误列表
     pub errors: Vec<ProcessingError>,
+    /// 成功写出的输出文件
+    #[serde(default)]
+    pub output_files: Vec<PathBuf>,
+    /// 各输出文件的数据行数（与 `output_files` 一一对应）
+    #[serde(default)]
+    pub output_rows: Vec<usize>,
     /// 处理耗时
     #[serde(with = "duration_serde")]
     pub duration: Duration,
+    /// 输入目录中没有找到任何 Excel 文件（与处理了文件但没有输出区分）
+    #[serde(default)]
+    pub input_empty: bool,
+    /// 批量处理提前中止的原因（如失败文件过多），正常结束时为 None
+    #[serde(default)]
+    pub aborted: Option<String>,
 }
 
 impl ProcessingResult {
@@ -26,15 +38,61 @@ impl ProcessingResult {
             successful: 0,
             failed: 0,
             errors: Vec::new(),
+            output_files: Vec::new(),
+            output_rows: Vec::new(),
             duration: Duration::default(),
+            input_empty: false,
+            aborted: None,
         }
     }
 
+    /// 输入目录中没有 Excel 文件时的结果
+    pub fn empty_input() -> Self {
+        Self {
+            input_empty: true,
+            ..Self::new(0)
+        }
+    }
+
+    /// 是否因为输入目录中没有 Excel 文件而未处理任何文件
+    pub fn is_empty_input(&self) -> bool {
+        self.input_empty
+    }
+
+    /// 记录批量处理提前中止的原因
+    pub fn mark_aborted(&mut self, reason: impl Into<String>) {
+        self.aborted = Some(reason.into());
+    }
+
+    /// 是否提前中止（剩余文件未处理）
+    pub fn was_aborted(&self) -> bool {
+        self.aborted.is_some()
+    }
+
     /// 添加成功记录
     pub fn add_success(&mut self) {
         self.successful += 1;
     }
 
+    /// 记录成功写出的输出文件及其数据行数
+    pub fn add_output(&mut self, path: PathBuf, rows: usize) {
+        self.output_files.push(path);
+        self.output_rows.push(rows);
+    }
+
+    /// 所有输出文件的数据行数合计
+    pub fn total_rows(&self) -> usize {
+        self.output_rows.iter().sum()
+    }
+
+    /// 只产生一个输出文件时返回该文件，可直接打开查看
+    pub fn single_output_file(&self) -> Option<&std::path::Path> {
+        match self.output_files.as_slice() {
+            [file] => Some(file),
+            _ => None,
+        }
+    }
+
     /// 添加失败记录
     pub fn add_failure(&mut self, error: ProcessingError) {
         self.failed += 1;
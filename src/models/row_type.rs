@@ -1,8 +1,9 @@
 // Row Type Identification Models
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// 表示Excel行的语义类型
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RowType {
     /// 项目编号行 (浅绿色背景)
     ProjectNumber,
@@ -24,17 +25,98 @@ pub enum RowType {
     Custom(String),
 }
 
+impl RowType {
+    /// 用于界面显示的名称，自定义类型显示其名称本身
+    pub fn display_name(&self) -> &str {
+        match self {
+            RowType::ProjectNumber => "项目编号行",
+            RowType::CabinetNumber => "柜号行",
+            RowType::Header => "表头行",
+            RowType::Data => "数据行",
+            RowType::SubTotal => "小计行",
+            RowType::UnitTotal => "单台合计行",
+            RowType::GrandTotal => "总计行",
+            RowType::Unknown => "未知",
+            RowType::Custom(name) => name,
+        }
+    }
+
+    /// 图表中使用的颜色，同一类型始终相同；自定义类型按名称从调色板中选取
+    pub fn chart_color(&self) -> egui::Color32 {
+        use egui::Color32;
+        match self {
+            RowType::ProjectNumber => Color32::from_rgb(76, 175, 80),
+            RowType::CabinetNumber => Color32::from_rgb(96, 125, 139),
+            RowType::Header => Color32::from_rgb(158, 158, 158),
+            RowType::Data => Color32::from_rgb(33, 150, 243),
+            RowType::SubTotal => Color32::from_rgb(233, 30, 99),
+            RowType::UnitTotal => Color32::from_rgb(156, 39, 176),
+            RowType::GrandTotal => Color32::from_rgb(255, 152, 0),
+            RowType::Unknown => Color32::from_rgb(244, 67, 54),
+            RowType::Custom(name) => {
+                const PALETTE: [Color32; 6] = [
+                    Color32::from_rgb(0, 150, 136),
+                    Color32::from_rgb(121, 85, 72),
+                    Color32::from_rgb(205, 220, 57),
+                    Color32::from_rgb(63, 81, 181),
+                    Color32::from_rgb(255, 193, 7),
+                    Color32::from_rgb(0, 188, 212),
+                ];
+                // FNV-1a，保证不同运行之间颜色一致
+                let hash = name
+                    .bytes()
+                    .fold(0x811c9dc5u32, |h, b| (h ^ b as u32).wrapping_mul(0x01000193));
+                PALETTE[hash as usize % PALETTE.len()]
+            }
+        }
+    }
+
+    /// 所有内置类型（不含自定义类型）
+    pub const BUILTIN: [RowType; 8] = [
+        RowType::ProjectNumber,
+        RowType::CabinetNumber,
+        RowType::Header,
+        RowType::Data,
+        RowType::SubTotal,
+        RowType::UnitTotal,
+        RowType::GrandTotal,
+        RowType::Unknown,
+    ];
+
+    /// 从标签解析行类型，接受显示名称（如 "表头行"）或变体名称（如 "Header"），
+    /// 其他文本视为自定义类型
+    pub fn from_label(label: &str) -> Self {
+        let label = label.trim();
+        Self::BUILTIN
+            .into_iter()
+            .find(|t| t.display_name() == label || format!("{:?}", t) == label)
+            .unwrap_or_else(|| RowType::Custom(label.to_string()))
+    }
+}
+
 /// RGB颜色值
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RgbColor {
     pub r: u8,
     pub g: u8,
     pub b: u8,
 }
 
+/// 常见高亮色及其显示名称（货物分析表使用的底色）
+const NAMED_COLORS: &[(RgbColor, &str)] = &[
+    (RgbColor::new(198, 239, 206), "浅绿/项目色"),
+    (RgbColor::new(127, 150, 152), "灰蓝/柜号色"),
+    (RgbColor::new(217, 217, 217), "浅灰/表头色"),
+    (RgbColor::new(255, 204, 253), "浅粉/合计色"),
+    (RgbColor::new(255, 255, 255), "白色"),
+];
+
+/// 颜色名称匹配的最大欧氏距离
+const COLOR_NAME_TOLERANCE: u32 = 24;
+
 impl RgbColor {
     /// 创建新的RGB颜色
-    pub fn new(r: u8, g: u8, b: u8) -> Self {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
         Self { r, g, b }
     }
     
@@ -42,10 +124,87 @@ impl RgbColor {
     pub fn white() -> Self {
         Self::new(255, 255, 255)
     }
+
+    /// 黑色
+    pub fn black() -> Self {
+        Self::new(0, 0, 0)
+    }
+
+    /// 从 "RRGGBB" 或 "#RRGGBB" 形式的十六进制字符串解析
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Self::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    /// 转换为 "RRGGBB" 形式的大写十六进制字符串（与 [`from_hex`](Self::from_hex) 互逆）
+    pub fn to_hex(&self) -> String {
+        format!("{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+
+    /// 与另一颜色的欧氏距离平方
+    pub fn distance_squared(&self, other: &RgbColor) -> u32 {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+        d(self.r, other.r) + d(self.g, other.g) + d(self.b, other.b)
+    }
+
+    /// 转换为 CIE Lab 颜色空间（sRGB, D65 白点）
+    pub fn to_lab(&self) -> (f64, f64, f64) {
+        // sRGB 伽马解码
+        let linear = |c: u8| {
+            let c = c as f64 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let (r, g, b) = (linear(self.r), linear(self.g), linear(self.b));
+
+        // 线性 RGB -> XYZ，按 D65 白点归一化
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+
+        let f = |t: f64| {
+            const DELTA: f64 = 6.0 / 29.0;
+            if t > DELTA.powi(3) {
+                t.cbrt()
+            } else {
+                t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+
+    /// 与另一颜色的感知色差（CIE76 ΔE）
+    ///
+    /// ΔE 约 2.3 为人眼可察觉的最小差异。
+    pub fn delta_e76(&self, other: &RgbColor) -> f64 {
+        let (l1, a1, b1) = self.to_lab();
+        let (l2, a2, b2) = other.to_lab();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// 最接近的常见颜色名称，超出容差时返回 "未命名"
+    pub fn nearest_name(&self) -> &'static str {
+        NAMED_COLORS
+            .iter()
+            .map(|(color, name)| (self.distance_squared(color), *name))
+            .filter(|(distance, _)| *distance <= COLOR_NAME_TOLERANCE * COLOR_NAME_TOLERANCE)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, name)| name)
+            .unwrap_or("未命名")
+    }
 }
 
 /// 单元格样式信息
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CellStyle {
     /// 背景颜色
     pub background_color: Option<RgbColor>,
@@ -69,7 +228,7 @@ impl Default for CellStyle {
 }
 
 /// 单元格合并信息
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MergeInfo {
     /// 合并范围的起始列
     pub start_col: usize,
@@ -94,7 +253,7 @@ impl MergeInfo {
 }
 
 /// 表示Excel中单个单元格的数据
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CellData {
     /// 列索引 (从0开始)
     pub column_index: usize,
@@ -104,6 +263,11 @@ pub struct CellData {
     pub style: CellStyle,
     /// 合并信息
     pub merge_info: Option<MergeInfo>,
+    /// 单元格批注
+    pub comment: Option<String>,
+    /// 文本规范化前的原始内容（仅在规范化改变了内容时保存）
+    #[serde(default)]
+    pub raw_content: Option<String>,
 }
 
 impl CellData {
@@ -111,10 +275,15 @@ impl CellData {
     pub fn is_empty(&self) -> bool {
         self.content.trim().is_empty()
     }
+
+    /// 读取时的原始内容（未规范化时与 `content` 相同）
+    pub fn raw(&self) -> &str {
+        self.raw_content.as_deref().unwrap_or(&self.content)
+    }
 }
 
 /// 表示Excel中一行的完整数据
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RowData {
     /// 行号 (从0开始)
     pub row_index: usize,
@@ -123,6 +292,40 @@ pub struct RowData {
 }
 
 impl RowData {
+    /// 创建新的行数据
+    pub fn new(row_index: usize, cells: Vec<CellData>) -> Self {
+        Self { row_index, cells }
+    }
+
+    /// 获取每一列的背景颜色
+    pub fn background_colors(&self) -> Vec<Option<RgbColor>> {
+        self.cells
+            .iter()
+            .map(|cell| cell.style.background_color)
+            .collect()
+    }
+
+    /// 获取行主色（出现次数最多的背景色，次数相同时取靠前的列）
+    ///
+    /// 没有任何单元格设置背景色时返回None。
+    pub fn dominant_color(&self) -> Option<RgbColor> {
+        let mut counts: Vec<(RgbColor, usize)> = Vec::new();
+        for color in self.cells.iter().filter_map(|c| c.style.background_color) {
+            match counts.iter_mut().find(|(c, _)| *c == color) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((color, 1)),
+            }
+        }
+
+        let mut dominant: Option<(RgbColor, usize)> = None;
+        for (color, count) in counts {
+            if dominant.map_or(true, |(_, best)| count > best) {
+                dominant = Some((color, count));
+            }
+        }
+        dominant.map(|(color, _)| color)
+    }
+
     /// 获取指定列的单元格
     pub fn get_cell(&self, column_index: usize) -> Option<&CellData> {
         self.cells.get(column_index)
@@ -132,10 +335,15 @@ impl RowData {
     pub fn first_non_empty_cell(&self) -> Option<&CellData> {
         self.cells.iter().find(|cell| !cell.is_empty())
     }
+
+    /// 序列化为格式化的 JSON（内容、颜色、合并信息），便于粘贴到问题反馈中
+    pub fn to_debug_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
+    }
 }
 
 /// 表示Excel工作表的完整数据
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WorksheetData {
     /// 工作表名称
     pub name: String,
@@ -168,6 +376,16 @@ pub struct RowIdentificationResult {
     pub confidence: f32,
 }
 
+impl RowIdentificationResult {
+    /// 没有任何规则匹配时的规则名称
+    pub const UNMATCHED_RULE: &'static str = "none";
+
+    /// 是否没有规则匹配（行类型为配置的默认类型）
+    pub fn is_unmatched(&self) -> bool {
+        self.matched_rule == Self::UNMATCHED_RULE
+    }
+}
+
 /// 识别统计信息
 #[derive(Debug, Clone)]
 pub struct IdentificationStatistics {
@@ -177,6 +395,8 @@ pub struct IdentificationStatistics {
     pub row_type_counts: HashMap<RowType, usize>,
     /// 未知类型行数
     pub unknown_count: usize,
+    /// 没有规则匹配、按配置的默认类型归类的行数（默认类型为Unknown时为0）
+    pub defaulted_count: usize,
     /// 识别成功率
     pub success_rate: f32,
 }
@@ -187,11 +407,14 @@ impl IdentificationStatistics {
         let total_rows = results.len();
         let mut row_type_counts = HashMap::new();
         let mut unknown_count = 0;
+        let mut defaulted_count = 0;
         
         for result in results {
             *row_type_counts.entry(result.row_type.clone()).or_insert(0) += 1;
             if result.row_type == RowType::Unknown {
                 unknown_count += 1;
+            } else if result.is_unmatched() {
+                defaulted_count += 1;
             }
         }
         
@@ -205,7 +428,189 @@ impl IdentificationStatistics {
             total_rows,
             row_type_counts,
             unknown_count,
+            defaulted_count,
             success_rate,
         }
     }
+
+    /// 按行数降序排列的各类型统计（包括自定义类型），行数相同时按名称排序
+    pub fn sorted_counts(&self) -> Vec<(&RowType, usize)> {
+        let mut counts: Vec<(&RowType, usize)> = self
+            .row_type_counts
+            .iter()
+            .map(|(row_type, count)| (row_type, *count))
+            .collect();
+        counts.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| a.0.display_name().cmp(b.0.display_name()))
+        });
+        counts
+    }
+
+    /// 用于绘制饼图/条形图的数据序列：按行数降序的 (类型名称, 行数, 颜色)
+    pub fn chart_series(&self) -> Vec<(String, usize, egui::Color32)> {
+        self.sorted_counts()
+            .into_iter()
+            .map(|(row_type, count)| (row_type.display_name().to_string(), count, row_type.chart_color()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_color_name() {
+        assert_eq!(RgbColor::new(198, 239, 206).nearest_name(), "浅绿/项目色");
+        assert_eq!(RgbColor::new(127, 150, 152).nearest_name(), "灰蓝/柜号色");
+        assert_eq!(RgbColor::new(217, 217, 217).nearest_name(), "浅灰/表头色");
+        assert_eq!(RgbColor::new(255, 204, 253).nearest_name(), "浅粉/合计色");
+        // 容差范围内的近似色
+        assert_eq!(RgbColor::new(200, 240, 205).nearest_name(), "浅绿/项目色");
+        assert_eq!(RgbColor::new(0, 0, 255).nearest_name(), "未命名");
+    }
+
+    #[test]
+    fn test_rgb_from_hex() {
+        assert_eq!(RgbColor::from_hex("#C6EFCE"), Some(RgbColor::new(198, 239, 206)));
+        assert_eq!(RgbColor::from_hex("ffccfd"), Some(RgbColor::new(255, 204, 253)));
+        assert_eq!(RgbColor::from_hex("xyz"), None);
+    }
+
+    #[test]
+    fn test_row_type_display_name() {
+        assert_eq!(RowType::Custom("X".to_string()).display_name(), "X");
+        assert_eq!(RowType::Header.display_name(), "表头行");
+    }
+
+    #[test]
+    fn test_sorted_counts_include_custom_types() {
+        let result = |row_index, row_type| RowIdentificationResult {
+            row_index,
+            row_type,
+            matched_rule: "rule".to_string(),
+            confidence: 1.0,
+        };
+        let stats = IdentificationStatistics::from_results(&[
+            result(0, RowType::Data),
+            result(1, RowType::Custom("备注行".to_string())),
+            result(2, RowType::Data),
+        ]);
+
+        let counts = stats.sorted_counts();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0], (&RowType::Data, 2));
+        assert_eq!(counts[1].0.display_name(), "备注行");
+    }
+
+    #[test]
+    fn test_chart_series_sums_to_total_rows() {
+        let result = |row_index, row_type| RowIdentificationResult {
+            row_index,
+            row_type,
+            matched_rule: "rule".to_string(),
+            confidence: 1.0,
+        };
+        let stats = IdentificationStatistics::from_results(&[
+            result(0, RowType::Header),
+            result(1, RowType::Data),
+            result(2, RowType::Data),
+            result(3, RowType::Unknown),
+            result(4, RowType::Custom("备注行".to_string())),
+        ]);
+
+        let series = stats.chart_series();
+        assert_eq!(series.iter().map(|(_, count, _)| count).sum::<usize>(), stats.total_rows);
+        assert_eq!(series[0].0, "数据行");
+        assert_eq!(series[0].2, RowType::Data.chart_color());
+        // 自定义类型颜色稳定
+        let custom = series.iter().find(|(name, _, _)| name == "备注行").unwrap();
+        assert_eq!(custom.2, RowType::Custom("备注行".to_string()).chart_color());
+    }
+
+    fn cell(column_index: usize, background_color: Option<RgbColor>) -> CellData {
+        CellData {
+            column_index,
+            content: String::new(),
+            style: CellStyle {
+                background_color,
+                ..Default::default()
+            },
+            merge_info: None,
+            comment: None,
+            raw_content: None,
+        }
+    }
+
+    #[test]
+    fn test_background_colors() {
+        let pink = RgbColor::new(255, 204, 253);
+        let row = RowData::new(0, vec![cell(0, Some(pink)), cell(1, None)]);
+
+        assert_eq!(row.background_colors(), vec![Some(pink), None]);
+    }
+
+    #[test]
+    fn test_dominant_color_mostly_pink_row() {
+        let pink = RgbColor::new(255, 204, 253);
+        let gray = RgbColor::new(217, 217, 217);
+        let mut row = RowData::new(
+            0,
+            vec![
+                cell(0, Some(gray)),
+                cell(1, Some(pink)),
+                cell(2, Some(pink)),
+                cell(3, None),
+                cell(4, Some(pink)),
+            ],
+        );
+
+        assert_eq!(row.dominant_color(), Some(pink));
+
+        // 修改单元格后按新的颜色计算
+        for cell in &mut row.cells {
+            cell.style.background_color = Some(gray);
+        }
+        assert_eq!(row.dominant_color(), Some(gray));
+    }
+
+    #[test]
+    fn test_dominant_color_without_background() {
+        let row = RowData::new(0, vec![cell(0, None), cell(1, None)]);
+        assert_eq!(row.dominant_color(), None);
+    }
+
+    #[test]
+    fn test_row_to_debug_json_includes_color_and_merge() {
+        let cell = CellData {
+            column_index: 0,
+            content: "项目编号: P-001".to_string(),
+            style: CellStyle {
+                background_color: Some(RgbColor::new(198, 239, 206)),
+                ..CellStyle::default()
+            },
+            merge_info: Some(MergeInfo {
+                start_col: 0,
+                end_col: 10,
+                start_row: 2,
+                end_row: 2,
+            }),
+            comment: None,
+            raw_content: None,
+        };
+        let row = RowData::new(2, vec![cell]);
+
+        let json = row.to_debug_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let cell = &value["cells"][0];
+        assert_eq!(value["row_index"], 2);
+        assert_eq!(cell["content"], "项目编号: P-001");
+        assert_eq!(cell["style"]["background_color"]["g"], 239);
+        assert_eq!(cell["merge_info"]["end_col"], 10);
+        assert!(value.get("dominant_color").is_none());
+
+        let parsed: RowData = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.get_cell(0).unwrap().merge_info.as_ref().unwrap().column_span(), 11);
+    }
 }
@@ -73,6 +73,43 @@ pub enum AppView {
     History,
 }
 
+/// 批量处理的取消信号
+///
+/// 用户取消、失败过多自动中止和关闭失败后继续时的停止都通过它通知处理流程：
+/// 尚未开始的文件直接跳过，正在处理的文件不再写出。克隆共享同一个信号，
+/// 只记录第一次取消的原因。
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    reason: std::sync::Arc<std::sync::Mutex<Option<String>>>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 发出取消信号，第一次取消时返回 true
+    pub fn cancel(&self, reason: impl Into<String>) -> bool {
+        let mut current = self.reason.lock().unwrap_or_else(|e| e.into_inner());
+        if current.is_some() {
+            return false;
+        }
+        *current = Some(reason.into());
+        self.cancelled.store(true, std::sync::atomic::Ordering::SeqCst);
+        true
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// 取消原因，未取消时返回 None
+    pub fn reason(&self) -> Option<String> {
+        self.reason.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
 /// 处理状态（用于 egui 应用）
 #[derive(Debug)]
 pub enum ProcessingState {
@@ -80,11 +117,18 @@ pub enum ProcessingState {
     Idle,
     /// 处理中状态
     Processing {
-        /// 取消发送通道
-        cancel_tx: std::sync::mpsc::Sender<()>,
+        /// 取消信号（与批量处理选项共享）
+        cancel: CancelToken,
     },
     /// 完成状态
     Completed(crate::models::ProcessingResult),
     /// 错误状态
     Error(String),
 }
+
+impl ProcessingState {
+    /// 检查是否有任务正在处理
+    pub fn is_processing(&self) -> bool {
+        matches!(self, Self::Processing { .. })
+    }
+}
@@ -0,0 +1,175 @@
+// 工作表对比
+//
+// 供提取器的回归测试使用：把提取结果与预期结构逐行逐格比较，
+// 定位第一处不同的单元格，而不是只得到一个笼统的 “不相等”。
+use crate::models::{CellData, WorksheetData};
+use std::fmt;
+
+/// 两个工作表之间的第一处差异
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorksheetDiff {
+    /// 工作表名称不同
+    Name { expected: String, actual: String },
+    /// 第 `position` 行的行号不同，或一方缺少该行（None）
+    Row {
+        position: usize,
+        expected: Option<usize>,
+        actual: Option<usize>,
+    },
+    /// 单元格不同，或一方缺少该单元格（None）
+    Cell {
+        row_index: usize,
+        column_index: usize,
+        expected: Option<CellData>,
+        actual: Option<CellData>,
+    },
+}
+
+impl fmt::Display for WorksheetDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name { expected, actual } => {
+                write!(f, "工作表名称不同: 预期 \"{}\"，实际 \"{}\"", expected, actual)
+            }
+            Self::Row { position, expected, actual } => {
+                write!(f, "第 {} 条行数据不同: 预期行号 {:?}，实际行号 {:?}", position + 1, expected, actual)
+            }
+            Self::Cell {
+                row_index,
+                column_index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "第 {} 行第 {} 列不同: 预期 {:?}，实际 {:?}",
+                row_index + 1,
+                column_index + 1,
+                expected,
+                actual
+            ),
+        }
+    }
+}
+
+impl WorksheetData {
+    /// 与预期工作表比较，返回第一处差异，完全相同时返回 None
+    ///
+    /// 按行和单元格在列表中的位置依次比较，单元格的内容、样式、合并信息和批注都参与比较。
+    pub fn first_difference(&self, expected: &WorksheetData) -> Option<WorksheetDiff> {
+        if self.name != expected.name {
+            return Some(WorksheetDiff::Name {
+                expected: expected.name.clone(),
+                actual: self.name.clone(),
+            });
+        }
+
+        let row_count = self.rows.len().max(expected.rows.len());
+        for position in 0..row_count {
+            let (expected_row, actual_row) = match (expected.rows.get(position), self.rows.get(position)) {
+                (Some(e), Some(a)) if e.row_index == a.row_index => (e, a),
+                (e, a) => {
+                    return Some(WorksheetDiff::Row {
+                        position,
+                        expected: e.map(|row| row.row_index),
+                        actual: a.map(|row| row.row_index),
+                    })
+                }
+            };
+
+            let cell_count = actual_row.cells.len().max(expected_row.cells.len());
+            for index in 0..cell_count {
+                let expected_cell = expected_row.cells.get(index);
+                let actual_cell = actual_row.cells.get(index);
+                if expected_cell != actual_cell {
+                    let column_index = expected_cell.or(actual_cell).map_or(index, |cell| cell.column_index);
+                    return Some(WorksheetDiff::Cell {
+                        row_index: expected_row.row_index,
+                        column_index,
+                        expected: expected_cell.cloned(),
+                        actual: actual_cell.cloned(),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CellStyle, RgbColor, RowData};
+
+    fn cell(column_index: usize, content: &str) -> CellData {
+        CellData {
+            column_index,
+            content: content.to_string(),
+            style: CellStyle::default(),
+            merge_info: None,
+            comment: None,
+            raw_content: None,
+        }
+    }
+
+    fn worksheet() -> WorksheetData {
+        WorksheetData {
+            name: "Sheet1".to_string(),
+            rows: vec![
+                RowData::new(0, vec![cell(0, "柜号:1-1"), cell(1, "")]),
+                RowData::new(1, vec![cell(0, "电缆"), cell(1, "10")]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_identical_worksheets_equal() {
+        let expected = worksheet();
+        let actual = worksheet();
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual.first_difference(&expected), None);
+    }
+
+    #[test]
+    fn test_first_difference_reports_cell_coordinates() {
+        let expected = worksheet();
+
+        let mut actual = worksheet();
+        actual.rows[1].cells[1].content = "12".to_string();
+        actual.rows[1].cells[0].style.background_color = Some(RgbColor::new(255, 0, 0));
+        assert_ne!(actual, expected);
+
+        // 只报告第一处差异（第 1 行第 0 列的底色）
+        match actual.first_difference(&expected) {
+            Some(WorksheetDiff::Cell {
+                row_index,
+                column_index,
+                expected: Some(e),
+                actual: Some(a),
+            }) => {
+                assert_eq!((row_index, column_index), (1, 0));
+                assert_eq!(e.style.background_color, None);
+                assert_eq!(a.style.background_color, Some(RgbColor::new(255, 0, 0)));
+            }
+            other => panic!("unexpected diff: {:?}", other),
+        }
+
+        // 缺少单元格和行
+        let mut short = worksheet();
+        short.rows[1].cells.pop();
+        assert!(matches!(
+            short.first_difference(&expected),
+            Some(WorksheetDiff::Cell { row_index: 1, column_index: 1, actual: None, .. })
+        ));
+        short.rows.pop();
+        assert_eq!(
+            short.first_difference(&expected),
+            Some(WorksheetDiff::Row {
+                position: 1,
+                expected: Some(1),
+                actual: None
+            })
+        );
+    }
+}
@@ -0,0 +1,93 @@
+// 在阻塞线程中执行异步处理器
+//
+// DataEngine 在 spawn_blocking 线程中以同步闭包的形式调用处理器，
+// 而 `DataProcessor::process` 是异步方法。这里用调用方显式传入的运行时句柄驱动它，
+// 不在闭包内调用 `Handle::current()`，也不在 current_thread 运行时上嵌套 block_on。
+use crate::error::{AppError, Result};
+use crate::processor::DataProcessor;
+use polars::prelude::DataFrame;
+use std::sync::Arc;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// 获取当前运行时句柄，不在运行时中时返回错误而不是 panic
+pub fn current_runtime() -> Result<Handle> {
+    Handle::try_current().map_err(|e| AppError::processing_error(format!("没有可用的异步运行时: {}", e)))
+}
+
+/// 把异步处理器包装为 DataEngine 使用的同步处理函数
+///
+/// 返回的闭包只能在阻塞线程（如 spawn_blocking）中调用。多线程运行时直接用句柄驱动；
+/// current_thread 运行时的 IO 和定时器只能由其 `block_on` 所在线程推进，
+/// 因此改用一个临时的 current_thread 运行时执行，避免处理器等待定时器时卡死。
+pub fn blocking_process(
+    processor: Arc<dyn DataProcessor>,
+    runtime: Handle,
+) -> impl Fn(DataFrame) -> Result<DataFrame> + Send + Sync + Clone + 'static {
+    move |df| match runtime.runtime_flavor() {
+        RuntimeFlavor::MultiThread => runtime.block_on(processor.process(df)),
+        _ => tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| AppError::processing_error(format!("无法创建运行时: {}", e)))?
+            .block_on(processor.process(df)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use polars::prelude::*;
+
+    /// 处理时等待定时器的处理器
+    struct SleepyProcessor;
+
+    #[async_trait]
+    impl DataProcessor for SleepyProcessor {
+        fn id(&self) -> &str {
+            "sleepy"
+        }
+
+        fn name(&self) -> &str {
+            "延时处理器"
+        }
+
+        fn description(&self) -> &str {
+            "等待片刻后原样返回数据"
+        }
+
+        async fn process(&self, df: DataFrame) -> Result<DataFrame> {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            Ok(df)
+        }
+    }
+
+    fn sample() -> DataFrame {
+        DataFrame::new(vec![Series::new("数量".into(), vec![1i64, 2]).into_column()]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_blocking_process_on_current_thread_runtime() {
+        let process = blocking_process(Arc::new(SleepyProcessor), current_runtime().unwrap());
+        let df = tokio::task::spawn_blocking(move || process(sample()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(df.height(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_blocking_process_on_multi_thread_runtime() {
+        let process = blocking_process(Arc::new(SleepyProcessor), current_runtime().unwrap());
+        let df = tokio::task::spawn_blocking(move || process(sample()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(df.height(), 2);
+    }
+
+    #[test]
+    fn test_current_runtime_outside_runtime_is_error() {
+        assert!(current_runtime().is_err());
+    }
+}
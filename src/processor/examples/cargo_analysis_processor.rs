@@ -1,10 +1,12 @@
 // Cargo Analysis Processor - 货物分析表处理器
-use crate::engine::{ExcelExtractor, FileTypeProfile, RowTypeIdentifier};
+use crate::engine::data_engine::DataEngine;
+use crate::engine::{ExcelExtractor, ExtractOptions, FileTypeProfile, RowTypeIdentifier, TextNormalization};
 use crate::error::Result;
-use crate::models::RowType;
-use crate::processor::DataProcessor;
+use crate::models::{RowData, RowIdentificationResult, RowType, WorksheetData};
+use crate::processor::{DataProcessor, ProcessorCapabilities};
 use async_trait::async_trait;
 use polars::prelude::*;
+use std::collections::HashMap;
 use std::path::Path;
 
 /// 货物分析表处理器
@@ -14,11 +16,222 @@ use std::path::Path;
 /// - 自动识别项目编号行、柜号行、表头行、数据行和合计行
 /// - 根据识别结果分组处理不同类型的行
 /// - 提取和转换数据到标准格式
-pub struct CargoAnalysisProcessor;
+/// - 按项目/柜号分组重新计算数据行的数值合计
+pub struct CargoAnalysisProcessor {
+    /// 输出模式
+    mode: CargoAnalysisMode,
+    /// 用户调整后的行类型识别顺序（规则名称列表）
+    row_type_order: Vec<String>,
+    /// 读取单元格时的文本规范化设置
+    normalization: TextNormalization,
+    /// 只输出这些类型的行，为空时输出汇总
+    kept_row_types: Vec<RowType>,
+    /// 识别配置（内置配置名称或配置文件路径），为 None 时使用内置的货物分析表配置
+    profile_spec: Option<String>,
+}
+
+/// 货物分析表处理器的输出模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CargoAnalysisMode {
+    /// 输出项目编号、柜号数量、数据行数量的汇总
+    Summary,
+    /// 按项目/柜号分组，对指定数值列求和与平均（重新计算小计）
+    GroupTotals {
+        /// 数值列索引 (从0开始)
+        value_column: usize,
+    },
+}
+
+/// 单个项目/柜号分组的数值合计
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupTotal {
+    /// 项目编号
+    pub project_number: String,
+    /// 柜号
+    pub cabinet_number: String,
+    /// 参与计算的数值单元格数量
+    pub count: usize,
+    /// 合计
+    pub sum: f64,
+}
+
+impl GroupTotal {
+    /// 平均值（没有数值时为0）
+    pub fn average(&self) -> f64 {
+        if self.count > 0 {
+            self.sum / self.count as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 货物分析表中的已知字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CargoField {
+    /// 序号
+    SequenceNo,
+    /// 柜号
+    Cabinet,
+    /// 名称
+    Name,
+    /// 数量
+    Quantity,
+    /// 单价
+    UnitPrice,
+    /// 总价
+    TotalPrice,
+}
+
+impl CargoField {
+    pub const ALL: [CargoField; 6] = [
+        CargoField::SequenceNo,
+        CargoField::Cabinet,
+        CargoField::Name,
+        CargoField::Quantity,
+        CargoField::UnitPrice,
+        CargoField::TotalPrice,
+    ];
+
+    /// 表头中可能出现的名称
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            CargoField::SequenceNo => &["序号", "编号"],
+            CargoField::Cabinet => &["柜号"],
+            CargoField::Name => &["名称", "元件名称", "物料名称", "品名"],
+            CargoField::Quantity => &["数量"],
+            CargoField::UnitPrice => &["单价"],
+            CargoField::TotalPrice => &["总价", "金额"],
+        }
+    }
+
+    /// 未找到表头时使用的默认列索引 (从0开始)
+    pub fn default_column(&self) -> usize {
+        match self {
+            CargoField::SequenceNo => 0,
+            CargoField::Cabinet => 1,
+            CargoField::Name => 1,
+            CargoField::Quantity => 2,
+            CargoField::UnitPrice => 3,
+            CargoField::TotalPrice => 4,
+        }
+    }
+
+    /// 判断表头文本是否对应该字段（忽略空白和冒号）
+    fn matches_header(&self, header: &str) -> bool {
+        let normalized: String = header
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != ':' && *c != '：')
+            .collect();
+        self.aliases().iter().any(|alias| normalized == *alias)
+    }
+}
+
+/// 字段到列索引的映射
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMapping {
+    /// 从表头识别出的列
+    detected: HashMap<CargoField, usize>,
+}
+
+impl ColumnMapping {
+    /// 从表头行建立映射
+    pub fn from_header(header: &crate::models::RowData) -> Self {
+        let mut detected = HashMap::new();
+        for cell in &header.cells {
+            if let Some(field) = CargoField::ALL.iter().find(|f| f.matches_header(&cell.content)) {
+                detected.entry(*field).or_insert(cell.column_index);
+            }
+        }
+        Self { detected }
+    }
+
+    /// 字段所在列；表头中没有该字段时使用默认列
+    pub fn column(&self, field: CargoField) -> usize {
+        self.detected
+            .get(&field)
+            .copied()
+            .unwrap_or_else(|| field.default_column())
+    }
+
+    /// 表头中是否包含该字段
+    pub fn is_detected(&self, field: CargoField) -> bool {
+        self.detected.contains_key(&field)
+    }
+}
 
 impl CargoAnalysisProcessor {
+    /// 根据第一个表头行检测字段所在列，没有表头时使用默认列
+    pub fn detect_column_mapping(
+        worksheet_data: &WorksheetData,
+        results: &[RowIdentificationResult],
+    ) -> ColumnMapping {
+        results
+            .iter()
+            .find(|r| r.row_type == RowType::Header)
+            .and_then(|r| worksheet_data.get_row(r.row_index))
+            .map(ColumnMapping::from_header)
+            .unwrap_or_default()
+    }
+
     pub fn new() -> Self {
-        Self
+        Self {
+            mode: CargoAnalysisMode::Summary,
+            row_type_order: Vec::new(),
+            normalization: TextNormalization::default(),
+            kept_row_types: Vec::new(),
+            profile_spec: None,
+        }
+    }
+
+    /// 使用指定的识别配置（内置配置名称或 TOML 配置文件路径）
+    pub fn with_profile(mut self, spec: impl Into<String>) -> Self {
+        self.profile_spec = Some(spec.into());
+        self
+    }
+
+    /// 只输出指定类型的行（例如只导出数据行，去掉表头和合计），为空时输出汇总
+    ///
+    /// 分组合计模式不受影响。
+    pub fn with_row_type_filter(mut self, row_types: Vec<RowType>) -> Self {
+        self.kept_row_types = row_types;
+        self
+    }
+
+    /// 使用用户调整后的行类型识别顺序
+    pub fn with_row_type_order(mut self, order: Vec<String>) -> Self {
+        self.row_type_order = order;
+        self
+    }
+
+    /// 读取单元格时规范化文本（去除多余空白、全角转半角等）
+    pub fn with_text_normalization(mut self, normalization: TextNormalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// 构建识别配置（应用用户调整后的顺序）
+    ///
+    /// 指定的配置无法加载时记录警告并回退到内置配置。
+    pub fn profile(&self) -> FileTypeProfile {
+        let mut profile = FileTypeProfile::resolve_or_builtin(self.profile_spec.as_deref());
+        profile.apply_row_type_order(&self.row_type_order);
+        profile
+    }
+
+    /// 切换为分组合计模式
+    ///
+    /// # Arguments
+    ///
+    /// * `value_column` - 要汇总的数值列索引 (从0开始)
+    pub fn with_group_totals(mut self, value_column: usize) -> Self {
+        self.mode = CargoAnalysisMode::GroupTotals { value_column };
+        self
+    }
+
+    /// 获取当前输出模式
+    pub fn mode(&self) -> CargoAnalysisMode {
+        self.mode
     }
 
     /// 处理Excel文件并识别行类型
@@ -26,7 +239,11 @@ impl CargoAnalysisProcessor {
         tracing::info!("Processing cargo analysis file: {}", file_path.display());
 
         // 1. 使用ExcelExtractor读取工作表数据
-        let worksheet_data = ExcelExtractor::read_worksheet(file_path, 0)
+        let options = ExtractOptions {
+            normalization: self.normalization,
+            ..Default::default()
+        };
+        let worksheet_data = ExcelExtractor::read_worksheet_with_options(file_path, 0, &options)
             .map_err(|e| crate::error::AppError::processing_error(e.to_string()))?;
 
         tracing::info!(
@@ -36,8 +253,7 @@ impl CargoAnalysisProcessor {
         );
 
         // 2. 创建行类型识别器
-        let profile = FileTypeProfile::cargo_analysis();
-        let identifier = RowTypeIdentifier::new(profile);
+        let identifier = RowTypeIdentifier::new(self.profile());
 
         // 3. 识别所有行的类型
         let results = identifier.identify_all_rows(&worksheet_data);
@@ -45,12 +261,35 @@ impl CargoAnalysisProcessor {
         // 4. 获取统计信息
         let stats = identifier.get_statistics(&results);
         tracing::info!(
-            "Row identification complete: {} rows, {:.1}% success rate",
+            "Row identification complete: {} rows, {} success rate",
             stats.total_rows,
-            stats.success_rate * 100.0
+            crate::models::format_percentage(stats.success_rate * 100.0)
         );
         tracing::debug!("Row type distribution: {:?}", stats.row_type_counts);
 
+        let rule_counts = identifier.rule_match_counts(&results);
+        tracing::debug!("Rule match counts: {:?}", rule_counts);
+        for (rule, count) in &rule_counts {
+            if *count == 0 {
+                tracing::warn!("Rule '{}' did not match any row", rule);
+            }
+        }
+
+        let mapping = Self::detect_column_mapping(&worksheet_data, &results);
+        tracing::debug!("Column mapping: {:?}", mapping);
+
+        if let CargoAnalysisMode::GroupTotals { value_column } = self.mode {
+            let totals = self.compute_group_totals(&worksheet_data, &results, value_column);
+            tracing::info!("Computed totals for {} groups", totals.len());
+            return self.create_group_totals_dataframe(&totals);
+        }
+
+        if !self.kept_row_types.is_empty() {
+            let rows = Self::filter_rows(&worksheet_data, &results, &self.kept_row_types);
+            tracing::info!("Kept {} of {} rows by row type", rows.len(), results.len());
+            return Self::create_rows_dataframe(&rows);
+        }
+
         // 5. 根据识别结果分组处理
         let mut project_numbers = Vec::new();
         let mut cabinet_numbers = Vec::new();
@@ -69,7 +308,7 @@ impl CargoAnalysisProcessor {
                 }
                 RowType::CabinetNumber => {
                     // 提取柜号信息
-                    if let Some(cell) = row.get_cell(1) {
+                    if let Some(cell) = row.get_cell(CargoField::Cabinet.default_column()) {
                         cabinet_numbers.push(cell.content.clone());
                         tracing::debug!("Found cabinet number: {}", cell.content);
                     }
@@ -111,6 +350,165 @@ impl CargoAnalysisProcessor {
         Ok(df)
     }
 
+    /// 按项目/柜号分组计算数据行指定列的数值合计
+    ///
+    /// 数据行归属于其之前最近出现的项目编号行与柜号行，
+    /// 无法解析为数字的单元格会被跳过。
+    pub fn compute_group_totals(
+        &self,
+        worksheet_data: &WorksheetData,
+        results: &[RowIdentificationResult],
+        value_column: usize,
+    ) -> Vec<GroupTotal> {
+        let mut totals: Vec<GroupTotal> = Vec::new();
+        let mut project_number = String::new();
+        let mut cabinet_number = String::new();
+        let mut mapping = ColumnMapping::default();
+
+        for result in results {
+            let row = match worksheet_data.get_row(result.row_index) {
+                Some(row) => row,
+                None => continue,
+            };
+
+            match result.row_type {
+                RowType::ProjectNumber => {
+                    project_number = row
+                        .first_non_empty_cell()
+                        .map(|c| c.content.trim().to_string())
+                        .unwrap_or_default();
+                    cabinet_number.clear();
+                }
+                RowType::CabinetNumber => {
+                    cabinet_number = row
+                        .get_cell(CargoField::Cabinet.default_column())
+                        .map(|c| c.content.trim().to_string())
+                        .unwrap_or_default();
+                }
+                RowType::Header => {
+                    mapping = ColumnMapping::from_header(row);
+                }
+                RowType::Data => {
+                    let value = row
+                        .get_cell(value_column)
+                        .and_then(|c| Self::parse_number(&c.content));
+
+                    // 表头中有柜号列时（平铺表格），以数据行自身的柜号为准
+                    if mapping.is_detected(CargoField::Cabinet) {
+                        if let Some(cell) = row.get_cell(mapping.column(CargoField::Cabinet)) {
+                            if !cell.is_empty() {
+                                cabinet_number = cell.content.trim().to_string();
+                            }
+                        }
+                    }
+
+                    let group = match totals.iter_mut().find(|g| {
+                        g.project_number == project_number && g.cabinet_number == cabinet_number
+                    }) {
+                        Some(group) => group,
+                        None => {
+                            totals.push(GroupTotal {
+                                project_number: project_number.clone(),
+                                cabinet_number: cabinet_number.clone(),
+                                count: 0,
+                                sum: 0.0,
+                            });
+                            totals.last_mut().unwrap()
+                        }
+                    };
+
+                    if let Some(value) = value {
+                        group.count += 1;
+                        group.sum += value;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        totals
+    }
+
+    /// 将单元格文本解析为数字（支持千分位、全角数字和尾部单位）
+    fn parse_number(content: &str) -> Option<f64> {
+        crate::engine::parse_localized_number(content)
+    }
+
+    /// 创建分组合计DataFrame
+    fn create_group_totals_dataframe(&self, totals: &[GroupTotal]) -> Result<DataFrame> {
+        let columns = vec![
+            Series::new(
+                "项目编号".into(),
+                totals.iter().map(|g| g.project_number.clone()).collect::<Vec<_>>(),
+            )
+            .into_column(),
+            Series::new(
+                "柜号".into(),
+                totals.iter().map(|g| g.cabinet_number.clone()).collect::<Vec<_>>(),
+            )
+            .into_column(),
+            Series::new(
+                "数据行数量".into(),
+                totals.iter().map(|g| g.count as i32).collect::<Vec<_>>(),
+            )
+            .into_column(),
+            Series::new("合计".into(), totals.iter().map(|g| g.sum).collect::<Vec<_>>()).into_column(),
+            Series::new(
+                "平均值".into(),
+                totals.iter().map(|g| g.average()).collect::<Vec<_>>(),
+            )
+            .into_column(),
+        ];
+
+        DataFrame::new(columns)
+            .map_err(|e| crate::error::AppError::polars_error(e.to_string()))
+    }
+
+    /// 按识别结果筛选出类型在 `kept` 中的行，保持原有顺序
+    pub fn filter_rows<'a>(
+        worksheet_data: &'a WorksheetData,
+        results: &'a [RowIdentificationResult],
+        kept: &[RowType],
+    ) -> Vec<(&'a RowData, &'a RowType)> {
+        results
+            .iter()
+            .filter(|result| kept.contains(&result.row_type))
+            .filter_map(|result| {
+                worksheet_data
+                    .get_row(result.row_index)
+                    .map(|row| (row, &result.row_type))
+            })
+            .collect()
+    }
+
+    /// 把筛选出的行转换为DataFrame：行号（从1开始）、行类型和各列内容
+    fn create_rows_dataframe(rows: &[(&RowData, &RowType)]) -> Result<DataFrame> {
+        let width = rows.iter().map(|(row, _)| row.cells.len()).max().unwrap_or(0);
+
+        let mut columns = vec![
+            Series::new(
+                "行号".into(),
+                rows.iter().map(|(row, _)| row.row_index as u32 + 1).collect::<Vec<_>>(),
+            )
+            .into_column(),
+            Series::new(
+                "行类型".into(),
+                rows.iter().map(|(_, row_type)| row_type.display_name()).collect::<Vec<_>>(),
+            )
+            .into_column(),
+        ];
+        for col in 0..width {
+            let values: Vec<&str> = rows
+                .iter()
+                .map(|(row, _)| row.get_cell(col).map_or("", |cell| cell.content.as_str()))
+                .collect();
+            columns.push(Series::new(format!("列{}", col + 1).into(), values).into_column());
+        }
+
+        DataFrame::new(columns)
+            .map_err(|e| crate::error::AppError::polars_error(e.to_string()))
+    }
+
     /// 创建汇总DataFrame
     fn create_summary_dataframe(
         &self,
@@ -183,15 +581,7 @@ impl DataProcessor for CargoAnalysisProcessor {
 
     fn validate_input(&self, file: &Path) -> Result<()> {
         // 验证文件是否为Excel文件
-        if let Some(ext) = file.extension() {
-            if ext.eq_ignore_ascii_case("xlsx") || ext.eq_ignore_ascii_case("xls") {
-                return Ok(());
-            }
-        }
-
-        Err(crate::error::AppError::processing_error(
-            "文件必须是Excel格式 (.xlsx 或 .xls)",
-        ))
+        DataEngine::validate_format(file)
     }
 
     fn icon(&self) -> Option<&str> {
@@ -201,6 +591,14 @@ impl DataProcessor for CargoAnalysisProcessor {
     fn version(&self) -> &str {
         "1.0.0"
     }
+
+    fn capabilities(&self) -> ProcessorCapabilities {
+        // 每次只识别一个工作表，多 Sheet 文件需要明确选择
+        ProcessorCapabilities {
+            requires_sheet_selection: true,
+            ..Default::default()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -215,6 +613,23 @@ mod tests {
         assert_eq!(processor.icon(), Some("📦"));
     }
 
+    #[test]
+    fn test_configured_profile_path_is_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cargo.toml");
+        std::fs::write(&path, "base = \"cargo_analysis\"\nname = \"现场货物表\"\ndefault_row_type = \"数据行\"\n").unwrap();
+
+        let profile = CargoAnalysisProcessor::new().with_profile(path.to_str().unwrap()).profile();
+        assert_eq!(profile.name, "现场货物表");
+        assert_eq!(profile.default_row_type, RowType::Data);
+
+        // 无法加载时回退到内置配置
+        let fallback = CargoAnalysisProcessor::new()
+            .with_profile(dir.path().join("missing.toml").to_str().unwrap())
+            .profile();
+        assert_eq!(fallback.name, FileTypeProfile::cargo_analysis().name);
+    }
+
     #[test]
     fn test_validate_input() {
         let processor = CargoAnalysisProcessor::new();
@@ -226,6 +641,12 @@ mod tests {
         // 无效的文件类型
         let invalid_path = Path::new("test.txt");
         assert!(processor.validate_input(invalid_path).is_err());
+
+        // 绕过对话框过滤的格式应返回 UnsupportedFormat
+        for path in ["test.csv", "test.numbers"] {
+            let err = processor.validate_input(Path::new(path)).unwrap_err();
+            assert!(err.is_unsupported_format());
+        }
     }
 
     #[test]
@@ -246,4 +667,154 @@ mod tests {
         assert_eq!(df.height(), 1);
         assert_eq!(df.width(), 3);
     }
+
+    fn text_row(row_index: usize, values: &[&str]) -> crate::models::RowData {
+        crate::models::RowData::new(
+            row_index,
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| crate::models::CellData {
+                    column_index: i,
+                    content: v.to_string(),
+                    style: crate::models::CellStyle::default(),
+                    merge_info: None,
+                    comment: None,
+                    raw_content: None,
+                })
+                .collect(),
+        )
+    }
+
+    fn result(row_index: usize, row_type: RowType) -> RowIdentificationResult {
+        RowIdentificationResult {
+            row_index,
+            row_type,
+            matched_rule: "test".to_string(),
+            confidence: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_filter_keeps_only_data_rows() {
+        let worksheet = WorksheetData {
+            name: "Sheet1".to_string(),
+            rows: vec![
+                text_row(0, &["P-001"]),
+                text_row(1, &["序号", "名称", "数量"]),
+                text_row(2, &["1", "电缆", "10"]),
+                text_row(3, &["2", "开关", "5"]),
+                text_row(4, &["小计", "", "15"]),
+                text_row(5, &["备注"]),
+            ],
+        };
+        let results = vec![
+            result(0, RowType::ProjectNumber),
+            result(1, RowType::Header),
+            result(2, RowType::Data),
+            result(3, RowType::Data),
+            result(4, RowType::SubTotal),
+            result(5, RowType::Unknown),
+        ];
+
+        let rows = CargoAnalysisProcessor::filter_rows(&worksheet, &results, &[RowType::Data]);
+        let indices: Vec<usize> = rows.iter().map(|(row, _)| row.row_index).collect();
+        assert_eq!(indices, vec![2, 3]);
+
+        let df = CargoAnalysisProcessor::create_rows_dataframe(&rows).unwrap();
+        assert_eq!(df.height(), 2);
+        assert_eq!(df.width(), 5);
+        let names: Vec<Option<&str>> = df.column("列2").unwrap().str().unwrap().into_iter().collect();
+        assert_eq!(names, vec![Some("电缆"), Some("开关")]);
+
+        // 同时保留多种类型
+        let totals = CargoAnalysisProcessor::filter_rows(&worksheet, &results, &[RowType::Data, RowType::SubTotal]);
+        assert_eq!(totals.len(), 3);
+    }
+
+    #[test]
+    fn test_compute_group_totals() {
+        let processor = CargoAnalysisProcessor::new().with_group_totals(2);
+        assert_eq!(processor.mode(), CargoAnalysisMode::GroupTotals { value_column: 2 });
+
+        let worksheet = WorksheetData {
+            name: "屏柜分项表".to_string(),
+            rows: vec![
+                text_row(0, &["G00E-500009085-00011"]),
+                text_row(1, &["1-1", "柜号：直流充电柜"]),
+                text_row(2, &["序号", "元件名称", "总价"]),
+                text_row(3, &["1", "断路器", "1,000.50"]),
+                text_row(4, &["2", "接触器", "499.50"]),
+                text_row(5, &["小计: 1500"]),
+                text_row(6, &["1-2", "柜号：交流柜"]),
+                text_row(7, &["1", "电表", "300"]),
+                text_row(8, &["2", "备注", "-"]),
+            ],
+        };
+        let results = vec![
+            result(0, RowType::ProjectNumber),
+            result(1, RowType::CabinetNumber),
+            result(2, RowType::Header),
+            result(3, RowType::Data),
+            result(4, RowType::Data),
+            result(5, RowType::SubTotal),
+            result(6, RowType::CabinetNumber),
+            result(7, RowType::Data),
+            result(8, RowType::Data),
+        ];
+
+        let totals = processor.compute_group_totals(&worksheet, &results, 2);
+
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0].project_number, "G00E-500009085-00011");
+        assert_eq!(totals[0].cabinet_number, "柜号：直流充电柜");
+        assert_eq!(totals[0].count, 2);
+        assert_eq!(totals[0].sum, 1500.0);
+        assert_eq!(totals[0].average(), 750.0);
+
+        // 无法解析的单元格被跳过
+        assert_eq!(totals[1].cabinet_number, "柜号：交流柜");
+        assert_eq!(totals[1].count, 1);
+        assert_eq!(totals[1].sum, 300.0);
+
+        let df = processor.create_group_totals_dataframe(&totals).unwrap();
+        assert_eq!(df.height(), 2);
+        assert_eq!(df.width(), 5);
+    }
+
+    #[test]
+    fn test_detect_column_mapping_reordered_headers() {
+        let worksheet = WorksheetData {
+            name: "Sheet1".to_string(),
+            rows: vec![
+                text_row(0, &["数量", "名称：", " 序号 ", "柜号"]),
+                text_row(1, &["2", "断路器", "1", "A1"]),
+            ],
+        };
+        let results = vec![result(0, RowType::Header), result(1, RowType::Data)];
+
+        let mapping = CargoAnalysisProcessor::detect_column_mapping(&worksheet, &results);
+
+        assert_eq!(mapping.column(CargoField::Quantity), 0);
+        assert_eq!(mapping.column(CargoField::Name), 1);
+        assert_eq!(mapping.column(CargoField::SequenceNo), 2);
+        assert_eq!(mapping.column(CargoField::Cabinet), 3);
+        // 表头中没有的字段使用默认列
+        assert!(!mapping.is_detected(CargoField::TotalPrice));
+        assert_eq!(mapping.column(CargoField::TotalPrice), CargoField::TotalPrice.default_column());
+    }
+
+    #[test]
+    fn test_detect_column_mapping_without_header() {
+        let worksheet = WorksheetData {
+            name: "Sheet1".to_string(),
+            rows: vec![text_row(0, &["1", "断路器"])],
+        };
+        let results = vec![result(0, RowType::Data)];
+
+        let mapping = CargoAnalysisProcessor::detect_column_mapping(&worksheet, &results);
+
+        assert_eq!(mapping, ColumnMapping::default());
+        assert_eq!(mapping.column(CargoField::SequenceNo), 0);
+    }
 }
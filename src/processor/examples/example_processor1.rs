@@ -38,12 +38,11 @@ impl DataProcessor for DataCleaningProcessor {
     async fn process(&self, df: DataFrame) -> Result<DataFrame> {
         tracing::info!("开始数据清洗处理");
 
-        // 删除重复行
-        let df = df
-            .unique::<Vec<String>, String>(None, UniqueKeepStrategy::First, None)
-            .map_err(|e| crate::error::AppError::polars_error(e.to_string()))?;
+        // 删除重复行（保留首次出现的行及原有顺序）
+        let duplicates = crate::engine::find_duplicate_rows_df(&df, &[])?;
+        let df = crate::engine::remove_duplicate_rows(&df, &duplicates)?;
 
-        tracing::info!("数据清洗完成，剩余 {} 行", df.height());
+        tracing::info!("数据清洗完成，删除 {} 个重复行，剩余 {} 行", duplicates.count(), df.height());
 
         Ok(df)
     }
@@ -1,5 +1,6 @@
+use crate::engine::data_engine::DataEngine;
 use crate::error::Result;
-use crate::processor::trait_def::DataProcessor;
+use crate::processor::trait_def::{DataProcessor, ProcessorCapabilities};
 use async_trait::async_trait;
 use polars::prelude::*;
 use std::path::Path;
@@ -9,12 +10,56 @@ use umya_spreadsheet as umya;
 ///
 /// 功能：
 /// - 分析 Excel 文件中单个 Sheet 的表格结构
-/// - 直接输出完整的表格内容到日志
-pub struct ExcelStructureAnalyzer;
+/// - 输出表格内容到日志（最多 `max_rows` 行）
+/// - 仅颜色模式下跳过表格内容输出，只统计背景色分布
+/// - 快速预览模式下只输出前几行并报告总行数，跳过颜色分析
+pub struct ExcelStructureAnalyzer {
+    /// 输出到日志的最大行数，超出部分只记录省略行数
+    max_rows: usize,
+    /// 只分析颜色，不输出表格内容
+    colors_only: bool,
+    /// 快速预览的行数，设置后只输出前 N 行且不做颜色分析
+    preview_rows: Option<usize>,
+}
+
+/// 默认最多输出的行数
+pub const DEFAULT_DUMP_MAX_ROWS: usize = 500;
+
+/// 输出表格内容时，每隔多少行记录一次进度
+const DUMP_PROGRESS_INTERVAL: usize = 100;
 
 impl ExcelStructureAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self {
+            max_rows: DEFAULT_DUMP_MAX_ROWS,
+            colors_only: false,
+            preview_rows: None,
+        }
+    }
+
+    /// 快速预览：只输出前 `rows` 行并报告总行数，跳过颜色分析等全表扫描
+    ///
+    /// 优先于 [`with_max_rows`](Self::with_max_rows) 和仅颜色模式；None 或 0 表示关闭。
+    pub fn with_preview_rows(mut self, rows: Option<usize>) -> Self {
+        self.preview_rows = rows.filter(|&n| n > 0);
+        self
+    }
+
+    /// 本次实际输出的最大行数
+    fn dump_limit(&self) -> usize {
+        self.preview_rows.unwrap_or(self.max_rows)
+    }
+
+    /// 只分析颜色分布（跳过逐行输出，大表格上明显更快）
+    pub fn with_colors_only(mut self, colors_only: bool) -> Self {
+        self.colors_only = colors_only;
+        self
+    }
+
+    /// 设置输出到日志的最大行数（至少 1 行）
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows.max(1);
+        self
     }
 
     /// 分析 Excel 文件结构并输出到日志
@@ -25,6 +70,8 @@ impl ExcelStructureAnalyzer {
     pub fn analyze_excel_structure(&self, file_path: &Path, selected_sheet: Option<&str>, analyze_colors: bool) -> Result<()> {
         crate::log_info!("开始分析 Excel 文件: {}", file_path.display());
 
+        DataEngine::validate_format(file_path)?;
+
         // 打开 Excel 文件（umya）
         let book = umya::reader::xlsx::read(file_path)
             .map_err(|e| crate::error::AppError::excel_error(format!("无法打开 Excel 文件: {}", e)))?;
@@ -47,9 +94,20 @@ impl ExcelStructureAnalyzer {
             .unwrap_or_else(|| names[0].clone());
 
         crate::log_info!("将分析 Sheet: {}", sheet_to_analyze);
-        self.analyze_sheet(&book, &sheet_to_analyze)?;
+        if let Some(rows) = self.preview_rows {
+            crate::log_info!("快速预览模式：只输出前 {} 行，跳过颜色分析", rows);
+            self.analyze_sheet(&book, &sheet_to_analyze)?;
+            crate::log_info!("Excel 文件快速预览完成");
+            return Ok(());
+        }
 
-        if analyze_colors {
+        if self.colors_only {
+            crate::log_info!("仅颜色模式：跳过表格内容输出");
+        } else {
+            self.analyze_sheet(&book, &sheet_to_analyze)?;
+        }
+
+        if analyze_colors || self.colors_only {
             match self.analyze_sheet_colors_ws(&book, &sheet_to_analyze) {
                 Ok(_) => {}
                 Err(e) => {
@@ -78,34 +136,54 @@ impl ExcelStructureAnalyzer {
         let (height, width) = Self::worksheet_size(ws);
         crate::log_info!("Sheet 尺寸: {} 行 × {} 列", height, width);
 
-        // 直接输出完整表格内容
+        // 输出表格内容（受 max_rows 或预览行数限制）
         self.dump_full_sheet(ws, height, width)?;
 
         crate::log_info!("=== Sheet {} 分析完成 ===", sheet_name);
         Ok(())
     }
 
-    /// 输出完整表格内容（逐行逐列）
+    /// 输出表格内容（逐行逐列，最多 `max_rows` 行）
     fn dump_full_sheet(&self, ws: &umya::Worksheet, height: u32, width: u32) -> Result<()> {
         if height == 0 || width == 0 {
             crate::log_info!("工作表为空");
             return Ok(());
         }
 
-        crate::log_info!("表格完整内容 ({} 行 × {} 列):", height, width);
+        crate::log_info!("表格内容 ({} 行 × {} 列):", height, width);
 
-        for r in 1..=height {
-            let mut row_vals: Vec<String> = Vec::with_capacity(width as usize);
-            for c in 1..=width {
-                let v = ws.get_value((c, r));
-                row_vals.push(v);
+        let (lines, omitted) = self.format_rows(ws, height, width);
+        let total = lines.len();
+        for (i, line) in lines.iter().enumerate() {
+            crate::log_info!("{}", line);
+            let done = i + 1;
+            if done % DUMP_PROGRESS_INTERVAL == 0 && done < total {
+                crate::log_debug!("已输出 {}/{} 行", done, total);
             }
-            crate::log_info!("第{}行: {:?}", r, row_vals);
+        }
+
+        if omitted > 0 {
+            crate::log_info!("... (还有 {} 行未输出，共 {} 行)", omitted, height);
         }
 
         Ok(())
     }
 
+    /// 格式化前 `max_rows`（预览模式下为预览行数）行的内容，返回 (各行文本, 省略的行数)
+    fn format_rows(&self, ws: &umya::Worksheet, height: u32, width: u32) -> (Vec<String>, usize) {
+        let shown = (height as usize).min(self.dump_limit());
+        let omitted = height as usize - shown;
+
+        let lines = (1..=shown as u32)
+            .map(|r| {
+                let row_vals: Vec<String> = (1..=width).map(|c| ws.get_value((c, r))).collect();
+                format!("第{}行: {:?}", r, row_vals)
+            })
+            .collect();
+
+        (lines, omitted)
+    }
+
     fn worksheet_size(ws: &umya::Worksheet) -> (u32, u32) {
         let rows = ws.get_highest_row();
         let cols = ws.get_highest_column();
@@ -131,6 +209,11 @@ impl DataProcessor for ExcelStructureAnalyzer {
         Some("🔍")
     }
 
+    fn capabilities(&self) -> ProcessorCapabilities {
+        // 只分析单个文件，结果直接输出到日志面板
+        ProcessorCapabilities::log_only()
+    }
+
     async fn process(&self, _df: DataFrame) -> Result<DataFrame> {
         // 这个处理器主要处理文件而不是 DataFrame
         // 实际处理逻辑在 analyze_excel_structure 方法中
@@ -144,15 +227,7 @@ impl DataProcessor for ExcelStructureAnalyzer {
             return Err(crate::error::AppError::processing_error("文件不存在".to_string()));
         }
 
-        if let Some(extension) = file.extension() {
-            if extension != "xlsx" && extension != "xls" {
-                return Err(crate::error::AppError::processing_error("不是有效的 Excel 文件".to_string()));
-            }
-        } else {
-            return Err(crate::error::AppError::processing_error("文件没有扩展名".to_string()));
-        }
-
-        Ok(())
+        DataEngine::validate_format(file)
     }
 }
 
@@ -242,7 +317,7 @@ impl ExcelStructureAnalyzer {
             let mut items: Vec<(String, usize)> = color_counts.into_iter().collect();
             items.sort_by(|a, b| b.1.cmp(&a.1));
             for (i, (col, cnt)) in items.into_iter().take(8).enumerate() {
-                crate::log_info!("  {}. #{}: {} 个单元格", i + 1, col, cnt);
+                crate::log_info!("  {}. #{} ({}): {} 个单元格", i + 1, col, Self::color_name(&col), cnt);
             }
         }
 
@@ -255,14 +330,109 @@ impl ExcelStructureAnalyzer {
         if !dominants.is_empty() {
             dominants.sort_by_key(|(r, _)| *r);
             crate::log_info!("行主色（>50% 单元格同色）：");
-            for (r, hex) in dominants { crate::log_info!("  第{}行: #{}", r, hex); }
+            for (r, hex) in dominants { crate::log_info!("  第{}行: #{} ({})", r, hex, Self::color_name(&hex)); }
         }
 
         Ok(())
     }
 
+    fn color_name(hex: &str) -> &'static str {
+        crate::models::RgbColor::from_hex(hex)
+            .map(|c| c.nearest_name())
+            .unwrap_or("未命名")
+    }
+
     fn argb_to_rgb_hex(argb: &str) -> String {
         let s = argb.trim_start_matches('#');
         if s.len() == 8 { s[2..].to_uppercase() } else { s.to_uppercase() }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sheet_with_rows(rows: u32) -> umya::Spreadsheet {
+        let mut book = umya::new_file();
+        let ws = book.get_sheet_mut(&0).unwrap();
+        for r in 1..=rows {
+            ws.get_cell_mut((1, r)).set_value(format!("行{}", r));
+        }
+        book
+    }
+
+    #[test]
+    fn test_dump_respects_max_rows() {
+        let book = sheet_with_rows(10);
+        let ws = book.get_sheet(&0).unwrap();
+        let analyzer = ExcelStructureAnalyzer::new().with_max_rows(3);
+
+        let (lines, omitted) = analyzer.format_rows(ws, 10, 1);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(omitted, 7);
+        assert!(lines[2].contains("行3"));
+    }
+
+    #[test]
+    fn test_dump_below_cap_outputs_all_rows() {
+        let book = sheet_with_rows(4);
+        let ws = book.get_sheet(&0).unwrap();
+        let analyzer = ExcelStructureAnalyzer::new();
+
+        let (lines, omitted) = analyzer.format_rows(ws, 4, 1);
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(omitted, 0);
+    }
+
+    #[test]
+    fn test_preview_dumps_first_rows_and_reports_total() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("preview.xlsx");
+
+        let mut book = umya::new_file();
+        let ws = book.get_sheet_mut(&0).unwrap();
+        for r in 1..=40u32 {
+            ws.get_cell_mut((1, r)).set_value(format!("预览测试-{}", r));
+        }
+        ws.get_style_mut("A1").set_background_color("FF654321");
+        umya::writer::xlsx::write(&book, &path).unwrap();
+
+        let analyzer = ExcelStructureAnalyzer::new().with_preview_rows(Some(5));
+        let ws = book.get_sheet(&0).unwrap();
+        let (lines, omitted) = analyzer.format_rows(ws, 40, 1);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(omitted, 35);
+
+        analyzer.analyze_excel_structure(&path, None, true).unwrap();
+
+        let entries = crate::logger::LOGGER.get_entries();
+        assert!(entries.iter().any(|e| e.message.contains("\"预览测试-5\"")));
+        assert!(!entries.iter().any(|e| e.message.contains("\"预览测试-6\"")));
+        assert!(entries.iter().any(|e| e.message.contains("还有 35 行未输出，共 40 行")));
+        // 预览模式不做颜色分析
+        assert!(!entries.iter().any(|e| e.message.contains("#654321")));
+    }
+
+    #[test]
+    fn test_colors_only_skips_row_dump() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("colors.xlsx");
+
+        let mut book = umya::new_file();
+        let ws = book.get_sheet_mut(&0).unwrap();
+        ws.get_cell_mut("A1").set_value("仅颜色模式-单元格内容");
+        ws.get_style_mut("A1").set_background_color("FF123456");
+        umya::writer::xlsx::write(&book, &path).unwrap();
+
+        ExcelStructureAnalyzer::new()
+            .with_colors_only(true)
+            .analyze_excel_structure(&path, None, false)
+            .unwrap();
+
+        let entries = crate::logger::LOGGER.get_entries();
+        assert!(entries.iter().any(|e| e.message.contains("#123456")));
+        assert!(!entries.iter().any(|e| e.message.contains("仅颜色模式-单元格内容")));
+    }
+}
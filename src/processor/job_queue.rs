@@ -0,0 +1,430 @@
+// 处理任务队列
+//
+// 正在处理时新任务进入先进先出队列，当前任务完成后自动开始下一个，
+// 每个完成的任务各自生成一条历史记录。主界面的任务（如对比运行）进行中时队列暂停，
+// 不与其同时执行；队列任务的进度单独保存，不与主任务的进度混在一起。
+use crate::engine::data_engine::{BatchOptions, DataEngine};
+use crate::error::{AppError, Result};
+use crate::history::HistoryEntry;
+use crate::models::{ProcessingProgress, ProcessingResult};
+use crate::processor::{blocking_process, current_runtime, DataProcessor};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::Arc;
+use tracing::Instrument;
+
+/// 生成短运行 ID（UUID 的前 8 位十六进制字符）
+pub fn new_run_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..8].to_string()
+}
+
+/// 队列中的一个处理任务
+#[derive(Clone)]
+pub struct QueuedJob {
+    pub id: u64,
+    /// 运行 ID，出现在该任务的每条日志和生成的历史记录中
+    pub run_id: String,
+    pub processor: Arc<dyn DataProcessor>,
+    pub input_dir: PathBuf,
+    pub output_dir: PathBuf,
+    pub options: BatchOptions,
+}
+
+impl QueuedJob {
+    /// 界面显示的任务说明
+    pub fn label(&self) -> String {
+        let input = self
+            .input_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.input_dir.display().to_string());
+        format!("[{}] {} — {}", self.run_id, self.processor.name(), input)
+    }
+
+    fn history_entry(&self, result: ProcessingResult) -> HistoryEntry {
+        HistoryEntry::new(
+            self.processor.id().to_string(),
+            self.processor.name().to_string(),
+            self.input_dir.clone(),
+            self.output_dir.clone(),
+            result,
+        )
+        .with_run_id(self.run_id.clone())
+    }
+}
+
+/// 后台任务发回的事件
+enum JobEvent {
+    Progress(ProcessingProgress),
+    Finished(Result<ProcessingResult>),
+}
+
+struct RunningJob {
+    job: QueuedJob,
+    receiver: mpsc::Receiver<JobEvent>,
+    /// 该任务收到的最新进度
+    progress: ProcessingProgress,
+}
+
+/// 一次 [`JobQueue::poll`] 的结果
+#[derive(Default)]
+pub struct QueuePoll {
+    /// 收到的最新进度
+    pub progress: Option<ProcessingProgress>,
+    /// 本次完成的任务的历史记录（按完成顺序）
+    pub finished: Vec<HistoryEntry>,
+    /// 本次失败的任务及错误
+    pub failed: Vec<(String, AppError)>,
+}
+
+/// 先进先出的处理任务队列，任务依次在后台线程中执行
+#[derive(Default)]
+pub struct JobQueue {
+    pending: VecDeque<QueuedJob>,
+    running: Option<RunningJob>,
+    next_id: u64,
+    /// 暂停开始新任务（正在执行的任务不受影响）
+    held: bool,
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 加入队列，返回任务 ID；空闲且未暂停时立即开始执行
+    pub fn enqueue(
+        &mut self,
+        processor: Arc<dyn DataProcessor>,
+        input_dir: &Path,
+        output_dir: &Path,
+        options: BatchOptions,
+    ) -> u64 {
+        self.next_id += 1;
+        let job = QueuedJob {
+            id: self.next_id,
+            run_id: new_run_id(),
+            processor,
+            input_dir: input_dir.to_path_buf(),
+            output_dir: output_dir.to_path_buf(),
+            options,
+        };
+        crate::log_info!("任务已加入队列: {}", job.label());
+        self.pending.push_back(job);
+        self.start_next();
+        self.next_id
+    }
+
+    /// 取消尚未开始的任务，任务不存在或已开始时返回 false
+    pub fn cancel(&mut self, id: u64) -> bool {
+        match self.pending.iter().position(|job| job.id == id) {
+            Some(index) => {
+                if let Some(job) = self.pending.remove(index) {
+                    crate::log_info!("已取消排队任务: {}", job.label());
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 等待中的任务（按执行顺序）
+    pub fn pending(&self) -> impl Iterator<Item = &QueuedJob> {
+        self.pending.iter()
+    }
+
+    /// 正在执行的任务
+    pub fn running(&self) -> Option<&QueuedJob> {
+        self.running.as_ref().map(|r| &r.job)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// 正在执行的任务的最新进度
+    pub fn progress(&self) -> Option<&ProcessingProgress> {
+        self.running.as_ref().map(|r| &r.progress)
+    }
+
+    /// 主任务进行中时暂停队列，结束后继续执行等待中的任务
+    pub fn set_held(&mut self, held: bool) {
+        if self.held == held {
+            return;
+        }
+        self.held = held;
+        if held {
+            if !self.pending.is_empty() {
+                crate::log_info!("有其他处理任务正在进行，队列暂停");
+            }
+        } else {
+            self.start_next();
+        }
+    }
+
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
+    /// 没有正在执行或等待中的任务
+    pub fn is_idle(&self) -> bool {
+        self.running.is_none() && self.pending.is_empty()
+    }
+
+    /// 收集后台事件；当前任务结束后开始下一个任务
+    pub fn poll(&mut self) -> QueuePoll {
+        let mut poll = QueuePoll::default();
+
+        while let Some(running) = &mut self.running {
+            let finished = match running.receiver.try_recv() {
+                Ok(JobEvent::Progress(progress)) => {
+                    running.progress = progress.clone();
+                    poll.progress = Some(progress);
+                    continue;
+                }
+                Ok(JobEvent::Finished(outcome)) => outcome,
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    Err(AppError::processing_error("处理线程意外退出"))
+                }
+            };
+
+            let Some(RunningJob { job, .. }) = self.running.take() else {
+                break;
+            };
+            match finished {
+                Ok(result) => {
+                    crate::log_info!("队列任务完成: {}", job.label());
+                    poll.finished.push(job.history_entry(result));
+                }
+                Err(e) => {
+                    crate::log_error!("队列任务失败: {}: {}", job.label(), e);
+                    poll.failed.push((job.label(), e));
+                }
+            }
+            self.start_next();
+        }
+
+        poll
+    }
+
+    fn start_next(&mut self) {
+        if self.running.is_some() || self.held {
+            return;
+        }
+        let Some(job) = self.pending.pop_front() else {
+            return;
+        };
+
+        crate::log_info!("开始执行队列任务: {}", job.label());
+        let (tx, rx) = mpsc::channel();
+        let worker_job = job.clone();
+        std::thread::spawn(move || {
+            let progress_tx = tx.clone();
+            let outcome = tokio::runtime::Runtime::new()
+                .map_err(|e| AppError::processing_error(format!("无法创建运行时: {}", e)))
+                .and_then(|runtime| {
+                    runtime.block_on(run_job(worker_job, move |p| {
+                        let _ = progress_tx.send(JobEvent::Progress(p));
+                    }))
+                });
+            let _ = tx.send(JobEvent::Finished(outcome));
+        });
+
+        self.running = Some(RunningJob {
+            job,
+            receiver: rx,
+            progress: ProcessingProgress::default(),
+        });
+    }
+}
+
+async fn run_job<F>(job: QueuedJob, progress_callback: F) -> Result<ProcessingResult>
+where
+    F: Fn(ProcessingProgress) + Send + Sync + 'static,
+{
+    let options = BatchOptions {
+        processor_id: job.processor.id().to_string(),
+        ..job.options.clone()
+    };
+
+    // DataEngine 在阻塞线程中调用处理器，这里借助运行时句柄执行异步的 process
+    let process = blocking_process(job.processor.clone(), current_runtime()?);
+
+    // 引擎通过 tracing 输出的日志同样带上运行 ID
+    let span = tracing::info_span!("run", run_id = %job.run_id);
+    DataEngine::process_batch(&job.input_dir, &job.output_dir, process, progress_callback, &options)
+        .instrument(span)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::examples::{DataCleaningProcessor, DataStatisticsProcessor};
+    use std::time::{Duration, Instant};
+    use tempfile::tempdir;
+
+    fn write_input(dir: &Path) {
+        let mut book = umya_spreadsheet::new_file();
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("数量");
+        sheet.get_cell_mut("A2").set_value("1");
+        umya_spreadsheet::writer::xlsx::write(&book, dir.join("a.xlsx")).unwrap();
+    }
+
+    #[test]
+    fn test_enqueued_jobs_run_in_order() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_input(input_dir.path());
+
+        let mut queue = JobQueue::new();
+        let first = queue.enqueue(
+            Arc::new(DataCleaningProcessor::new()),
+            input_dir.path(),
+            &output_dir.path().join("first"),
+            BatchOptions::default(),
+        );
+        let second = queue.enqueue(
+            Arc::new(DataStatisticsProcessor::new()),
+            input_dir.path(),
+            &output_dir.path().join("second"),
+            BatchOptions::default(),
+        );
+
+        // 第一个任务立即开始，第二个排队等待
+        assert_eq!(queue.running().map(|j| j.id), Some(first));
+        assert_eq!(queue.pending().map(|j| j.id).collect::<Vec<_>>(), vec![second]);
+
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let mut finished = Vec::new();
+        while !queue.is_idle() {
+            assert!(Instant::now() < deadline, "队列任务超时");
+            let poll = queue.poll();
+            assert!(poll.failed.is_empty());
+            finished.extend(poll.finished);
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let order: Vec<&str> = finished.iter().map(|e| e.processor_id.as_str()).collect();
+        assert_eq!(order, vec!["data_cleaning", "data_statistics"]);
+        assert_ne!(finished[0].run_id, finished[1].run_id);
+        assert!(output_dir.path().join("first").join("a.xlsx").exists());
+        assert!(output_dir.path().join("second").join("a.xlsx").exists());
+    }
+
+    #[test]
+    fn test_run_id_in_logs_and_history_entry() {
+        // 与 main 相同，把运行中的 tracing 日志转发到 LOGGER（全局只能设置一次）
+        {
+            use tracing_subscriber::layer::SubscriberExt;
+            let _ = tracing::subscriber::set_global_default(
+                tracing_subscriber::registry().with(crate::logger::RunLogLayer),
+            );
+        }
+
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_input(input_dir.path());
+
+        let mut queue = JobQueue::new();
+        queue.enqueue(
+            Arc::new(DataCleaningProcessor::new()),
+            input_dir.path(),
+            output_dir.path(),
+            BatchOptions::default(),
+        );
+        let run_id = queue.running().unwrap().run_id.clone();
+        assert_eq!(run_id.len(), 8);
+
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let mut finished = Vec::new();
+        while !queue.is_idle() {
+            assert!(Instant::now() < deadline, "队列任务超时");
+            finished.extend(queue.poll().finished);
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].run_id.as_deref(), Some(run_id.as_str()));
+
+        let logged: Vec<String> = crate::logger::LOGGER
+            .get_entries()
+            .into_iter()
+            .map(|e| e.message)
+            .filter(|m| m.contains(&run_id))
+            .collect();
+        // 加入队列、开始执行、完成三条日志都带有运行 ID
+        assert!(logged.iter().any(|m| m.contains("任务已加入队列")));
+        assert!(logged.iter().any(|m| m.contains("开始执行队列任务")));
+        assert!(logged.iter().any(|m| m.contains("队列任务完成")));
+        // 引擎通过 tracing 输出的逐文件日志也带有运行 ID
+        let engine_line = format!("[{}] 成功处理: a.xlsx", run_id);
+        assert!(logged.iter().any(|m| m == &engine_line), "{:?}", logged);
+    }
+
+    #[test]
+    fn test_held_queue_waits_for_main_task() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_input(input_dir.path());
+
+        let mut queue = JobQueue::new();
+        queue.set_held(true);
+        let id = queue.enqueue(
+            Arc::new(DataCleaningProcessor::new()),
+            input_dir.path(),
+            output_dir.path(),
+            BatchOptions::default(),
+        );
+
+        // 暂停期间只排队，不开始执行
+        assert!(!queue.is_running());
+        assert!(queue.progress().is_none());
+        assert_eq!(queue.pending().map(|j| j.id).collect::<Vec<_>>(), vec![id]);
+        assert!(queue.poll().finished.is_empty());
+        assert!(!queue.is_running());
+
+        // 主任务结束后开始执行
+        queue.set_held(false);
+        assert_eq!(queue.running().map(|j| j.id), Some(id));
+        assert!(queue.progress().is_some());
+
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let mut finished = Vec::new();
+        while !queue.is_idle() {
+            assert!(Instant::now() < deadline, "队列任务超时");
+            finished.extend(queue.poll().finished);
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(finished.len(), 1);
+    }
+
+    #[test]
+    fn test_cancel_pending_job() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        write_input(input_dir.path());
+
+        let mut queue = JobQueue::new();
+        let running = queue.enqueue(
+            Arc::new(DataCleaningProcessor::new()),
+            input_dir.path(),
+            output_dir.path(),
+            BatchOptions::default(),
+        );
+        let queued = queue.enqueue(
+            Arc::new(DataStatisticsProcessor::new()),
+            input_dir.path(),
+            output_dir.path(),
+            BatchOptions::default(),
+        );
+
+        // 正在执行的任务不能从队列中取消
+        assert!(!queue.cancel(running));
+        assert!(queue.cancel(queued));
+        assert_eq!(queue.pending().count(), 0);
+    }
+}
@@ -1,4 +1,8 @@
-use crate::processor::trait_def::ProcessorInfo;
+use crate::error::{AppError, Result};
+use crate::processor::examples::{CargoAnalysisProcessor, ExcelStructureAnalyzer};
+use crate::processor::registry::ProcessorRegistry;
+use crate::processor::trait_def::{DataProcessor, ProcessorCapabilities, ProcessorInfo};
+use std::collections::HashMap;
 
 /// 处理器信息（用于 UI 显示）
 #[derive(Debug, Clone)]
@@ -7,12 +11,36 @@ pub struct ProcessorEntry {
     pub name: String,
     pub description: String,
     pub icon: Option<String>,
+    /// 分类名称，None 时归入“其他”
+    pub category: Option<String>,
+    /// 搜索用的标签
+    pub tags: Vec<String>,
     pub version: String,
+    pub capabilities: ProcessorCapabilities,
+}
+
+impl ProcessorEntry {
+    /// 转换为 UI 使用的处理器信息
+    pub fn info(&self) -> ProcessorInfo {
+        ProcessorInfo {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            icon: self.icon.clone(),
+            category: self.category.clone(),
+            tags: self.tags.clone(),
+            version: self.version.clone(),
+            available: true,
+            capabilities: self.capabilities,
+        }
+    }
 }
 
 /// 处理器管理器
 pub struct ProcessorManager {
     processors: Vec<ProcessorEntry>,
+    /// 处理器 ID 到 `processors` 下标的索引
+    index: HashMap<String, usize>,
 }
 
 impl ProcessorManager {
@@ -20,6 +48,7 @@ impl ProcessorManager {
     pub fn new() -> Self {
         let mut manager = Self {
             processors: Vec::new(),
+            index: HashMap::new(),
         };
         
         // 注册示例处理器
@@ -28,59 +57,169 @@ impl ProcessorManager {
         manager
     }
 
+    /// 创建处理器管理器，并应用配置目录中的处理器注册表（如果存在）
+    ///
+    /// 注册表读取失败时记录警告并使用全部内置处理器。
+    pub fn load() -> Self {
+        let mut manager = Self::new();
+        let Some(path) = ProcessorRegistry::default_path() else {
+            return manager;
+        };
+        match ProcessorRegistry::load(&path) {
+            Ok(Some(registry)) => manager.apply_registry(&registry),
+            Ok(None) => {}
+            Err(e) => crate::log_warning!("读取处理器注册表 {} 失败: {}", path.display(), e),
+        }
+        manager
+    }
+
+    /// 按注册表禁用处理器并调整顺序
+    ///
+    /// 列出的处理器按注册表顺序排在前面，未列出的保持原有顺序排在后面；
+    /// 注册表中未知的处理器 ID 会被忽略并记录警告。
+    pub fn apply_registry(&mut self, registry: &ProcessorRegistry) {
+        for entry in &registry.processors {
+            if !self.has_processor(&entry.id) {
+                crate::log_warning!("处理器注册表中的处理器不存在，已忽略: {}", entry.id);
+            }
+        }
+
+        let mut processors: Vec<ProcessorEntry> = std::mem::take(&mut self.processors)
+            .into_iter()
+            .filter(|entry| registry.is_enabled(&entry.id))
+            .collect();
+        // 稳定排序，未列出的处理器保持原有相对顺序
+        processors.sort_by_key(|entry| registry.position(&entry.id).unwrap_or(usize::MAX));
+
+        self.index = processors
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (entry.id.clone(), i))
+            .collect();
+        self.processors = processors;
+    }
+
+    /// 注册处理器
+    ///
+    /// ID 已被注册时返回 [`AppError::DuplicateProcessor`]，不会覆盖已有的处理器。
+    pub fn register(&mut self, entry: ProcessorEntry) -> Result<()> {
+        if self.index.contains_key(&entry.id) {
+            return Err(AppError::DuplicateProcessor(entry.id));
+        }
+
+        self.index.insert(entry.id.clone(), self.processors.len());
+        self.processors.push(entry);
+        Ok(())
+    }
+
     /// 注册示例处理器
     fn register_example_processors(&mut self) {
         // 处理器 1: 货物分析表处理器（使用行类型识别系统）
-        self.processors.push(ProcessorEntry {
+        self.register_or_log(ProcessorEntry {
             id: "cargo_analysis".to_string(),
             name: "货物分析表处理器".to_string(),
             description: "自动识别和处理货物分析表，提取项目编号、柜号、数据行等信息".to_string(),
             icon: Some("📦".to_string()),
+            category: Some("分析".to_string()),
+            tags: vec!["货物".to_string(), "柜号".to_string(), "项目编号".to_string()],
             version: "1.0.0".to_string(),
+            capabilities: CargoAnalysisProcessor::new().capabilities(),
         });
 
         // 处理器 2: 辅材处理
-        self.processors.push(ProcessorEntry {
+        self.register_or_log(ProcessorEntry {
             id: "auxiliary_material".to_string(),
             name: "辅材处理".to_string(),
             description: "处理和整理辅材相关数据".to_string(),
             icon: Some("🔧".to_string()),
+            category: Some("数据处理".to_string()),
+            tags: vec!["辅材".to_string(), "材料".to_string()],
             version: "1.0.0".to_string(),
+            capabilities: ProcessorCapabilities::default(),
         });
 
         // 处理器 3: Excel结构分析器
-        self.processors.push(ProcessorEntry {
+        self.register_or_log(ProcessorEntry {
             id: "excel_structure_analyzer".to_string(),
             name: "Excel结构分析器".to_string(),
             description: "分析Excel文件的单个Sheet结构".to_string(),
             icon: Some("🔍".to_string()),
+            category: Some("分析".to_string()),
+            tags: vec!["结构".to_string(), "工作表".to_string(), "sheet".to_string()],
             version: "1.0.0".to_string(),
+            capabilities: ExcelStructureAnalyzer::new().capabilities(),
         });
     }
 
+    fn register_or_log(&mut self, entry: ProcessorEntry) {
+        if let Err(e) = self.register(entry) {
+            crate::log_error!("注册处理器失败: {}", e);
+        }
+    }
+
     /// 获取处理器信息
     pub fn get_processor(&self, id: &str) -> Option<&ProcessorEntry> {
-        self.processors.iter().find(|p| p.id == id)
+        self.index.get(id).and_then(|&i| self.processors.get(i))
     }
 
     /// 列出所有处理器
+    ///
+    /// 这是处理器名称、图标、描述等显示信息的唯一来源，UI 不应另行维护。
     pub fn list_processors(&self) -> Vec<ProcessorInfo> {
-        self.processors
-            .iter()
-            .map(|p| ProcessorInfo {
-                id: p.id.clone(),
-                name: p.name.clone(),
-                description: p.description.clone(),
-                icon: p.icon.clone(),
-                version: p.version.clone(),
-                available: true,
-            })
-            .collect()
+        self.processors.iter().map(ProcessorEntry::info).collect()
+    }
+
+    /// 获取单个处理器的显示信息
+    pub fn get_info(&self, id: &str) -> Option<ProcessorInfo> {
+        self.get_processor(id).map(ProcessorEntry::info)
+    }
+
+    /// 创建可执行的处理器实例（没有实现的处理器返回 None）
+    pub fn create_processor(&self, id: &str) -> Option<std::sync::Arc<dyn DataProcessor>> {
+        if !self.has_processor(id) {
+            return None;
+        }
+        match id {
+            "cargo_analysis" => Some(std::sync::Arc::new(CargoAnalysisProcessor::new())),
+            "excel_structure_analyzer" => Some(std::sync::Arc::new(ExcelStructureAnalyzer::new())),
+            _ => None,
+        }
+    }
+
+    /// 按处理器配置创建处理器实例（应用识别配置、识别顺序、文本规范化和输出行类型等选项）
+    ///
+    /// `profile` 为应用配置中该处理器的默认识别配置，None 时使用内置配置。
+    pub fn create_configured_processor(
+        &self,
+        id: &str,
+        config: &crate::models::ProcessorConfig,
+        profile: Option<&str>,
+    ) -> Option<std::sync::Arc<dyn DataProcessor>> {
+        match id {
+            "cargo_analysis" if self.has_processor(id) => {
+                let mut processor = CargoAnalysisProcessor::new()
+                    .with_row_type_order(config.row_type_order())
+                    .with_text_normalization(config.text_normalization())
+                    .with_row_type_filter(config.kept_row_types());
+                if let Some(spec) = profile {
+                    processor = processor.with_profile(spec);
+                }
+                Some(std::sync::Arc::new(processor))
+            }
+            _ => self.create_processor(id),
+        }
+    }
+
+    /// 获取处理器能力描述（未知处理器返回默认能力）
+    pub fn get_capabilities(&self, id: &str) -> ProcessorCapabilities {
+        self.get_processor(id)
+            .map(|p| p.capabilities)
+            .unwrap_or_default()
     }
 
     /// 检查处理器是否存在
     pub fn has_processor(&self, id: &str) -> bool {
-        self.processors.iter().any(|p| p.id == id)
+        self.index.contains_key(id)
     }
 
     /// 获取处理器数量
@@ -95,3 +234,131 @@ impl Default for ProcessorManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::examples::{DataCleaningProcessor, DataStatisticsProcessor};
+
+    #[test]
+    fn test_cargo_analysis_capabilities() {
+        let caps = CargoAnalysisProcessor::new().capabilities();
+        assert!(caps.supports_folder_input);
+        assert!(caps.requires_output_dir);
+        assert!(caps.requires_output_filename);
+        assert!(caps.requires_sheet_selection);
+        assert!(caps.writes_output());
+    }
+
+    #[test]
+    fn test_excel_structure_analyzer_capabilities() {
+        let caps = ExcelStructureAnalyzer::new().capabilities();
+        assert!(!caps.supports_folder_input);
+        assert!(!caps.requires_output_dir);
+        assert!(!caps.requires_output_filename);
+        assert!(caps.requires_sheet_selection);
+        assert!(!caps.writes_output());
+    }
+
+    #[test]
+    fn test_example_processor_capabilities() {
+        assert_eq!(
+            DataCleaningProcessor::new().capabilities(),
+            ProcessorCapabilities::default()
+        );
+        assert_eq!(
+            DataStatisticsProcessor::new().capabilities(),
+            ProcessorCapabilities::default()
+        );
+    }
+
+    fn entry(id: &str, name: &str) -> ProcessorEntry {
+        ProcessorEntry {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            icon: None,
+            category: None,
+            tags: Vec::new(),
+            version: "1.0.0".to_string(),
+            capabilities: ProcessorCapabilities::default(),
+        }
+    }
+
+    #[test]
+    fn test_register_duplicate_id_fails() {
+        let mut manager = ProcessorManager::new();
+        let count = manager.processor_count();
+
+        manager.register(entry("custom", "第一个")).unwrap();
+        let err = manager.register(entry("custom", "第二个")).unwrap_err();
+
+        assert!(matches!(err, AppError::DuplicateProcessor(ref id) if id == "custom"));
+        assert_eq!(manager.processor_count(), count + 1);
+        assert_eq!(manager.get_processor("custom").unwrap().name, "第一个");
+        assert!(manager.has_processor("custom"));
+        assert!(!manager.has_processor("missing"));
+    }
+
+    #[test]
+    fn test_registry_disables_and_orders_processors() {
+        let registry = ProcessorRegistry::from_toml(
+            r#"
+            [[processors]]
+            id = "excel_structure_analyzer"
+
+            [[processors]]
+            id = "auxiliary_material"
+            enabled = false
+
+            [[processors]]
+            id = "registry_test_unknown"
+            "#,
+        )
+        .unwrap();
+
+        let mut manager = ProcessorManager::new();
+        manager.apply_registry(&registry);
+
+        let ids: Vec<String> = manager.list_processors().into_iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec!["excel_structure_analyzer", "cargo_analysis"]);
+        assert!(!manager.has_processor("auxiliary_material"));
+        assert_eq!(manager.get_processor("cargo_analysis").unwrap().id, "cargo_analysis");
+
+        assert!(crate::logger::LOGGER
+            .get_entries()
+            .iter()
+            .any(|e| e.message.contains("registry_test_unknown")));
+    }
+
+    #[test]
+    fn test_example_processor_ids_are_unique() {
+        let manager = ProcessorManager::new();
+        assert_eq!(manager.processor_count(), 3);
+        for info in manager.list_processors() {
+            assert_eq!(manager.get_processor(&info.id).unwrap().id, info.id);
+        }
+    }
+
+    #[test]
+    fn test_manager_capabilities() {
+        let manager = ProcessorManager::new();
+
+        assert_eq!(
+            manager.get_capabilities("excel_structure_analyzer"),
+            ProcessorCapabilities::log_only()
+        );
+        assert_eq!(
+            manager.get_capabilities("auxiliary_material"),
+            ProcessorCapabilities::default()
+        );
+        assert_eq!(
+            manager.get_capabilities("cargo_analysis"),
+            CargoAnalysisProcessor::new().capabilities()
+        );
+
+        for info in manager.list_processors() {
+            assert_eq!(info.capabilities, manager.get_capabilities(&info.id));
+        }
+    }
+}
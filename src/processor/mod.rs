@@ -1,7 +1,15 @@
 // Processor 模块
+pub mod blocking;
 pub mod examples;
+pub mod job_queue;
 pub mod manager;
+pub mod multi_run;
+pub mod registry;
 pub mod trait_def;
 
+pub use blocking::{blocking_process, current_runtime};
+pub use job_queue::{JobQueue, QueuedJob};
 pub use manager::ProcessorManager;
-pub use trait_def::{DataProcessor, ProcessorInfo};
+pub use multi_run::{execute_many, MultiRunResult, ProcessorRun};
+pub use registry::ProcessorRegistry;
+pub use trait_def::{DataProcessor, ProcessorCapabilities, ProcessorInfo};
@@ -0,0 +1,187 @@
+// 多处理器对比运行
+use crate::engine::data_engine::{BatchOptions, DataEngine};
+use crate::error::Result;
+use crate::history::HistoryEntry;
+use crate::models::{CancelToken, ProcessingProgress, ProcessingResult};
+use crate::processor::{blocking_process, current_runtime, DataProcessor};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// 单个处理器的运行结果
+#[derive(Debug, Clone)]
+pub struct ProcessorRun {
+    pub processor_id: String,
+    pub processor_name: String,
+    /// 该处理器的输出子目录
+    pub output_dir: PathBuf,
+    pub result: ProcessingResult,
+}
+
+/// 多个处理器依次运行的汇总结果
+#[derive(Debug, Clone)]
+pub struct MultiRunResult {
+    /// 本次运行的分组 ID，写入各条历史记录
+    pub group_id: String,
+    pub input_dir: PathBuf,
+    pub runs: Vec<ProcessorRun>,
+}
+
+impl MultiRunResult {
+    /// 所有处理器成功处理的文件数合计
+    pub fn total_successful(&self) -> usize {
+        self.runs.iter().map(|r| r.result.successful).sum()
+    }
+
+    /// 所有处理器失败的文件数合计
+    pub fn total_failed(&self) -> usize {
+        self.runs.iter().map(|r| r.result.failed).sum()
+    }
+
+    /// 为每个处理器生成一条带分组 ID 的历史记录
+    pub fn history_entries(&self) -> Vec<HistoryEntry> {
+        self.runs
+            .iter()
+            .map(|run| {
+                HistoryEntry::new(
+                    run.processor_id.clone(),
+                    run.processor_name.clone(),
+                    self.input_dir.clone(),
+                    run.output_dir.clone(),
+                    run.result.clone(),
+                )
+                .with_group(self.group_id.clone())
+            })
+            .collect()
+    }
+}
+
+/// 对同一输入依次运行多个处理器
+///
+/// 每个处理器写入 `output_dir/<处理器 ID>` 子目录，互不覆盖。
+/// 某个处理器整体失败（如目录无法创建）时立即返回错误。
+pub async fn execute_many<F>(
+    processors: &[Arc<dyn DataProcessor>],
+    input_dir: &Path,
+    output_dir: &Path,
+    options: &BatchOptions,
+    progress_callback: F,
+) -> Result<MultiRunResult>
+where
+    F: Fn(ProcessingProgress) + Send + Sync + Clone + 'static,
+{
+    let mut multi = MultiRunResult {
+        group_id: uuid::Uuid::new_v4().to_string(),
+        input_dir: input_dir.to_path_buf(),
+        runs: Vec::with_capacity(processors.len()),
+    };
+
+    let runtime = current_runtime()?;
+    for processor in processors {
+        let sub_dir = output_dir.join(processor.id());
+        crate::log_info!("对比运行: {} -> {}", processor.name(), sub_dir.display());
+
+        // 每个处理器单独中止：前一个处理器失败过多不影响后面的对比运行
+        let options = BatchOptions {
+            processor_id: processor.id().to_string(),
+            cancel: CancelToken::new(),
+            ..options.clone()
+        };
+
+        // DataEngine 在阻塞线程中调用处理器，这里借助运行时句柄执行异步的 process
+        let process = blocking_process(processor.clone(), runtime.clone());
+
+        let result = DataEngine::process_batch(
+            input_dir,
+            &sub_dir,
+            process,
+            progress_callback.clone(),
+            &options,
+        )
+        .await?;
+
+        multi.runs.push(ProcessorRun {
+            processor_id: processor.id().to_string(),
+            processor_name: processor.name().to_string(),
+            output_dir: sub_dir,
+            result,
+        });
+    }
+
+    crate::log_info!(
+        "对比运行完成: {} 个处理器，成功 {} 个文件，失败 {} 个文件",
+        multi.runs.len(),
+        multi.total_successful(),
+        multi.total_failed()
+    );
+
+    Ok(multi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::examples::{DataCleaningProcessor, DataStatisticsProcessor};
+    use tempfile::tempdir;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_execute_many_groups_results() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+
+        let mut book = umya_spreadsheet::new_file();
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("数量");
+        sheet.get_cell_mut("A2").set_value("1");
+        sheet.get_cell_mut("A3").set_value("2");
+        umya_spreadsheet::writer::xlsx::write(&book, input_dir.path().join("a.xlsx")).unwrap();
+
+        let processors: Vec<Arc<dyn DataProcessor>> = vec![
+            Arc::new(DataCleaningProcessor::new()),
+            Arc::new(DataStatisticsProcessor::new()),
+        ];
+
+        let multi = execute_many(
+            &processors,
+            input_dir.path(),
+            output_dir.path(),
+            &BatchOptions::default(),
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(multi.runs.len(), 2);
+        for (run, id) in multi.runs.iter().zip(["data_cleaning", "data_statistics"]) {
+            assert_eq!(run.processor_id, id);
+            assert_eq!(run.result.successful, 1);
+            assert!(output_dir.path().join(id).join("a.xlsx").exists());
+        }
+
+        let entries = multi.history_entries();
+        assert_eq!(entries.len(), 2);
+        assert!(entries
+            .iter()
+            .all(|e| e.group_id.as_deref() == Some(multi.group_id.as_str())));
+    }
+
+    // 默认的 current_thread 运行时下完整执行处理流程，不会因嵌套 block_on 而 panic
+    #[tokio::test]
+    async fn test_execute_many_on_current_thread_runtime() {
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+
+        let mut book = umya_spreadsheet::new_file();
+        let sheet = book.get_sheet_mut(&0).unwrap();
+        sheet.get_cell_mut("A1").set_value("数量");
+        sheet.get_cell_mut("A2").set_value("1");
+        umya_spreadsheet::writer::xlsx::write(&book, input_dir.path().join("a.xlsx")).unwrap();
+
+        let processors: Vec<Arc<dyn DataProcessor>> = vec![Arc::new(DataCleaningProcessor::new())];
+        let multi = execute_many(&processors, input_dir.path(), output_dir.path(), &BatchOptions::default(), |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(multi.total_successful(), 1);
+        assert_eq!(multi.total_failed(), 0);
+    }
+}
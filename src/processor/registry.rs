@@ -0,0 +1,110 @@
+// 处理器注册表
+//
+// 用户可以在配置目录的 processors.toml 中启用/禁用内置处理器并调整显示顺序，
+// 无需重新编译。未列出的处理器保持启用，排在列出的处理器之后。
+//
+// ```toml
+// [[processors]]
+// id = "excel_structure_analyzer"
+//
+// [[processors]]
+// id = "auxiliary_material"
+// enabled = false
+// ```
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 注册表文件名
+pub const REGISTRY_FILE_NAME: &str = "processors.toml";
+
+/// 注册表中的单个处理器
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    /// 内置处理器 ID
+    pub id: String,
+    /// 是否启用（默认启用）
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 处理器注册表，列表顺序即显示顺序
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessorRegistry {
+    #[serde(default)]
+    pub processors: Vec<RegistryEntry>,
+}
+
+impl ProcessorRegistry {
+    /// 默认的注册表路径（配置目录下的 IntegratedPower/processors.toml）
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("IntegratedPower").join(REGISTRY_FILE_NAME))
+    }
+
+    /// 从 TOML 文本解析
+    pub fn from_toml(text: &str) -> Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// 读取注册表文件，文件不存在时返回 None
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(path)?;
+        Self::from_toml(&text).map(Some)
+    }
+
+    /// 是否启用指定处理器（未列出的处理器默认启用）
+    pub fn is_enabled(&self, id: &str) -> bool {
+        self.processors
+            .iter()
+            .find(|entry| entry.id == id)
+            .map_or(true, |entry| entry.enabled)
+    }
+
+    /// 处理器在注册表中的位置，未列出时为 None
+    pub fn position(&self, id: &str) -> Option<usize> {
+        self.processors.iter().position(|entry| entry.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_registry() {
+        let registry = ProcessorRegistry::from_toml(
+            r#"
+            [[processors]]
+            id = "excel_structure_analyzer"
+
+            [[processors]]
+            id = "auxiliary_material"
+            enabled = false
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(registry.processors.len(), 2);
+        assert!(registry.is_enabled("excel_structure_analyzer"));
+        assert!(!registry.is_enabled("auxiliary_material"));
+        // 未列出的处理器保持启用
+        assert!(registry.is_enabled("cargo_analysis"));
+        assert_eq!(registry.position("auxiliary_material"), Some(1));
+
+        assert!(ProcessorRegistry::from_toml("processors = 1").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_registry() {
+        let dir = tempdir().unwrap();
+        assert_eq!(ProcessorRegistry::load(&dir.path().join(REGISTRY_FILE_NAME)).unwrap(), None);
+    }
+}
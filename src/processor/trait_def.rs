@@ -29,6 +29,16 @@ pub trait DataProcessor: Send + Sync {
         None
     }
 
+    /// 获取处理器分类（可选），功能列表按分类分组显示
+    fn category(&self) -> Option<&str> {
+        None
+    }
+
+    /// 获取处理器标签，搜索时与名称、描述一起匹配
+    fn tags(&self) -> &[&str] {
+        &[]
+    }
+
     /// 获取处理器版本
     fn version(&self) -> &str {
         "1.0.0"
@@ -38,6 +48,64 @@ pub trait DataProcessor: Send + Sync {
     fn is_available(&self) -> bool {
         true
     }
+
+    /// 获取处理器能力描述（输入输出要求）
+    fn capabilities(&self) -> ProcessorCapabilities {
+        ProcessorCapabilities::default()
+    }
+}
+
+/// 处理器能力描述
+///
+/// 描述处理器对输入输出的要求，UI 据此决定显示哪些设置项以及何时允许开始处理。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessorCapabilities {
+    /// 是否支持文件夹输入（否则只能选择单个文件）
+    pub supports_folder_input: bool,
+    /// 是否需要输出目录
+    pub requires_output_dir: bool,
+    /// 是否需要输出文件名
+    pub requires_output_filename: bool,
+    /// 只处理单个 Sheet，输入文件有多个 Sheet 时必须明确选择
+    pub requires_sheet_selection: bool,
+}
+
+impl ProcessorCapabilities {
+    /// 只读取单个文件、结果输出到日志的处理器
+    pub fn log_only() -> Self {
+        Self {
+            supports_folder_input: false,
+            requires_output_dir: false,
+            requires_output_filename: false,
+            requires_sheet_selection: true,
+        }
+    }
+
+    /// 当前配置下是否还需要用户选择 Sheet
+    ///
+    /// 仅在文件输入且文件包含多个 Sheet、尚未选择时返回 true。
+    pub fn needs_sheet_choice(&self, config: &crate::models::ProcessorConfig) -> bool {
+        self.requires_sheet_selection
+            && config.input_type == crate::models::InputType::File
+            && config.available_sheets.len() > 1
+            && config.selected_sheet.is_none()
+    }
+
+    /// 是否写出结果文件
+    pub fn writes_output(&self) -> bool {
+        self.requires_output_dir || self.requires_output_filename
+    }
+}
+
+impl Default for ProcessorCapabilities {
+    fn default() -> Self {
+        Self {
+            supports_folder_input: true,
+            requires_output_dir: true,
+            requires_output_filename: true,
+            requires_sheet_selection: false,
+        }
+    }
 }
 
 /// 处理器信息
@@ -47,8 +115,11 @@ pub struct ProcessorInfo {
     pub name: String,
     pub description: String,
     pub icon: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
     pub version: String,
     pub available: bool,
+    pub capabilities: ProcessorCapabilities,
 }
 
 impl ProcessorInfo {
@@ -59,8 +130,11 @@ impl ProcessorInfo {
             name: processor.name().to_string(),
             description: processor.description().to_string(),
             icon: processor.icon().map(|s| s.to_string()),
+            category: processor.category().map(|s| s.to_string()),
+            tags: processor.tags().iter().map(|s| s.to_string()).collect(),
             version: processor.version().to_string(),
             available: processor.is_available(),
+            capabilities: processor.capabilities(),
         }
     }
 }
@@ -0,0 +1,111 @@
+// 运行时资源检查
+//
+// build.rs 会把 resources 目录复制到可执行文件旁边；运行时按
+// 「当前工作目录 → 可执行文件所在目录」的顺序查找资源。
+use std::path::{Path, PathBuf};
+
+/// 中文字体（思源黑体）相对路径
+pub const FONT_RESOURCE: &str = "resources/fonts/SourceHanSansSC-Regular.otf";
+
+/// 启动时检查的资源（名称, 相对路径）
+pub const EXPECTED_RESOURCES: &[(&str, &str)] = &[("中文字体", FONT_RESOURCE)];
+
+/// 单个资源的检查结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceStatus {
+    pub name: &'static str,
+    pub relative_path: &'static str,
+    /// 找到时为实际路径
+    pub resolved: Option<PathBuf>,
+}
+
+/// 资源查找的根目录：当前工作目录、可执行文件所在目录
+pub fn resource_roots() -> Vec<PathBuf> {
+    let mut roots = vec![PathBuf::from(".")];
+    if let Ok(exe) = std::env::current_exe() {
+        if let Some(dir) = exe.parent() {
+            roots.push(dir.to_path_buf());
+        }
+    }
+    roots
+}
+
+/// 资源在各根目录下的候选路径
+pub fn candidate_paths(relative_path: &str, roots: &[PathBuf]) -> Vec<PathBuf> {
+    roots.iter().map(|root| root.join(relative_path)).collect()
+}
+
+/// 在根目录中按顺序查找资源，返回第一个存在的路径
+pub fn resolve_resource(relative_path: &str, roots: &[PathBuf]) -> Option<PathBuf> {
+    candidate_paths(relative_path, roots)
+        .into_iter()
+        .find(|p| p.is_file())
+}
+
+/// 检查所有预期资源
+pub fn check_resources(roots: &[PathBuf]) -> Vec<ResourceStatus> {
+    EXPECTED_RESOURCES
+        .iter()
+        .map(|(name, relative_path)| ResourceStatus {
+            name,
+            relative_path,
+            resolved: resolve_resource(relative_path, roots),
+        })
+        .collect()
+}
+
+/// 把资源检查结果写入日志面板
+pub fn log_resource_check() {
+    let roots = resource_roots();
+    for status in check_resources(&roots) {
+        match &status.resolved {
+            Some(path) => crate::log_info!("资源 {} 已找到: {}", status.name, display_path(path)),
+            None => crate::log_warning!(
+                "资源 {} 缺失（{}），已查找: {}",
+                status.name,
+                status.relative_path,
+                roots
+                    .iter()
+                    .map(|r| display_path(r))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+fn display_path(path: &Path) -> String {
+    path.canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_resource_prefers_first_root() {
+        let cwd = tempfile::tempdir().unwrap();
+        let exe_dir = tempfile::tempdir().unwrap();
+        let roots = vec![cwd.path().to_path_buf(), exe_dir.path().to_path_buf()];
+
+        // 都不存在
+        assert_eq!(resolve_resource(FONT_RESOURCE, &roots), None);
+        assert!(check_resources(&roots).iter().all(|s| s.resolved.is_none()));
+
+        // 只有可执行文件目录下存在
+        let exe_font = exe_dir.path().join(FONT_RESOURCE);
+        std::fs::create_dir_all(exe_font.parent().unwrap()).unwrap();
+        std::fs::write(&exe_font, b"font").unwrap();
+        assert_eq!(resolve_resource(FONT_RESOURCE, &roots), Some(exe_font));
+
+        // 工作目录下也存在时优先使用工作目录
+        let cwd_font = cwd.path().join(FONT_RESOURCE);
+        std::fs::create_dir_all(cwd_font.parent().unwrap()).unwrap();
+        std::fs::write(&cwd_font, b"font").unwrap();
+        assert_eq!(resolve_resource(FONT_RESOURCE, &roots), Some(cwd_font.clone()));
+        assert_eq!(check_resources(&roots)[0].resolved, Some(cwd_font));
+    }
+}
@@ -0,0 +1,218 @@
+// 后台任务管理
+//
+// 统一在 tokio 运行时中执行分析、读取 Sheet、批量处理等耗时任务：
+// 结果和进度经通道传回界面线程，每次更新都请求 egui 重绘。
+use crate::error::{AppError, Result};
+use crate::models::ProcessingProgress;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::mpsc;
+use tokio::task::JoinHandle;
+
+pub type TaskId = u64;
+
+/// 任务内部用于上报进度的发送端
+#[derive(Clone)]
+pub struct ProgressSender {
+    task: TaskId,
+    tx: mpsc::Sender<(TaskId, ProcessingProgress)>,
+    repaint: Option<egui::Context>,
+}
+
+impl ProgressSender {
+    pub fn send(&self, progress: ProcessingProgress) {
+        let _ = self.tx.send((self.task, progress));
+        if let Some(ctx) = &self.repaint {
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// 单个任务的结果接收端
+pub struct TaskHandle<T> {
+    pub id: TaskId,
+    receiver: mpsc::Receiver<T>,
+}
+
+impl<T> TaskHandle<T> {
+    /// 非阻塞地获取结果；任务被取消或崩溃时返回 `Disconnected`
+    pub fn try_recv(&self) -> std::result::Result<T, mpsc::TryRecvError> {
+        self.receiver.try_recv()
+    }
+}
+
+struct RunningTask {
+    name: String,
+    join: JoinHandle<()>,
+}
+
+/// 后台任务管理器，持有运行时和所有未结束任务的句柄
+pub struct TaskManager {
+    runtime: tokio::runtime::Runtime,
+    repaint: Option<egui::Context>,
+    tasks: HashMap<TaskId, RunningTask>,
+    next_id: TaskId,
+    progress_tx: mpsc::Sender<(TaskId, ProcessingProgress)>,
+    progress_rx: mpsc::Receiver<(TaskId, ProcessingProgress)>,
+}
+
+impl TaskManager {
+    pub fn new() -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("integrated-power-task")
+            .build()
+            .map_err(|e| AppError::processing_error(format!("无法创建后台任务运行时: {}", e)))?;
+        let (progress_tx, progress_rx) = mpsc::channel();
+
+        Ok(Self {
+            runtime,
+            repaint: None,
+            tasks: HashMap::new(),
+            next_id: 0,
+            progress_tx,
+            progress_rx,
+        })
+    }
+
+    /// 任务有结果或进度时请求重绘
+    pub fn with_repaint(mut self, ctx: egui::Context) -> Self {
+        self.repaint = Some(ctx);
+        self
+    }
+
+    /// 在运行时中启动异步任务，`task` 接收进度发送端并返回任务的 Future
+    pub fn spawn<T, F, Fut>(&mut self, name: &str, task: F) -> TaskHandle<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(ProgressSender) -> Fut,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        self.next_id += 1;
+        let id = self.next_id;
+        let (result_tx, receiver) = mpsc::channel();
+        let repaint = self.repaint.clone();
+        let future = task(self.progress_sender(id));
+
+        let join = self.runtime.spawn(async move {
+            let output = future.await;
+            let _ = result_tx.send(output);
+            if let Some(ctx) = repaint {
+                ctx.request_repaint();
+            }
+        });
+
+        crate::log_debug!("启动后台任务 #{}: {}", id, name);
+        self.tasks.insert(
+            id,
+            RunningTask {
+                name: name.to_string(),
+                join,
+            },
+        );
+        TaskHandle { id, receiver }
+    }
+
+    /// 在阻塞线程池中执行同步任务
+    pub fn spawn_blocking<T, F>(&mut self, name: &str, task: F) -> TaskHandle<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(ProgressSender) -> T + Send + 'static,
+    {
+        self.spawn(name, move |progress| async move {
+            tokio::task::spawn_blocking(move || task(progress))
+                .await
+                .expect("后台任务崩溃")
+        })
+    }
+
+    /// 取消任务，任务不存在或已结束时返回 false
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        match self.tasks.remove(&id) {
+            Some(task) if !task.join.is_finished() => {
+                task.join.abort();
+                crate::log_info!("已取消后台任务: {}", task.name);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 取出所有任务上报的进度，同时清理已结束的任务
+    pub fn drain_progress(&mut self) -> Vec<(TaskId, ProcessingProgress)> {
+        self.tasks.retain(|_, task| !task.join.is_finished());
+        self.progress_rx.try_iter().collect()
+    }
+
+    /// 未结束的任务数
+    pub fn running_count(&self) -> usize {
+        self.tasks.values().filter(|task| !task.join.is_finished()).count()
+    }
+
+    fn progress_sender(&self, task: TaskId) -> ProgressSender {
+        ProgressSender {
+            task,
+            tx: self.progress_tx.clone(),
+            repaint: self.repaint.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn wait_for<T>(handle: &TaskHandle<T>) -> T {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            match handle.try_recv() {
+                Ok(value) => return value,
+                Err(mpsc::TryRecvError::Empty) => {
+                    assert!(Instant::now() < deadline, "任务超时");
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => panic!("任务未返回结果"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_task_result_and_progress_delivered() {
+        let mut manager = TaskManager::new().unwrap();
+
+        let handle = manager.spawn("sum", |progress| async move {
+            let mut p = ProcessingProgress::new(2);
+            p.update(1, "a.xlsx".to_string());
+            progress.send(p);
+            1 + 2
+        });
+        let blocking = manager.spawn_blocking("label", |_| "done".to_string());
+
+        assert_eq!(wait_for(&handle), 3);
+        assert_eq!(wait_for(&blocking), "done");
+
+        let progress = manager.drain_progress();
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].0, handle.id);
+        assert_eq!(progress[0].1.current_file, "a.xlsx");
+    }
+
+    #[test]
+    fn test_cancelled_task_disconnects() {
+        let mut manager = TaskManager::new().unwrap();
+        let handle = manager.spawn("sleep", |_| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+
+        assert!(manager.cancel(handle.id));
+        assert!(!manager.cancel(handle.id));
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while handle.try_recv() == Err(mpsc::TryRecvError::Empty) {
+            assert!(Instant::now() < deadline);
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(handle.try_recv(), Err(mpsc::TryRecvError::Disconnected));
+    }
+}
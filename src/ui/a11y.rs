@@ -0,0 +1,65 @@
+// 无障碍辅助：为只显示图标的控件提供可读名称
+use egui::{Response, Ui};
+
+/// 只显示图标的工具栏操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolbarAction {
+    /// 切换主题（图标随当前主题变化）
+    ToggleTheme,
+    /// 打开/关闭日志查看器
+    ToggleLogViewer,
+    /// 加入对比运行（功能列表中的勾选框）
+    CompareSelect,
+}
+
+impl ToolbarAction {
+    pub const ALL: [ToolbarAction; 3] = [
+        ToolbarAction::ToggleTheme,
+        ToolbarAction::ToggleLogViewer,
+        ToolbarAction::CompareSelect,
+    ];
+
+    /// 供辅助技术朗读的名称，同时用作悬停提示
+    pub fn accessible_name(&self) -> &'static str {
+        match self {
+            ToolbarAction::ToggleTheme => "切换主题",
+            ToolbarAction::ToggleLogViewer => "查看日志",
+            ToolbarAction::CompareSelect => "加入对比运行",
+        }
+    }
+}
+
+/// 为控件设置可读名称和悬停提示
+pub fn label_widget(ui: &Ui, response: Response, action: ToolbarAction) -> Response {
+    let enabled = ui.is_enabled();
+    let widget_type = match action {
+        ToolbarAction::CompareSelect => egui::WidgetType::Checkbox,
+        _ => egui::WidgetType::Button,
+    };
+    response.widget_info(|| egui::WidgetInfo::labeled(widget_type, enabled, action.accessible_name()));
+    response.on_hover_text(action.accessible_name())
+}
+
+/// 只显示图标的按钮，辅助技术朗读 `action` 的名称
+pub fn icon_button(ui: &mut Ui, icon: &str, action: ToolbarAction) -> Response {
+    let response = ui.add_sized(
+        egui::vec2(40.0, 32.0),
+        egui::Button::new(egui::RichText::new(icon).size(18.0)),
+    );
+    label_widget(ui, response, action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_toolbar_action_has_accessible_name() {
+        for action in ToolbarAction::ALL {
+            let name = action.accessible_name();
+            assert!(!name.trim().is_empty(), "{:?}", action);
+            // 名称应为文字而不是图标
+            assert!(name.chars().any(|c| c.is_alphanumeric()), "{:?}", action);
+        }
+    }
+}
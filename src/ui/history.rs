@@ -1,9 +1,85 @@
 // 历史记录视图
 use crate::app::IntegratedPowerApp;
+use crate::history::HistoryManager;
 
-pub fn render(_app: &IntegratedPowerApp, ui: &mut egui::Ui) {
+pub fn render(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
     ui.add_space(20.0);
     ui.heading("历史记录");
+    ui.add_space(10.0);
+
+    render_import(app, ui);
+
     ui.add_space(10.0);
     ui.label("历史记录界面将在任务 14 中实现");
 }
+
+// 导入历史记录：后台解析，显示进度并可取消
+fn render_import(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
+    match &app.history_import {
+        Some(task) => {
+            let (parsed, total) = app.history_import_progress;
+            ui.horizontal(|ui| {
+                ui.spinner();
+                let fraction = if total > 0 { parsed as f32 / total as f32 } else { 0.0 };
+                ui.add(
+                    egui::ProgressBar::new(fraction)
+                        .desired_width(240.0)
+                        .text(format!("正在导入 {}/{}", parsed, total)),
+                );
+                if ui.button("取消").clicked() {
+                    task.cancel();
+                }
+            });
+        }
+        None => {
+            ui.horizontal(|ui| {
+                ui.label(format!("共 {} 条记录", app.history_manager.entry_count()));
+                if ui.button("📤 导出历史记录").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("JSON 文件", &["json"])
+                        .set_file_name("history.json")
+                        .save_file()
+                    {
+                        let exported = if app.history_export_anonymize {
+                            app.history_manager.export_anonymized(&path)
+                        } else {
+                            app.history_manager.export_to_file(&path)
+                        };
+                        match exported {
+                            Ok(_) => crate::log_info!("历史记录已导出到: {}", path.display()),
+                            Err(e) => app.report_error("导出历史记录失败", e.user_message()),
+                        }
+                    }
+                }
+                ui.checkbox(&mut app.history_export_anonymize, "匿名化路径")
+                    .on_hover_text("导出时只保留文件名，隐藏用户名和目录结构");
+
+                if ui.button("📥 导入历史记录").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("JSON 文件", &["json"])
+                        .pick_file()
+                    {
+                        app.history_import_progress = (0, 0);
+                        app.history_import = Some(HistoryManager::start_import(path));
+                    }
+                }
+
+                if ui
+                    .button("📜 导入旧版日志")
+                    .on_hover_text("从 [时间] [级别] 消息 格式的文本日志中还原批量处理记录")
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("日志文件", &["log", "txt"])
+                        .pick_file()
+                    {
+                        match app.history_manager.import_legacy_log(&path) {
+                            Ok(count) => crate::log_info!("从旧版日志导入 {} 条历史记录", count),
+                            Err(e) => app.report_error("导入旧版日志失败", e.user_message()),
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
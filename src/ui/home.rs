@@ -1,6 +1,6 @@
 // 主页视图 - 左右分栏布局
 use crate::app::IntegratedPowerApp;
-use crate::processor::trait_def::ProcessorInfo;
+use crate::processor::trait_def::{ProcessorCapabilities, ProcessorInfo};
 
 pub fn render(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
     // 左侧功能选择面板
@@ -18,7 +18,7 @@ pub fn render(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
         if let Some(selected_id) = &app.selected_processor.clone() {
             render_config_panel(app, ui, &selected_id);
         } else {
-            render_empty_state(ui);
+            render_empty_state(app, ui);
         }
     });
 }
@@ -45,30 +45,396 @@ fn render_function_list(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
 
     // 功能列表
     egui::ScrollArea::vertical().show(ui, |ui| {
-        let processors = get_filtered_processors(app);
-        
-        for processor in processors.iter() {
-            render_compact_card(app, ui, processor);
-            ui.add_space(8.0);
+        let processors = match get_filtered_processors(app) {
+            FunctionListModel::NoProcessors => {
+                render_no_processors(app, ui);
+                return;
+            }
+            FunctionListModel::NoMatches { query } => {
+                render_no_matches(app, ui, &query);
+                return;
+            }
+            FunctionListModel::Items(processors) => processors,
+        };
+
+        // 搜索时展开所有分组，避免匹配项被折叠
+        let searching = !app.search_query.trim().is_empty();
+        for (category, processors) in group_by_category(processors) {
+            egui::CollapsingHeader::new(
+                egui::RichText::new(format!("{} ({})", category, processors.len())).strong(),
+            )
+            .id_source(("function_category", &category))
+            .default_open(true)
+            .open(searching.then_some(true))
+            .show(ui, |ui| {
+                for processor in processors.iter() {
+                    ui.horizontal(|ui| {
+                        // 勾选多个处理器后可对同一输入依次运行并对比结果
+                        let mut compared = app.compare_selection.contains(&processor.id);
+                        let response = ui.checkbox(&mut compared, "");
+                        let response = super::a11y::label_widget(ui, response, super::a11y::ToolbarAction::CompareSelect);
+                        if response.changed() {
+                            if compared {
+                                app.compare_selection.push(processor.id.clone());
+                            } else {
+                                app.compare_selection.retain(|id| id != &processor.id);
+                            }
+                        }
+                        ui.vertical(|ui| render_compact_card(app, ui, processor));
+                    });
+                    ui.add_space(8.0);
+                }
+            });
+        }
+
+        if app.compare_selection.len() >= 2 {
+            ui.add_space(4.0);
+            // 其他任务执行中时不开始对比运行，避免两个任务同时处理
+            let running = app.is_busy();
+            let label = format!("▶ 对比运行所选 ({})", app.compare_selection.len());
+            if ui.add_enabled(!running, egui::Button::new(label)).clicked() {
+                start_multi_run(app);
+            }
+            if app.multi_run_task.is_some() {
+                ui.spinner();
+            }
         }
     });
 }
 
-fn get_filtered_processors(app: &IntegratedPowerApp) -> Vec<ProcessorInfo> {
-    let processors = app.processor_manager.list_processors();
-    
-    if app.search_query.is_empty() {
-        processors
+/// 没有注册任何处理器时的提示，可重新加载处理器列表
+fn render_no_processors(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
+    ui.vertical_centered(|ui| {
+        ui.add_space(40.0);
+        ui.label(
+            egui::RichText::new("⚠ 没有可用的处理功能")
+                .size(16.0)
+                .color(ui.visuals().warn_fg_color),
+        );
+        ui.add_space(6.0);
+        ui.label(
+            egui::RichText::new("处理器注册失败，详情请查看日志")
+                .color(ui.visuals().weak_text_color()),
+        );
+        ui.add_space(10.0);
+        if ui.button("🔄 重新加载").clicked() {
+            app.processor_manager = crate::processor::ProcessorManager::load();
+            crate::log_info!("已重新加载处理器: {} 个", app.processor_manager.processor_count());
+        }
+    });
+}
+
+/// 搜索无结果时的提示，回显搜索词并提供清除按钮
+fn render_no_matches(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, query: &str) {
+    ui.vertical_centered(|ui| {
+        ui.add_space(40.0);
+        ui.label(
+            egui::RichText::new("未找到匹配的功能")
+                .size(16.0)
+                .color(ui.visuals().weak_text_color()),
+        );
+        ui.add_space(6.0);
+        ui.label(
+            egui::RichText::new(format!("搜索: “{}”", query))
+                .color(ui.visuals().weak_text_color()),
+        );
+        ui.add_space(10.0);
+        if ui.button("✖ 清除搜索").clicked() {
+            app.search_query.clear();
+        }
+    });
+}
+
+/// 以当前选中处理器的输入输出配置，依次运行勾选的处理器
+fn start_multi_run(app: &mut IntegratedPowerApp) {
+    let Some(config) = app
+        .selected_processor
+        .as_ref()
+        .and_then(|id| app.processor_configs.get(id))
+        .cloned()
+    else {
+        app.error_message = Some("请先选中一个处理器并设置输入文件夹和输出目录".to_string());
+        return;
+    };
+
+    let (Some(input_dir), Some(output_dir)) = (config.input_path.clone(), config.output_dir.clone()) else {
+        app.error_message = Some("对比运行需要设置输入文件夹和输出目录".to_string());
+        return;
+    };
+    if !input_dir.is_dir() {
+        app.error_message = Some("对比运行的输入必须是文件夹".to_string());
+        return;
+    }
+
+    // 每个处理器按各自的配置（识别配置、行类型过滤、文本规范化等）创建，与单独运行时一致
+    let mut processors = Vec::new();
+    for id in &app.compare_selection {
+        let processor_config = app
+            .processor_configs
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| crate::models::ProcessorConfig::new(id));
+        let profile = app.config_manager.get_config().profile_spec(id);
+        match app
+            .processor_manager
+            .create_configured_processor(id, &processor_config, profile)
+        {
+            Some(processor) => processors.push(processor),
+            None => crate::log_warning!("处理器 {} 暂不支持对比运行，已跳过", id),
+        }
+    }
+    if processors.is_empty() {
+        app.error_message = Some("所选处理器都不支持对比运行".to_string());
+        return;
+    }
+
+    let options = configured_batch_options(app, &config, "");
+    let task = app.task_manager.spawn("对比运行", move |progress| async move {
+        crate::processor::execute_many(&processors, &input_dir, &output_dir, &options, move |p| {
+            progress.send(p)
+        })
+        .await
+    });
+    app.multi_run_task = Some(task);
+}
+
+/// CSV 输出的分隔符和 BOM 设置
+fn render_csv_options(ui: &mut egui::Ui, config: &mut crate::models::ProcessorConfig) {
+    use crate::engine::data_engine::CsvOptions;
+
+    let csv = config.csv_options();
+    ui.horizontal(|ui| {
+        ui.label("分隔符:");
+        egui::ComboBox::from_id_source("csv_delimiter")
+            .selected_text(csv.delimiter_label())
+            .show_ui(ui, |ui| {
+                for (delimiter, label) in CsvOptions::DELIMITERS {
+                    if ui.selectable_label(csv.delimiter == delimiter, label).clicked() {
+                        config.set_string("csv_delimiter".to_string(), (delimiter as char).to_string());
+                    }
+                }
+            });
+
+        let mut bom = csv.utf8_bom;
+        if ui
+            .checkbox(&mut bom, "UTF-8 BOM")
+            .on_hover_text("Excel 需要 BOM 才能正确显示中文，其他程序读取时可关闭")
+            .changed()
+        {
+            config.set_bool("csv_utf8_bom".to_string(), bom);
+        }
+    });
+}
+
+/// 以当前配置创建处理任务并加入队列
+fn enqueue_job(app: &mut IntegratedPowerApp, config: &crate::models::ProcessorConfig) {
+    let (Some(input_dir), Some(output_dir)) = (config.input_path.as_deref(), config.output_dir.as_deref()) else {
+        app.error_message = Some("加入队列需要设置输入文件夹和输出目录".to_string());
+        return;
+    };
+    let Some(processor_id) = app.selected_processor.clone() else {
+        return;
+    };
+    let profile = app.config_manager.get_config().profile_spec(&processor_id);
+    let Some(processor) = app
+        .processor_manager
+        .create_configured_processor(&processor_id, config, profile)
+    else {
+        app.error_message = Some("该处理器暂不支持队列处理".to_string());
+        return;
+    };
+
+    let options = configured_batch_options(app, config, &processor_id);
+    app.job_queue.enqueue(processor, input_dir, output_dir, options);
+}
+
+/// 处理器配置加上全局设置（内存预算、空输入、输出校验）得到的批量处理选项
+fn configured_batch_options(
+    app: &IntegratedPowerApp,
+    config: &crate::models::ProcessorConfig,
+    processor_id: &str,
+) -> crate::engine::data_engine::BatchOptions {
+    let app_config = app.config_manager.get_config();
+    let mut options = config.batch_options(processor_id);
+    options.memory_budget = app_config.memory_budget();
+    options.fail_on_empty_input = app_config.fail_on_empty_input;
+    options.verify_output = app_config.verify_output;
+    options
+}
+
+/// 以当前配置在后台开始处理：文件夹输入批量处理，单个文件写出到输出目录下的输出文件名
+///
+/// 运行期间 `processing_state` 为 `Processing`（可在处理视图中取消），
+/// 结束后由 `poll_main_run` 设为 `Completed` 或 `Error`。
+fn start_processing(app: &mut IntegratedPowerApp, config: &crate::models::ProcessorConfig) {
+    let (Some(processor_id), Some(input_path)) = (app.selected_processor.clone(), config.input_path.clone()) else {
+        return;
+    };
+    let profile = app.config_manager.get_config().profile_spec(&processor_id);
+    let Some(processor) = app
+        .processor_manager
+        .create_configured_processor(&processor_id, config, profile)
+    else {
+        app.error_message = Some("该处理器暂不支持直接处理".to_string());
+        return;
+    };
+
+    let options = configured_batch_options(app, config, &processor_id);
+    let cancel = options.cancel.clone();
+    let processor_name = processor.name().to_string();
+    let run_input = input_path.clone();
+    crate::log_info!("开始处理: {} — {}", processor_name, input_path.display());
+    let task = if input_path.is_dir() {
+        let Some(output_dir) = config.output_dir.clone() else {
+            return;
+        };
+        app.task_manager.spawn("批量处理", move |progress| async move {
+            let process = crate::processor::blocking_process(processor, crate::processor::current_runtime()?);
+            crate::engine::data_engine::DataEngine::process_batch(
+                &input_path,
+                &output_dir,
+                process,
+                move |p| progress.send(p),
+                &options,
+            )
+            .await
+        })
     } else {
-        let query = app.search_query.to_lowercase();
-        processors
-            .into_iter()
-            .filter(|p| {
-                p.name.to_lowercase().contains(&query)
-                    || p.description.to_lowercase().contains(&query)
-            })
-            .collect()
+        let Some(output_path) = config.single_output_path() else {
+            return;
+        };
+        app.task_manager.spawn("处理文件", move |_progress| async move {
+            let process = crate::processor::blocking_process(processor, crate::processor::current_runtime()?);
+            crate::engine::data_engine::DataEngine::process_file(&input_path, &output_path, process, &options).await
+        })
+    };
+
+    app.main_run = Some(crate::app::MainRun {
+        processor_id,
+        processor_name,
+        input_path: run_input,
+        output_dir: config.output_dir.clone().unwrap_or_default(),
+        task,
+    });
+    app.processing_state = crate::models::ProcessingState::Processing { cancel };
+    app.progress = crate::models::ProcessingProgress::default();
+    app.current_view = crate::models::AppView::Processing;
+}
+
+/// 功能列表的显示内容
+#[derive(Debug, Clone)]
+enum FunctionListModel {
+    /// 没有注册任何处理器（与搜索无结果区分）
+    NoProcessors,
+    /// 有处理器但没有匹配搜索词的
+    NoMatches { query: String },
+    /// 按搜索词筛选后的处理器
+    Items(Vec<ProcessorInfo>),
+}
+
+fn get_filtered_processors(app: &IntegratedPowerApp) -> FunctionListModel {
+    function_list_model(app.processor_manager.list_processors(), &app.search_query)
+}
+
+fn function_list_model(processors: Vec<ProcessorInfo>, query: &str) -> FunctionListModel {
+    if processors.is_empty() {
+        return FunctionListModel::NoProcessors;
     }
+    let ranked = rank_processors(processors, query);
+    if ranked.is_empty() {
+        return FunctionListModel::NoMatches {
+            query: query.trim().to_string(),
+        };
+    }
+    FunctionListModel::Items(ranked)
+}
+
+/// 未设置分类的处理器归入的分组
+const UNCATEGORIZED: &str = "其他";
+
+/// 按分类分组，分组顺序按首个处理器出现的顺序（排序结果因此保持），“其他”放在最后
+fn group_by_category(processors: Vec<ProcessorInfo>) -> Vec<(String, Vec<ProcessorInfo>)> {
+    let mut groups: Vec<(String, Vec<ProcessorInfo>)> = Vec::new();
+    let mut uncategorized = Vec::new();
+
+    for processor in processors {
+        let Some(category) = processor.category.clone().filter(|c| !c.trim().is_empty()) else {
+            uncategorized.push(processor);
+            continue;
+        };
+        match groups.iter_mut().find(|(name, _)| *name == category) {
+            Some((_, items)) => items.push(processor),
+            None => groups.push((category, vec![processor])),
+        }
+    }
+
+    if !uncategorized.is_empty() {
+        groups.push((UNCATEGORIZED.to_string(), uncategorized));
+    }
+    groups
+}
+
+/// 按模糊匹配得分筛选并排序处理器，名称匹配优先于描述和标签匹配
+fn rank_processors(processors: Vec<ProcessorInfo>, query: &str) -> Vec<ProcessorInfo> {
+    if query.trim().is_empty() {
+        return processors;
+    }
+
+    let mut scored: Vec<(u32, ProcessorInfo)> = processors
+        .into_iter()
+        .filter_map(|p| {
+            let name_score = fuzzy_score(query, &p.name).map(|s| s * 2);
+            let desc_score = fuzzy_score(query, &p.description);
+            let tag_score = fuzzy_score(query, &p.tags.join(" "));
+            name_score.max(desc_score).max(tag_score).map(|score| (score, p))
+        })
+        .collect();
+
+    // 稳定排序，得分相同时保持原有顺序
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, p)| p).collect()
+}
+
+/// 模糊匹配得分，不匹配时返回 None
+///
+/// 查询按空白拆分为多个词，每个词都必须匹配（词序不限）：
+/// 连续子串匹配得分最高，其次是按顺序出现的子序列匹配，相邻字符连续匹配会加分。
+fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut total = 0;
+
+    for word in query.to_lowercase().split_whitespace() {
+        let word: Vec<char> = word.chars().collect();
+        total += word_score(&word, &text)?;
+    }
+
+    Some(total)
+}
+
+fn word_score(word: &[char], text: &[char]) -> Option<u32> {
+    if word.is_empty() {
+        return Some(0);
+    }
+
+    // 连续子串匹配
+    if let Some(pos) = text.windows(word.len()).position(|w| w == word) {
+        let exact_bonus = if pos == 0 { 50 } else { 0 };
+        return Some(100 + exact_bonus + word.len() as u32 * 10);
+    }
+
+    // 子序列匹配
+    let mut score = 0;
+    let mut text_pos = 0;
+    let mut last_match: Option<usize> = None;
+    for &c in word {
+        let offset = text[text_pos..].iter().position(|&t| t == c)?;
+        let matched = text_pos + offset;
+        score += if last_match == Some(matched.wrapping_sub(1)) { 5 } else { 1 };
+        last_match = Some(matched);
+        text_pos = matched + 1;
+    }
+
+    Some(score)
 }
 
 fn render_compact_card(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, processor: &ProcessorInfo) {
@@ -130,6 +496,15 @@ fn render_compact_card(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, processo
             app.selected_processor = None;
         } else {
             app.selected_processor = Some(processor.id.clone());
+
+            // 预填从最近文件中选中的输入路径
+            if let Some(path) = app.pending_input_path.take() {
+                let config = app.processor_configs.get_or_create(&processor.id);
+                config.input_type = crate::models::InputType::File;
+                config.input_path = Some(path);
+                config.available_sheets.clear();
+                app.processor_configs.mark_changed();
+            }
         }
     }
 
@@ -138,7 +513,33 @@ fn render_compact_card(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, processo
     }
 }
 
-fn render_empty_state(ui: &mut egui::Ui) {
+/// 最近文件芯片（主页空态中可点击的快捷入口）
+#[derive(Debug, Clone, PartialEq)]
+struct RecentFileChip {
+    /// 完整路径
+    path: std::path::PathBuf,
+    /// 显示文本（文件名）
+    label: String,
+    /// 文件是否仍然存在
+    exists: bool,
+}
+
+/// 根据最近使用的输入文件构建芯片列表
+fn build_recent_file_chips(paths: &[std::path::PathBuf]) -> Vec<RecentFileChip> {
+    paths
+        .iter()
+        .map(|path| RecentFileChip {
+            path: path.clone(),
+            label: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string()),
+            exists: path.exists(),
+        })
+        .collect()
+}
+
+fn render_empty_state(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
     ui.vertical_centered(|ui| {
         ui.add_space(100.0);
         ui.label(
@@ -152,35 +553,62 @@ fn render_empty_state(ui: &mut egui::Ui) {
                 .size(14.0)
                 .color(ui.visuals().weak_text_color()),
         );
+
+        let chips = build_recent_file_chips(&app.config_manager.get_config().recent_input_files);
+        if chips.is_empty() {
+            return;
+        }
+
+        ui.add_space(40.0);
+        ui.label(egui::RichText::new("🕘 最近使用的文件").size(15.0).strong());
+        ui.add_space(10.0);
+
+        ui.horizontal_wrapped(|ui| {
+            for chip in chips {
+                let is_pending = app.pending_input_path.as_ref() == Some(&chip.path);
+                let response = ui
+                    .add_enabled(
+                        chip.exists,
+                        egui::SelectableLabel::new(is_pending, format!("📄 {}", chip.label)),
+                    )
+                    .on_hover_text(chip.path.display().to_string())
+                    .on_disabled_hover_text(format!("文件不存在: {}", chip.path.display()));
+
+                if response.clicked() {
+                    app.pending_input_path = if is_pending { None } else { Some(chip.path) };
+                }
+            }
+        });
+
+        if app.pending_input_path.is_some() {
+            ui.add_space(8.0);
+            ui.label(
+                egui::RichText::new("选择处理功能后将自动填入该输入文件")
+                    .size(12.0)
+                    .color(ui.visuals().weak_text_color()),
+            );
+        }
     });
 }
 
 fn render_config_panel(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, processor_id: &str) {
+    // Ctrl+Z 撤销配置修改（输入框获得焦点时交给输入框自身的撤销）
+    let nothing_focused = ui.ctx().memory(|m| m.focused().is_none());
+    if nothing_focused
+        && ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z))
+    {
+        app.processor_configs.undo(processor_id);
+    }
+
     // 获取或创建配置
     let config = app.processor_configs.get_or_create(processor_id).clone();
+    let capabilities = app.processor_manager.get_capabilities(processor_id);
     
     egui::ScrollArea::vertical().show(ui, |ui| {
         ui.add_space(10.0);
         
         // 功能标题
-        let (name, icon, description) = match processor_id {
-            "cargo_analysis" => (
-                "导出货物分析表",
-                "📦",
-                "分析货物数据并生成详细的分析报表"
-            ),
-            "auxiliary_material" => (
-                "辅材处理",
-                "🔧",
-                "处理和整理辅材相关数据"
-            ),
-            "excel_structure_analyzer" => (
-                "Excel结构分析器",
-                "🔍",
-                "分析Excel文件的单个 Sheet 结构，结果输出到日志面板"
-            ),
-            _ => ("未知功能", "❓", ""),
-        };
+        let (name, icon, description) = config_header(&app.processor_manager, processor_id);
         
         ui.horizontal(|ui| {
             ui.label(egui::RichText::new(icon).size(36.0));
@@ -205,43 +633,146 @@ fn render_config_panel(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, processo
 
         // 输入输出配置
         let mut updated_config = config.clone();
-        render_io_section(ui, &mut updated_config, processor_id);
+        render_io_section(ui, &mut updated_config, capabilities);
+
+        // 记录新选择的输入文件到最近文件列表
+        if updated_config.input_path != config.input_path {
+            if let Some(path) = updated_config.input_path.clone().filter(|p| p.is_file()) {
+                app.remember_recent_input(path);
+            }
+        }
 
         ui.add_space(30.0);
 
         // 功能配置
-        render_function_config(ui, processor_id, &mut updated_config);
+        if let Some(stats) = render_function_config(app, ui, processor_id, &mut updated_config) {
+            app.last_identification_stats = Some(stats);
+        }
 
         ui.add_space(40.0);
 
-        // 保存配置按钮
-        if ui.button("💾 保存配置").clicked() {
-            *app.processor_configs.get_or_create(processor_id) = updated_config.clone();
-            if let Err(e) = app.save_processor_configs() {
-                app.error_message = Some(format!("保存配置失败: {}", e));
+        let mut undo_requested = false;
+        let mut reset_confirmed = false;
+        let confirm_reset_id = egui::Id::new(("confirm_reset_config", processor_id));
+        let mut confirming_reset = ui.data(|d| d.get_temp::<bool>(confirm_reset_id).unwrap_or(false));
+
+        ui.horizontal(|ui| {
+            // 撤销按钮
+            if ui
+                .add_enabled(app.processor_configs.can_undo(processor_id), egui::Button::new("↩ 撤销"))
+                .on_hover_text("撤销上一次配置修改 (Ctrl+Z)")
+                .clicked()
+            {
+                undo_requested = true;
+            }
+
+            // 保存配置按钮
+            if ui.button("💾 保存配置").clicked() {
+                *app.processor_configs.get_or_create(processor_id) = updated_config.clone();
+                if let Err(e) = app.save_processor_configs() {
+                    app.report_error("保存配置失败", e);
+                }
             }
+
+            // 导出配置按钮
+            if ui.button("📤 导出配置").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("JSON 文件", &["json"])
+                    .set_file_name(format!("{}.json", processor_id))
+                    .save_file()
+                {
+                    match updated_config.export_to_file(processor_id, &path) {
+                        Ok(_) => crate::log_info!("处理器配置已导出到: {}", path.display()),
+                        Err(e) => app.report_error("导出配置失败", e.user_message()),
+                    }
+                }
+            }
+
+            // 导入配置按钮
+            if ui.button("📥 导入配置").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("JSON 文件", &["json"])
+                    .pick_file()
+                {
+                    match crate::models::ProcessorConfig::import_from_file(&path, processor_id) {
+                        Ok(imported) => {
+                            updated_config = imported;
+                            crate::log_info!("已从 {} 导入处理器配置", path.display());
+                            // 导出时的 Sheet 列表可能已过时，按本机的输入文件重新读取
+                            if updated_config.input_path.as_ref().is_some_and(|p| p.is_file()) {
+                                if let Err(e) =
+                                    updated_config.load_sheets_from_file(capabilities.requires_sheet_selection)
+                                {
+                                    crate::log_warning!("重新加载 Sheet 列表失败: {}", e);
+                                }
+                            }
+                        }
+                        Err(e) => app.report_error("导入配置失败", e.user_message()),
+                    }
+                }
+            }
+
+            // 恢复默认按钮（需再次确认）
+            if ui
+                .button("♻ 恢复默认")
+                .on_hover_text("把该处理器的配置恢复为默认值，不影响其他处理器")
+                .clicked()
+            {
+                confirming_reset = true;
+            }
+        });
+
+        if confirming_reset {
+            ui.horizontal(|ui| {
+                ui.colored_label(ui.visuals().warn_fg_color, "确定把该处理器的配置恢复为默认值吗？");
+                if ui.button("确定").clicked() {
+                    reset_confirmed = true;
+                    confirming_reset = false;
+                }
+                if ui.button("取消").clicked() {
+                    confirming_reset = false;
+                }
+            });
         }
+        ui.data_mut(|d| d.insert_temp(confirm_reset_id, confirming_reset));
 
         ui.add_space(10.0);
 
         // 开始按钮
-        render_start_button(app, ui, &updated_config);
+        render_start_button(app, ui, &updated_config, capabilities);
 
-        // 更新配置到 app
-        *app.processor_configs.get_or_create(processor_id) = updated_config;
+        // 更新配置到 app（有变化时记录撤销快照）
+        app.processor_configs.update(processor_id, updated_config);
+        if undo_requested {
+            app.processor_configs.undo(processor_id);
+        }
+        if reset_confirmed {
+            app.processor_configs.reset(processor_id);
+            match app.save_processor_configs() {
+                Ok(_) => crate::log_info!("已恢复默认配置: {}", processor_id),
+                Err(e) => app.report_error("保存配置失败", e),
+            }
+        }
 
         ui.add_space(40.0);
     });
 }
 
-fn render_io_section(ui: &mut egui::Ui, config: &mut crate::models::ProcessorConfig, processor_id: &str) {
+fn render_io_section(
+    ui: &mut egui::Ui,
+    config: &mut crate::models::ProcessorConfig,
+    capabilities: ProcessorCapabilities,
+) {
     ui.label(egui::RichText::new("⚙️ 输入输出设置").size(18.0).strong());
     ui.add_space(15.0);
 
-    // Excel分析器只需要输入文件
-    let is_excel_analyzer = processor_id == "excel_structure_analyzer";
+    // 不支持文件夹输入的处理器只需要选择单个文件
+    let force_file = !capabilities.supports_folder_input;
+    if force_file {
+        config.input_type = crate::models::InputType::File;
+    }
     
-    if !is_excel_analyzer {
+    if !force_file {
         // 输入类型选择
         ui.horizontal(|ui| {
             ui.label("输入类型:");
@@ -253,25 +784,28 @@ fn render_io_section(ui: &mut egui::Ui, config: &mut crate::models::ProcessorCon
     }
 
     // 输入路径
-    render_input_card(ui, config, is_excel_analyzer);
+    render_input_card(ui, config, force_file);
     ui.add_space(12.0);
     
     // Sheet 选择器（仅当选择了文件时显示）
     if config.input_type == crate::models::InputType::File && config.input_path.is_some() {
-        render_sheet_selector(ui, config);
+        render_sheet_selector(ui, config, capabilities.requires_sheet_selection);
         ui.add_space(12.0);
     }
     
-    // Excel分析器不需要输出设置
-    if !is_excel_analyzer {
+    if capabilities.writes_output() {
         // 输出目录
-        render_output_card(ui, config);
-        ui.add_space(12.0);
+        if capabilities.requires_output_dir {
+            render_output_card(ui, config);
+            ui.add_space(12.0);
+        }
 
         // 输出文件名
-        render_filename_card(ui, config);
+        if capabilities.requires_output_filename {
+            render_filename_card(ui, config);
+        }
     } else {
-        // 为Excel分析器显示提示信息
+        // 不写出文件的处理器显示提示信息
         egui::Frame::none()
             .fill(ui.visuals().faint_bg_color)
             .rounding(10.0)
@@ -366,7 +900,11 @@ fn render_input_card(ui: &mut egui::Ui, config: &mut crate::models::ProcessorCon
         });
 }
 
-fn render_sheet_selector(ui: &mut egui::Ui, config: &mut crate::models::ProcessorConfig) {
+fn render_sheet_selector(
+    ui: &mut egui::Ui,
+    config: &mut crate::models::ProcessorConfig,
+    require_selection: bool,
+) {
     egui::Frame::none()
         .fill(ui.visuals().faint_bg_color)
         .rounding(10.0)
@@ -393,7 +931,7 @@ fn render_sheet_selector(ui: &mut egui::Ui, config: &mut crate::models::Processo
                             
                             if ui.button("🔄 加载").clicked() {
                                 // 从文件加载 sheet 列表
-                                match config.load_sheets_from_file() {
+                                match config.load_sheets_from_file(require_selection) {
                                     Ok(_) => {
                                         crate::log_info!("成功加载 {} 个 Sheet", config.available_sheets.len());
                                     }
@@ -408,39 +946,59 @@ fn render_sheet_selector(ui: &mut egui::Ui, config: &mut crate::models::Processo
                         // 显示 sheet 下拉选择框
                         let selected_text = config.selected_sheet.as_ref()
                             .map(|s| s.as_str())
-                            .unwrap_or("所有 Sheet");
+                            .unwrap_or(if require_selection { "请选择 Sheet" } else { "所有 Sheet" });
                         
                         egui::ComboBox::from_label("")
                             .selected_text(selected_text)
                             .show_ui(ui, |ui| {
-                                // "所有 Sheet" 选项
-                                if ui.selectable_value(&mut config.selected_sheet, None, "所有 Sheet").clicked() {
-                                    // 选中了所有 Sheet
+                                // "所有 Sheet" 选项（只处理单个 Sheet 的处理器不提供）
+                                if !require_selection {
+                                    ui.selectable_value(&mut config.selected_sheet, None, "所有 Sheet");
+                                    ui.separator();
                                 }
                                 
-                                ui.separator();
-                                
-                                // 各个 sheet 选项
-                                for sheet in config.available_sheets.clone() {
+                                // 各个 sheet 选项（默认不显示隐藏的 sheet）
+                                for sheet in config.visible_sheets() {
                                     let is_selected = config.selected_sheet.as_ref() == Some(&sheet);
-                                    if ui.selectable_label(is_selected, &sheet).clicked() {
+                                    let label = if config.is_sheet_hidden(&sheet) {
+                                        format!("{}（隐藏）", sheet)
+                                    } else {
+                                        sheet.clone()
+                                    };
+                                    if ui.selectable_label(is_selected, label).clicked() {
                                         config.selected_sheet = Some(sheet.clone());
                                     }
                                 }
                             });
                         
                         ui.add_space(4.0);
-                        ui.label(
-                            egui::RichText::new(format!("共 {} 个 Sheet", config.available_sheets.len()))
-                                .size(11.0)
-                                .color(ui.visuals().weak_text_color()),
-                        );
+                        ui.horizontal(|ui| {
+                            let hidden_count = config.hidden_sheets.len();
+                            let summary = if hidden_count > 0 {
+                                format!("共 {} 个 Sheet（{} 个隐藏）", config.available_sheets.len(), hidden_count)
+                            } else {
+                                format!("共 {} 个 Sheet", config.available_sheets.len())
+                            };
+                            ui.label(
+                                egui::RichText::new(summary)
+                                    .size(11.0)
+                                    .color(ui.visuals().weak_text_color()),
+                            );
+
+                            if hidden_count > 0 {
+                                let mut show_hidden = config.show_hidden_sheets();
+                                if ui.checkbox(&mut show_hidden, "显示隐藏的 Sheet").changed() {
+                                    config.set_bool("show_hidden_sheets".to_string(), show_hidden);
+                                }
+                            }
+                        });
                     }
                 });
                 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     if !config.available_sheets.is_empty() && ui.button("重新加载").clicked() {
                         config.available_sheets.clear();
+                        config.hidden_sheets.clear();
                         config.selected_sheet = None;
                     }
                 });
@@ -514,94 +1072,585 @@ fn render_filename_card(ui: &mut egui::Ui, config: &mut crate::models::Processor
                             .hint_text("例如: 结果.xlsx")
                             .desired_width(ui.available_width() - 120.0),
                     );
+
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label("输出格式:");
+                        egui::ComboBox::from_id_source("output_format")
+                            .selected_text(config.output_format.label())
+                            .show_ui(ui, |ui| {
+                                for format in crate::models::OutputFormat::ALL {
+                                    ui.selectable_value(&mut config.output_format, format, format.label());
+                                }
+                            });
+
+                        if config.output_format == crate::models::OutputFormat::Xlsx {
+                            let mut mirror = config.get_bool("mirror_input_extension");
+                            if ui
+                                .checkbox(&mut mirror, "沿用输入扩展名")
+                                .on_hover_text("每个输出文件与其输入文件的扩展名一致；不支持写出 .xls 格式，.xls 输入仍输出 .xlsx")
+                                .changed()
+                            {
+                                config.set_bool("mirror_input_extension".to_string(), mirror);
+                            }
+                        }
+                    });
+
+                    if config.output_format == crate::models::OutputFormat::Csv {
+                        render_csv_options(ui, config);
+                    }
+
+                    // 文件夹模式下每个文件单独输出，可配置文件名模式
+                    if config.input_type == crate::models::InputType::Folder {
+                        ui.add_space(8.0);
+                        ui.label(egui::RichText::new("批量输出文件名模式").size(13.0));
+                        ui.add_space(4.0);
+
+                        let mut pattern = config.get_string("output_name_pattern");
+                        if ui
+                            .add(
+                                egui::TextEdit::singleline(&mut pattern)
+                                    .hint_text("留空沿用输入文件名，例如: {date}_{name}.xlsx")
+                                    .desired_width(ui.available_width() - 120.0),
+                            )
+                            .changed()
+                        {
+                            config.set_string("output_name_pattern".to_string(), pattern);
+                        }
+
+                        ui.label(
+                            egui::RichText::new("可用占位符: {name} 原文件名、{date} 日期、{processor} 处理器")
+                                .size(11.0)
+                                .color(ui.visuals().weak_text_color()),
+                        );
+
+                        if let Some(pattern) = config.output_name_pattern() {
+                            if let Err(e) = crate::engine::validate_output_name_pattern(&pattern, config.conflict_strategy()) {
+                                ui.label(
+                                    egui::RichText::new(format!("⚠ {}", e.user_message()))
+                                        .size(11.0)
+                                        .color(ui.visuals().warn_fg_color),
+                                );
+                            }
+                        }
+                    }
                 });
             });
         });
 }
 
-fn render_function_config(ui: &mut egui::Ui, processor_id: &str, config: &mut crate::models::ProcessorConfig) {
+/// 渲染功能选项，进行了识别预览时返回其统计信息
+fn render_function_config(
+    app: &mut IntegratedPowerApp,
+    ui: &mut egui::Ui,
+    processor_id: &str,
+    config: &mut crate::models::ProcessorConfig,
+) -> Option<crate::models::IdentificationStatistics> {
+    let mut stats = None;
+
     ui.label(egui::RichText::new("📝 处理选项").size(18.0).strong());
     ui.add_space(15.0);
 
+    let schema = crate::models::OptionsSchema::for_processor(processor_id);
+
     egui::Frame::none()
         .fill(ui.visuals().faint_bg_color)
         .rounding(8.0)
         .inner_margin(16.0)
         .show(ui, |ui| {
-            match processor_id {
-                "cargo_analysis" => {
-                    ui.label("📊 分析选项");
-                    ui.add_space(10.0);
-                    
-                    let mut include_summary = config.get_bool("include_summary");
-                    if ui.checkbox(&mut include_summary, "包含统计汇总").changed() {
-                        config.set_bool("include_summary".to_string(), include_summary);
-                    }
-                    
-                    let mut generate_charts = config.get_bool("generate_charts");
-                    if ui.checkbox(&mut generate_charts, "生成趋势图表").changed() {
-                        config.set_bool("generate_charts".to_string(), generate_charts);
-                    }
-                    
-                    let mut export_logs = config.get_bool("export_logs");
-                    if ui.checkbox(&mut export_logs, "导出详细日志").changed() {
-                        config.set_bool("export_logs".to_string(), export_logs);
-                    }
+            if !schema.title.is_empty() {
+                ui.label(schema.title);
+                ui.add_space(10.0);
+            }
+
+            render_schema_options(ui, &schema, config);
+
+            if schema.batch_options {
+                render_batch_options(ui, config);
+            }
+
+            if schema.row_type_priority {
+                ui.add_space(10.0);
+                stats = render_row_type_priority_editor(app, ui, processor_id, config);
+            }
+
+            if schema.row_type_filter {
+                ui.add_space(10.0);
+                render_row_type_filter(ui, config);
+            }
+
+            if let Some(note) = schema.note {
+                ui.add_space(10.0);
+                ui.label(egui::RichText::new(note)
+                    .size(12.0)
+                    .color(ui.visuals().weak_text_color()));
+            }
+        });
+
+    stats
+}
+
+/// 按选项描述渲染控件，修改后的值写回配置
+fn render_schema_options(
+    ui: &mut egui::Ui,
+    schema: &crate::models::OptionsSchema,
+    config: &mut crate::models::ProcessorConfig,
+) {
+    use crate::models::{ConfigValue, OptionKind};
+
+    for spec in &schema.options {
+        match (spec.kind, spec.read(config)) {
+            (OptionKind::Bool, ConfigValue::Bool(mut value)) => {
+                if ui.checkbox(&mut value, spec.label).changed() {
+                    config.set_bool(spec.key.to_string(), value);
                 }
-                "auxiliary_material" => {
-                    ui.label("🔧 处理选项");
-                    ui.add_space(10.0);
-                    
-                    let mut auto_classify = config.get_bool("auto_classify");
-                    if ui.checkbox(&mut auto_classify, "自动分类").changed() {
-                        config.set_bool("auto_classify".to_string(), auto_classify);
-                    }
-                    
-                    let mut remove_duplicates = config.get_bool("remove_duplicates");
-                    if ui.checkbox(&mut remove_duplicates, "去除重复项").changed() {
-                        config.set_bool("remove_duplicates".to_string(), remove_duplicates);
+            }
+            (OptionKind::Int { min, max }, ConfigValue::Int(mut value)) => {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}:", spec.label));
+                    if ui
+                        .add(egui::DragValue::new(&mut value).range(min..=max))
+                        .changed()
+                    {
+                        config.set_int(spec.key.to_string(), value);
                     }
-                    
-                    let mut generate_summary = config.get_bool("generate_summary");
-                    if ui.checkbox(&mut generate_summary, "生成汇总表").changed() {
-                        config.set_bool("generate_summary".to_string(), generate_summary);
+                });
+            }
+            (OptionKind::String, ConfigValue::String(mut value)) => {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}:", spec.label));
+                    if ui.text_edit_singleline(&mut value).changed() {
+                        config.set_string(spec.key.to_string(), value);
                     }
+                });
+            }
+            (_, value) => {
+                crate::log_warning!("选项 {} 的默认值类型与声明不符: {:?}", spec.key, value);
+            }
+        }
+    }
+}
+
+/// 配置面板标题信息（名称、图标、描述），取自处理器管理器
+fn config_header(
+    manager: &crate::processor::ProcessorManager,
+    processor_id: &str,
+) -> (String, String, String) {
+    match manager.get_info(processor_id) {
+        Some(info) => (
+            info.name,
+            info.icon.unwrap_or_else(|| "📦".to_string()),
+            info.description,
+        ),
+        None => ("未知功能".to_string(), "❓".to_string(), String::new()),
+    }
+}
+
+fn render_batch_options(ui: &mut egui::Ui, config: &mut crate::models::ProcessorConfig) {
+    let mut continue_on_error = config.continue_on_error();
+    if ui
+        .checkbox(&mut continue_on_error, "单个文件失败后继续处理")
+        .on_hover_text("关闭后，批量处理遇到第一个失败的文件即停止")
+        .changed()
+    {
+        config.set_bool("continue_on_error".to_string(), continue_on_error);
+    }
+
+    let mut preserve_input_order = config.preserve_input_order();
+    if ui
+        .checkbox(&mut preserve_input_order, "按输入顺序汇总输出")
+        .on_hover_text("并行处理时仍按输入文件顺序排列输出；关闭则按完成顺序")
+        .changed()
+    {
+        config.set_bool("preserve_input_order".to_string(), preserve_input_order);
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("输出文件已存在时:");
+        let mut strategy = config.conflict_strategy();
+        egui::ComboBox::from_id_source("conflict_strategy")
+            .selected_text(strategy.label())
+            .show_ui(ui, |ui| {
+                for option in crate::engine::ConflictStrategy::ALL {
+                    ui.selectable_value(&mut strategy, option, option.label());
                 }
-                "excel_structure_analyzer" => {
-                    ui.label("🔍 分析选项");
-                    ui.add_space(10.0);
+            });
+        if strategy != config.conflict_strategy() {
+            config.set_string("conflict_strategy".to_string(), strategy.key().to_string());
+        }
+    });
 
-                    let mut analyze_colors = config.get_bool("analyze_colors");
-                    if ui.checkbox(&mut analyze_colors, "分析颜色信息").changed() {
-                        config.set_bool("analyze_colors".to_string(), analyze_colors);
-                    }
+    ui.horizontal(|ui| {
+        ui.label("单个文件超时（秒，0 为不限）:");
+        let mut timeout_secs = config.file_timeout().map(|d| d.as_secs()).unwrap_or(0);
+        if ui
+            .add(egui::DragValue::new(&mut timeout_secs).range(0..=3600))
+            .changed()
+        {
+            config.set_int("file_timeout_secs".to_string(), timeout_secs as i64);
+        }
+    });
 
-                    let mut analyze_structure = config.get_bool("analyze_structure");
-                    if ui.checkbox(&mut analyze_structure, "分析表格结构").changed() {
-                        config.set_bool("analyze_structure".to_string(), analyze_structure);
-                    }
-                    
-                    let mut detailed_output = config.get_bool("detailed_output");
-                    if ui.checkbox(&mut detailed_output, "详细输出模式").changed() {
-                        config.set_bool("detailed_output".to_string(), detailed_output);
+    ui.horizontal(|ui| {
+        ui.label("失败超过（个，0 为不限）:");
+        let mut threshold = config.fail_fast_threshold().unwrap_or(0);
+        if ui
+            .add(egui::DragValue::new(&mut threshold).range(0..=10000))
+            .on_hover_text("失败文件数超过该值时中止批量处理，不再处理剩余文件")
+            .changed()
+        {
+            config.set_int("fail_fast_threshold".to_string(), threshold as i64);
+        }
+    });
+}
+
+/// 输出行类型多选，都不勾选时输出汇总
+fn render_row_type_filter(ui: &mut egui::Ui, config: &mut crate::models::ProcessorConfig) {
+    use crate::models::RowType;
+
+    ui.label("📑 只输出以下类型的行（都不勾选时输出汇总）");
+    ui.add_space(6.0);
+
+    let mut kept = config.kept_row_types();
+    let mut changed = false;
+    ui.horizontal_wrapped(|ui| {
+        for row_type in RowType::BUILTIN {
+            let mut selected = kept.contains(&row_type);
+            if ui.checkbox(&mut selected, row_type.display_name()).changed() {
+                if selected {
+                    kept.push(row_type);
+                } else {
+                    kept.retain(|t| *t != row_type);
+                }
+                changed = true;
+            }
+        }
+    });
+
+    if changed {
+        // 按内置顺序保存，勾选顺序不影响配置
+        kept.sort_by_key(|t| RowType::BUILTIN.iter().position(|b| b == t));
+        config.set_kept_row_types(&kept);
+    }
+}
+
+/// 行类型识别顺序编辑器：拖动调整优先级，可用当前输入文件预览识别效果
+///
+/// 进行了识别预览时返回其统计信息。
+fn render_row_type_priority_editor(
+    app: &mut IntegratedPowerApp,
+    ui: &mut egui::Ui,
+    processor_id: &str,
+    config: &mut crate::models::ProcessorConfig,
+) -> Option<crate::models::IdentificationStatistics> {
+    let mut stats = None;
+
+    let mut profile = configured_profile(app, processor_id, config);
+
+    ui.label("🔀 识别优先级（拖动调整，靠前的先匹配）");
+    ui.add_space(6.0);
+
+    let mut moved: Option<(usize, usize)> = None;
+    for (index, definition) in profile.row_type_definitions.iter().enumerate() {
+        let item_id = egui::Id::new(("row_type_priority", index));
+        let response = ui
+            .dnd_drag_source(item_id, index, |ui| {
+                ui.label(format!("☰ [{}] {}", definition.priority, definition.display_name))
+                    .on_hover_text(&definition.description);
+            })
+            .response;
+
+        if let Some(from) = response.dnd_release_payload::<usize>() {
+            moved = Some((*from, index));
+        }
+    }
+
+    if let Some((from, to)) = moved {
+        profile.move_row_type(from, to);
+        config.set_row_type_order(&profile.row_type_order());
+    }
+
+    // 拖动调整只保存为本处理器的识别顺序，配置来源是 TOML 文件时可以写回文件
+    let profile_file = app
+        .recognition_profile
+        .as_ref()
+        .and_then(|loaded| loaded.file_path())
+        .map(std::path::Path::to_path_buf);
+    match profile_file {
+        Some(path) => {
+            let stale = app.recognition_profile.as_ref().is_some_and(|loaded| loaded.is_stale());
+            if stale {
+                ui.colored_label(ui.visuals().warn_fg_color, "⚠ 识别配置文件已被修改，重新加载后生效");
+            }
+            if ui
+                .button("🔄 重新加载识别配置")
+                .on_hover_text(format!("重新读取 {}，无需重启程序", path.display()))
+                .clicked()
+            {
+                if let Some(loaded) = app.recognition_profile.as_mut() {
+                    loaded.reload();
+                }
+                crate::log_info!("已重新加载识别配置 {}", path.display());
+            }
+
+            let has_override = !config.row_type_order().is_empty();
+            if ui
+                .add_enabled(has_override, egui::Button::new("💾 写入识别配置文件"))
+                .on_hover_text(format!("把当前顺序写入 {}，其他使用该配置的处理器也会生效", path.display()))
+                .on_disabled_hover_text("当前顺序与识别配置文件一致")
+                .clicked()
+            {
+                match crate::engine::FileTypeProfile::save_row_type_order(&path, &profile.row_type_order()) {
+                    Ok(()) => {
+                        crate::log_info!("识别顺序已写入 {}", path.display());
+                        config.set_row_type_order(&[]);
+                        app.recognition_profile = None;
                     }
-                    
-                    ui.add_space(10.0);
-                    ui.label(egui::RichText::new("💡 提示: 分析结果将输出到日志面板")
-                        .size(12.0)
-                        .color(ui.visuals().weak_text_color()));
+                    Err(e) => crate::log_error!("写入识别配置失败: {}", e),
+                }
+            }
+        }
+        None => {
+            ui.label(egui::RichText::new("调整后的顺序只保存在本处理器的设置中，不会修改内置识别配置").weak());
+        }
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button("恢复默认顺序").clicked() {
+            config.set_row_type_order(&[]);
+        }
+
+        if ui
+            .button("✅ 验证配置")
+            .on_hover_text("选择包含同名 .xlsx 样本和 .json 标注的文件夹，检查当前顺序能否复现标注")
+            .clicked()
+        {
+            if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                verify_profile_against_samples(profile.clone(), &dir);
+            }
+        }
+
+        let comparing = app.file_compare_task.is_some();
+        if ui
+            .add_enabled(!comparing, egui::Button::new("🆚 对比两个文件"))
+            .on_hover_text("依次选择旧版本和新版本，差异输出到日志，可另存为标出差异的工作簿")
+            .clicked()
+        {
+            compare_two_files(app, profile.clone());
+        }
+        if comparing {
+            ui.spinner();
+        }
+
+        let can_preview = config.input_path.as_ref().is_some_and(|p| p.is_file());
+        let preview = ui
+            .add_enabled(can_preview, egui::Button::new("预览识别结果"))
+            .on_disabled_hover_text("请先选择输入文件")
+            .clicked();
+        let copy = ui
+            .add_enabled(can_preview, egui::Button::new("📋 复制为 Markdown 表格"))
+            .on_disabled_hover_text("请先选择输入文件")
+            .clicked();
+
+        if preview || copy {
+            if let Some((results, rows)) = preview_row_identification(config, profile) {
+                if copy {
+                    let table = crate::engine::to_markdown_table(&results);
+                    ui.output_mut(|o| o.copied_text = table);
+                    crate::log_info!("已复制 {} 行识别结果到剪贴板", results.len());
                 }
-                _ => {}
+                stats = Some(crate::models::IdentificationStatistics::from_results(&results));
+                let preview: PreviewRows = std::sync::Arc::new(results.into_iter().zip(rows).collect());
+                ui.data_mut(|d| d.insert_temp(preview_rows_id(), preview));
             }
+        }
+    });
+
+    render_preview_rows(ui);
+
+    stats
+}
+
+/// 选择两个版本的文件，在后台按当前识别顺序对比
+///
+/// 对比结果由 [`IntegratedPowerApp`] 轮询后写入日志，有单元格变化时可另存差异工作簿。
+fn compare_two_files(app: &mut IntegratedPowerApp, profile: crate::engine::FileTypeProfile) {
+    let pick = |title: &str| {
+        rfd::FileDialog::new()
+            .set_title(title)
+            .add_filter("Excel 文件", &["xlsx"])
+            .pick_file()
+    };
+    let (Some(old), Some(new)) = (pick("选择旧版本"), pick("选择新版本")) else {
+        return;
+    };
+
+    crate::log_info!("正在对比 {} -> {}", old.display(), new.display());
+    let task = app.task_manager.spawn_blocking("对比文件", move |_progress| {
+        let identifier = crate::engine::RowTypeIdentifier::new(profile);
+        crate::engine::compare_files(&old, &new, &identifier)
+            .map(|comparison| crate::app::FileComparison { old, new, comparison })
+            .map_err(|e| crate::error::AppError::processing_error(e.to_string()))
+    });
+    app.file_compare_task = Some(task);
+}
+
+/// 最近一次识别预览的结果及对应的原始行数据
+type PreviewRows = std::sync::Arc<Vec<(crate::models::RowIdentificationResult, crate::models::RowData)>>;
+
+fn preview_rows_id() -> egui::Id {
+    egui::Id::new("row_type_preview_rows")
+}
+
+/// 逐行列出识别预览结果，每行可复制原始数据的 JSON 以便反馈问题
+fn render_preview_rows(ui: &mut egui::Ui) {
+    let Some(preview) = ui.data(|d| d.get_temp::<PreviewRows>(preview_rows_id())) else {
+        return;
+    };
+
+    egui::CollapsingHeader::new(format!("识别结果 ({} 行)", preview.len()))
+        .id_source("row_type_preview_list")
+        .show(ui, |ui| {
+            egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                for (result, row) in preview.iter() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "第 {} 行 · {} · {}",
+                            result.row_index + 1,
+                            result.row_type.display_name(),
+                            result.matched_rule
+                        ));
+                        if ui
+                            .small_button("复制 JSON")
+                            .on_hover_text("复制该行的内容、颜色和合并信息")
+                            .clicked()
+                        {
+                            ui.output_mut(|o| o.copied_text = row.to_debug_json());
+                            crate::log_info!("已复制第 {} 行的 JSON 到剪贴板", result.row_index + 1);
+                        }
+                    });
+                }
+            });
         });
 }
 
-fn render_start_button(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, config: &crate::models::ProcessorConfig) {
+/// 处理器当前使用的识别配置，并应用该处理器调整后的识别顺序
+///
+/// 与 `ProcessorManager` 创建处理器时一致：使用设置中为该处理器指定的配置，
+/// 未指定或无法加载时使用内置配置。
+fn configured_profile(
+    app: &mut IntegratedPowerApp,
+    processor_id: &str,
+    config: &crate::models::ProcessorConfig,
+) -> crate::engine::FileTypeProfile {
+    let spec = app.config_manager.get_config().profile_spec(processor_id);
+    if app.recognition_profile.as_ref().is_some_and(|loaded| !loaded.is_for(spec)) {
+        app.recognition_profile = None;
+    }
+    let loaded = app
+        .recognition_profile
+        .get_or_insert_with(|| crate::engine::LoadedProfile::load(spec));
+    let mut profile = loaded.profile().clone();
+    profile.apply_row_type_order(&config.row_type_order());
+    profile
+}
+
+/// 用当前顺序识别标注样本，把不一致的行输出到日志
+fn verify_profile_against_samples(profile: crate::engine::FileTypeProfile, dir: &std::path::Path) {
+    let identifier = crate::engine::RowTypeIdentifier::new(profile);
+
+    match crate::engine::verify_samples(dir, &identifier) {
+        Ok(report) => report.log(),
+        Err(e) => crate::log_error!("验证配置失败: {}", e),
+    }
+}
+
+/// 用当前顺序重新识别输入文件，把统计结果输出到日志并返回识别结果及原始行数据
+fn preview_row_identification(
+    config: &crate::models::ProcessorConfig,
+    profile: crate::engine::FileTypeProfile,
+) -> Option<(Vec<crate::models::RowIdentificationResult>, Vec<crate::models::RowData>)> {
+    let path = config.input_path.as_ref()?;
+    let sheet_index = config
+        .selected_sheet
+        .as_ref()
+        .and_then(|name| config.available_sheets.iter().position(|s| s == name))
+        .unwrap_or(0);
+
+    let options = crate::engine::ExtractOptions {
+        normalization: config.text_normalization(),
+        ..Default::default()
+    };
+    match crate::engine::ExcelExtractor::read_worksheet_with_options(path, sheet_index, &options) {
+        Ok(worksheet) => {
+            let identifier_order = profile.row_type_order();
+            let identifier = crate::engine::RowTypeIdentifier::new(profile);
+            let results = identifier.identify_all_rows(&worksheet);
+            let stats = identifier.get_statistics(&results);
+
+            crate::log_info!(
+                "识别预览 '{}': {} 行，识别成功率 {}",
+                worksheet.name,
+                stats.total_rows,
+                crate::models::format_percentage(stats.success_rate * 100.0)
+            );
+            for (row_type, count) in stats.sorted_counts() {
+                crate::log_info!("  {}: {} 行", row_type.display_name(), count);
+            }
+
+            let rule_counts = identifier.rule_match_counts(&results);
+            for definition in &identifier_order {
+                let count = rule_counts.get(definition).copied().unwrap_or(0);
+                if count == 0 {
+                    crate::log_warning!("  规则 '{}' 未命中任何行，请检查配置", definition);
+                }
+            }
+
+            Some((results, worksheet.rows))
+        }
+        Err(e) => {
+            crate::log_error!("识别预览失败: {}", e);
+            None
+        }
+    }
+}
+
+/// 返回阻止开始处理的原因，可以开始时返回 None
+///
+/// `busy` 为 [`IntegratedPowerApp::is_busy`]：主任务、对比运行或队列任务进行中时都不能开始。
+fn start_blocker(
+    config: &crate::models::ProcessorConfig,
+    capabilities: ProcessorCapabilities,
+    busy: bool,
+) -> Option<&'static str> {
+    if busy {
+        Some("正在处理中，请等待当前任务完成")
+    } else if config.input_path.is_none() {
+        Some("请选择输入文件")
+    } else if capabilities.needs_sheet_choice(config) {
+        Some("该文件包含多个 Sheet，请先选择要处理的 Sheet")
+    } else if capabilities.requires_output_dir && config.output_dir.is_none() {
+        Some("请选择输出目录")
+    } else if capabilities.requires_output_filename && config.output_filename.is_empty() {
+        Some("请输入输出文件名")
+    } else {
+        None
+    }
+}
+
+fn render_start_button(
+    app: &mut IntegratedPowerApp,
+    ui: &mut egui::Ui,
+    config: &crate::models::ProcessorConfig,
+    capabilities: ProcessorCapabilities,
+) {
     ui.vertical_centered(|ui| {
-        // Excel分析器只需要输入文件，不需要输出目录
-        let is_excel_analyzer = app.selected_processor.as_ref() == Some(&"excel_structure_analyzer".to_string());
-        let can_start = config.input_path.is_some()
-            && (is_excel_analyzer || (config.output_dir.is_some() && !config.output_filename.is_empty()));
+        // 根据处理器能力判断需要哪些输入输出设置
+        let is_excel_analyzer = app.selected_processor.as_deref() == Some("excel_structure_analyzer");
+        let is_analysis_only = !capabilities.writes_output();
+        let is_processing = app.is_busy();
+        let start_blocker = start_blocker(config, capabilities, is_processing);
+        let can_start = start_blocker.is_none();
         
         let button_color = if can_start {
             egui::Color32::from_rgb(76, 175, 80)
@@ -609,7 +1658,9 @@ fn render_start_button(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, config:
             ui.visuals().widgets.inactive.bg_fill
         };
         
-        let button_text = if is_excel_analyzer {
+        let button_text = if is_processing {
+            "⏳ 处理中..."
+        } else if is_analysis_only {
             "🔍 开始分析"
         } else {
             "🚀 开始处理"
@@ -630,12 +1681,55 @@ fn render_start_button(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, config:
         .min_size(egui::vec2(220.0, 55.0));
         
         let response = ui.add_enabled(can_start, button);
+
+        if is_processing {
+            ui.add_space(8.0);
+            ui.spinner();
+        }
+
+        // 单文件任务完成后可以直接打开输出文件
+        let last_output = match &app.processing_state {
+            crate::models::ProcessingState::Completed(result) => result.single_output_file().map(|p| p.to_path_buf()),
+            _ => None,
+        };
+        if let Some(output) = last_output {
+            ui.add_space(6.0);
+            if ui
+                .button("📄 打开输出文件")
+                .on_hover_text(output.display().to_string())
+                .clicked()
+            {
+                if let Err(e) = super::open_file::open_in_default_app(&output) {
+                    app.report_error("打开输出文件失败", e);
+                }
+            }
+        }
+
+        // 批量处理任务可以在其他任务运行时排队
+        if !is_analysis_only && config.input_path.as_deref().is_some_and(|p| p.is_dir()) {
+            ui.add_space(6.0);
+            let queued = app.job_queue.pending().count();
+            let label = if queued > 0 {
+                format!("➕ 加入队列（已排队 {}）", queued)
+            } else {
+                "➕ 加入队列".to_string()
+            };
+            if ui.button(label).clicked() {
+                enqueue_job(app, config);
+            }
+        }
+
+        // 队列任务的进度和等待列表，可取消排队中的任务
+        super::processing::render_queue(app, ui);
         
         if response.clicked() {
             if is_excel_analyzer {
                 // 执行 Excel 分析（仅处理选中的单个 Sheet）
                 if let Some(input_path) = &config.input_path {
-                    let analyzer = crate::processor::examples::ExcelStructureAnalyzer::new();
+                    let analyzer = crate::processor::examples::ExcelStructureAnalyzer::new()
+                        .with_max_rows(config.dump_max_rows())
+                        .with_colors_only(config.get_bool("colors_only"))
+                        .with_preview_rows(config.preview_rows());
                     let sheet = config.selected_sheet.as_deref();
                     let analyze_colors = config.get_bool("analyze_colors");
                     match analyzer.analyze_excel_structure(input_path, sheet, analyze_colors) {
@@ -645,28 +1739,18 @@ fn render_start_button(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, config:
                             app.log_viewer.show = true;
                         }
                         Err(e) => {
-                            app.error_message = Some(format!("Excel分析失败: {}", e));
+                            app.report_error("Excel分析失败", e);
                         }
                     }
                 }
             } else {
-                app.error_message = Some("处理逻辑将在任务 12 中实现".to_string());
+                start_processing(app, config);
             }
         }
         
         ui.add_space(8.0);
         
-        if !can_start {
-            let missing = if config.input_path.is_none() {
-                "请选择输入文件"
-            } else if !is_excel_analyzer && config.output_dir.is_none() {
-                "请选择输出目录"
-            } else if !is_excel_analyzer && config.output_filename.is_empty() {
-                "请输入输出文件名"
-            } else {
-                "未知错误"
-            };
-            
+        if let Some(missing) = start_blocker {
             ui.label(
                 egui::RichText::new(format!("⚠ {}", missing))
                     .size(13.0)
@@ -674,7 +1758,7 @@ fn render_start_button(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, config:
                     .color(ui.visuals().warn_fg_color),
             );
         } else {
-            let ready_text = if is_excel_analyzer {
+            let ready_text = if is_analysis_only {
                 "✓ 准备就绪，点击开始分析"
             } else {
                 "✓ 准备就绪，点击开始处理"
@@ -688,3 +1772,267 @@ fn render_start_button(app: &mut IntegratedPowerApp, ui: &mut egui::Ui, config:
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+
+    fn processor_info(id: &str, name: &str, description: &str) -> ProcessorInfo {
+        ProcessorInfo {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            icon: None,
+            category: None,
+            tags: Vec::new(),
+            version: "1.0.0".to_string(),
+            available: true,
+            capabilities: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_start_blocked_until_sheet_selected() {
+        let capabilities = ProcessorCapabilities::log_only();
+        let mut config = crate::models::ProcessorConfig::new("excel_structure_analyzer");
+        config.input_path = Some(PathBuf::from("/data/multi.xlsx"));
+        config.available_sheets = vec!["Sheet1".to_string(), "Sheet2".to_string()];
+        config.selected_sheet = None;
+
+        assert!(start_blocker(&config, capabilities, false).is_some());
+
+        config.selected_sheet = Some("Sheet2".to_string());
+        assert_eq!(start_blocker(&config, capabilities, false), None);
+
+        // 单 Sheet 文件无需选择
+        config.selected_sheet = None;
+        config.available_sheets = vec!["Sheet1".to_string()];
+        assert_eq!(start_blocker(&config, capabilities, false), None);
+    }
+
+    #[test]
+    fn test_start_blocked_while_processing() {
+        let capabilities = ProcessorCapabilities::log_only();
+        let mut config = crate::models::ProcessorConfig::new("excel_structure_analyzer");
+        config.input_path = Some(PathBuf::from("/data/a.xlsx"));
+        let queue = crate::processor::JobQueue::new();
+
+        let processing = crate::models::ProcessingState::Processing {
+            cancel: crate::models::CancelToken::new(),
+        };
+        let busy = IntegratedPowerApp::busy(&processing, false, &queue);
+        assert!(start_blocker(&config, capabilities, busy).is_some());
+
+        // 对比运行进行中同样不能开始
+        let idle = crate::models::ProcessingState::Idle;
+        let busy = IntegratedPowerApp::busy(&idle, true, &queue);
+        assert!(start_blocker(&config, capabilities, busy).is_some());
+
+        let done = crate::models::ProcessingState::Completed(crate::models::ProcessingResult::new(1));
+        let busy = IntegratedPowerApp::busy(&done, false, &queue);
+        assert_eq!(start_blocker(&config, capabilities, busy), None);
+    }
+
+    #[test]
+    fn test_start_blocked_while_queue_job_running() {
+        let capabilities = ProcessorCapabilities::log_only();
+        let mut config = crate::models::ProcessorConfig::new("excel_structure_analyzer");
+        config.input_path = Some(PathBuf::from("/data/a.xlsx"));
+
+        let input_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        let mut queue = crate::processor::JobQueue::new();
+        queue.enqueue(
+            std::sync::Arc::new(crate::processor::examples::DataCleaningProcessor::new()),
+            input_dir.path(),
+            output_dir.path(),
+            crate::engine::data_engine::BatchOptions::default(),
+        );
+        assert!(queue.is_running());
+
+        // 主任务空闲，但队列任务正在执行
+        let idle = crate::models::ProcessingState::Idle;
+        let busy = IntegratedPowerApp::busy(&idle, false, &queue);
+        assert!(start_blocker(&config, capabilities, busy).is_some());
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(60);
+        while !queue.is_idle() {
+            assert!(std::time::Instant::now() < deadline, "队列任务超时");
+            queue.poll();
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        let busy = IntegratedPowerApp::busy(&idle, false, &queue);
+        assert_eq!(start_blocker(&config, capabilities, busy), None);
+    }
+
+    #[test]
+    fn test_fuzzy_score_subsequence() {
+        assert!(fuzzy_score("cgo", "cargo analysis").is_some());
+        assert!(fuzzy_score("analysis cargo", "cargo analysis").is_some());
+        assert!(fuzzy_score("xyz", "cargo analysis").is_none());
+        // 连续子串得分高于子序列
+        assert!(fuzzy_score("cargo", "cargo analysis") > fuzzy_score("cgo", "cargo analysis"));
+    }
+
+    #[test]
+    fn test_function_list_empty_distinct_from_filtered() {
+        assert!(matches!(function_list_model(Vec::new(), ""), FunctionListModel::NoProcessors));
+        assert!(matches!(function_list_model(Vec::new(), "货物"), FunctionListModel::NoProcessors));
+
+        let processors = vec![processor_info("cargo_analysis", "货物分析表", "统计货物数据")];
+        match function_list_model(processors.clone(), "") {
+            FunctionListModel::Items(items) => assert_eq!(items.len(), 1),
+            other => panic!("unexpected model: {:?}", other),
+        }
+        // 有处理器但搜索无匹配时不是“未注册”状态
+        assert!(!matches!(
+            function_list_model(processors, "xyz"),
+            FunctionListModel::NoProcessors
+        ));
+    }
+
+    #[test]
+    fn test_function_list_no_matches_echoes_query() {
+        let processors = vec![
+            processor_info("cargo_analysis", "货物分析表", "统计货物数据"),
+            processor_info("excel_structure_analyzer", "Excel结构分析", "分析工作表结构"),
+        ];
+
+        match function_list_model(processors.clone(), " xyz ") {
+            FunctionListModel::NoMatches { query } => assert_eq!(query, "xyz"),
+            other => panic!("unexpected model: {:?}", other),
+        }
+        assert!(matches!(
+            function_list_model(processors, "货物"),
+            FunctionListModel::Items(items) if items.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_rank_processors_exact_match_first() {
+        let processors = vec![
+            processor_info("a", "Excel结构分析器", "分析 Excel 文件的单个 Sheet 结构"),
+            processor_info("b", "货物分析表处理", "识别货物分析表的行类型"),
+        ];
+
+        // 子序列查询也能匹配到处理器名称
+        let ranked = rank_processors(processors.clone(), "货分表");
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].id, "b");
+
+        let ranked = rank_processors(processors, "结构");
+        assert_eq!(ranked[0].id, "a");
+    }
+
+    #[test]
+    fn test_group_by_category() {
+        let categorized = |id: &str, category: Option<&str>| ProcessorInfo {
+            category: category.map(str::to_string),
+            ..processor_info(id, id, "")
+        };
+        let processors = vec![
+            categorized("a", Some("分析")),
+            categorized("b", None),
+            categorized("c", Some("数据处理")),
+            categorized("d", Some("分析")),
+            categorized("e", Some(" ")),
+        ];
+
+        let groups = group_by_category(processors);
+        let summary: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|(name, items)| (name.as_str(), items.iter().map(|p| p.id.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("分析", vec!["a", "d"]),
+                ("数据处理", vec!["c"]),
+                (UNCATEGORIZED, vec!["b", "e"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rank_processors_matches_tags() {
+        let processors = vec![
+            processor_info("a", "Excel结构分析器", "分析单个 Sheet 结构"),
+            ProcessorInfo {
+                tags: vec!["辅材".to_string(), "材料".to_string()],
+                ..processor_info("b", "材料汇总", "整理数据")
+            },
+        ];
+
+        let ranked = rank_processors(processors, "辅材");
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].id, "b");
+    }
+
+    #[test]
+    fn test_build_recent_file_chips() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("货物分析表.xlsx");
+        std::fs::write(&existing, b"").unwrap();
+        let missing = dir.path().join("missing.xlsx");
+
+        let chips = build_recent_file_chips(&[existing.clone(), missing.clone()]);
+
+        assert_eq!(chips.len(), 2);
+        assert_eq!(chips[0].path, existing);
+        assert_eq!(chips[0].label, "货物分析表.xlsx");
+        assert!(chips[0].exists);
+        assert_eq!(chips[1].label, "missing.xlsx");
+        assert!(!chips[1].exists);
+    }
+
+    #[test]
+    fn test_build_recent_file_chips_empty() {
+        let chips = build_recent_file_chips(&Vec::<PathBuf>::new());
+        assert!(chips.is_empty());
+    }
+
+    #[test]
+    fn test_config_header_matches_manager_metadata() {
+        let manager = crate::processor::ProcessorManager::new();
+
+        for info in manager.list_processors() {
+            let (name, icon, description) = config_header(&manager, &info.id);
+            assert_eq!(name, info.name);
+            assert_eq!(Some(icon), info.icon);
+            assert_eq!(description, info.description);
+        }
+
+        let (name, _, _) = config_header(&manager, "no_such_processor");
+        assert_eq!(name, "未知功能");
+    }
+
+    #[test]
+    fn test_render_schema_options_reads_back_typed_values() {
+        use crate::models::{ConfigValue, OptionSpec, OptionsSchema};
+
+        let schema = OptionsSchema {
+            title: "测试选项",
+            options: vec![
+                OptionSpec::bool("flag", "开关", true),
+                OptionSpec::int("limit", "上限", 1, 10, 5),
+            ],
+            ..Default::default()
+        };
+        let mut config = crate::models::ProcessorConfig::default();
+        config.set_int("limit".to_string(), 7);
+
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                render_schema_options(ui, &schema, &mut config);
+            });
+        });
+
+        // 没有交互时渲染不修改配置，值按声明的类型读回
+        assert_eq!(schema.options[0].read(&config), ConfigValue::Bool(true));
+        assert_eq!(schema.options[1].read(&config), ConfigValue::Int(7));
+        assert!(schema.validate(&config).is_empty());
+    }
+}
@@ -66,6 +66,12 @@ impl LogViewer {
                     if ui.button("🗑 清空").clicked() {
                         LOGGER.clear();
                     }
+                    if ui.button("清除 DEBUG").clicked() {
+                        LOGGER.clear_level(LogLevel::Debug);
+                    }
+                    if ui.button("仅保留 ERROR/WARN").clicked() {
+                        LOGGER.retain(|e| matches!(e.level, LogLevel::Error | LogLevel::Warning));
+                    }
                     if ui.button("📋 复制全部").clicked() {
                         // 拼接当前过滤后的日志文本到剪贴板
                         let entries = LOGGER.get_entries();
@@ -83,6 +89,26 @@ impl LogViewer {
                         ui.output_mut(|o| o.copied_text = all_text);
                     }
                     
+                    if ui.button("🌐 导出 HTML").clicked() {
+                        let entries = LOGGER.get_entries();
+                        let filtered: Vec<&LogEntry> = entries
+                            .iter()
+                            .filter(|entry| {
+                                self.filter_level.is_none() || self.filter_level == Some(entry.level)
+                            })
+                            .collect();
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("HTML 文件", &["html"])
+                            .set_file_name("logs.html")
+                            .save_file()
+                        {
+                            match std::fs::write(&path, to_html(&filtered)) {
+                                Ok(_) => crate::log_info!("日志已导出到: {}", path.display()),
+                                Err(e) => crate::log_error!("导出日志失败: {}", e),
+                            }
+                        }
+                    }
+
                     if ui.button("📁 打开日志文件").clicked() {
                         if let Some(path) = LOGGER.get_log_file_path_str() {
                             #[cfg(target_os = "windows")]
@@ -228,3 +254,72 @@ impl LogViewer {
             });
     }
 }
+
+// 转义 HTML 特殊字符
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 将日志条目渲染为 HTML 文档，级别颜色与日志查看器一致
+pub fn to_html(entries: &[&LogEntry]) -> String {
+    use std::fmt::Write as _;
+
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>IntegratedPower 日志</title>\n\
+         <style>body { font-family: monospace; font-size: 13px; } \
+         .ts { color: #808080; } pre { margin: 0; white-space: pre-wrap; }</style>\n\
+         </head>\n<body>\n",
+    );
+
+    for e in entries {
+        let [r, g, b, _] = e.level.color().to_array();
+        let _ = writeln!(
+            html,
+            "<pre><span class=\"ts\">{}</span> <span style=\"color: #{:02x}{:02x}{:02x}\">[{}]</span> {}</pre>",
+            escape_html(&e.timestamp),
+            r,
+            g,
+            b,
+            e.level.as_str(),
+            escape_html(&e.message)
+        );
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_html_colors_error_lines() {
+        let error = LogEntry::new(LogLevel::Error, "读取失败 <a.xlsx>".to_string());
+        let info = LogEntry::new(LogLevel::Info, "开始处理".to_string());
+
+        let html = to_html(&[&info, &error]);
+
+        let error_line = html
+            .lines()
+            .find(|line| line.contains("[ERROR]"))
+            .expect("error line missing");
+        assert!(error_line.contains("color: #f44336"));
+        assert!(error_line.contains("读取失败 &lt;a.xlsx&gt;"));
+
+        let info_line = html.lines().find(|line| line.contains("[INFO]")).unwrap();
+        assert!(info_line.contains("color: #2196f3"));
+    }
+}
@@ -1,9 +1,12 @@
 // UI 模块 - 负责所有界面渲染
+pub mod a11y;
 pub mod home;
 pub mod processing;
 pub mod settings;
 pub mod history;
 pub mod log_viewer;
+pub mod open_file;
+pub mod summary_dialog;
 
 pub use log_viewer::LogViewer;
 
@@ -83,28 +86,14 @@ pub fn render_top_panel(app: &mut IntegratedPowerApp, ctx: &egui::Context) {
                         crate::config::Theme::System => "💻",
                     };
                     
-                    if ui
-                        .add_sized(
-                            egui::vec2(40.0, 32.0),
-                            egui::Button::new(egui::RichText::new(theme_icon).size(18.0)),
-                        )
-                        .on_hover_text("切换主题")
-                        .clicked()
-                    {
+                    if a11y::icon_button(ui, theme_icon, a11y::ToolbarAction::ToggleTheme).clicked() {
                         app.toggle_theme();
                     }
                     
                     ui.add_space(5.0);
                     
                     // 日志查看器按钮
-                    if ui
-                        .add_sized(
-                            egui::vec2(40.0, 32.0),
-                            egui::Button::new(egui::RichText::new("📋").size(18.0)),
-                        )
-                        .on_hover_text("查看日志")
-                        .clicked()
-                    {
+                    if a11y::icon_button(ui, "📋", a11y::ToolbarAction::ToggleLogViewer).clicked() {
                         app.log_viewer.show = !app.log_viewer.show;
                     }
                     
@@ -114,6 +103,34 @@ pub fn render_top_panel(app: &mut IntegratedPowerApp, ctx: &egui::Context) {
         });
 }
 
+/// 各行类型占比的横向堆叠条，悬停显示明细
+fn render_breakdown_bar(ui: &mut egui::Ui, series: &[(String, usize, egui::Color32)], total: usize) {
+    if total == 0 {
+        return;
+    }
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(120.0, 8.0), egui::Sense::hover());
+    let painter = ui.painter();
+    let mut left = rect.left();
+    for (_, count, color) in series {
+        let width = rect.width() * *count as f32 / total as f32;
+        let segment = egui::Rect::from_min_max(
+            egui::pos2(left, rect.top()),
+            egui::pos2(left + width, rect.bottom()),
+        );
+        painter.rect_filled(segment, 0.0, *color);
+        left += width;
+    }
+
+    response.on_hover_ui(|ui| {
+        for (name, count, color) in series {
+            ui.horizontal(|ui| {
+                ui.colored_label(*color, "■");
+                ui.label(format!("{}: {} 行 ({})", name, count, crate::models::format_ratio(*count, total)));
+            });
+        }
+    });
+}
+
 // 渲染底部面板
 pub fn render_bottom_panel(app: &IntegratedPowerApp, ctx: &egui::Context) {
     egui::TopBottomPanel::bottom("bottom_panel")
@@ -140,6 +157,25 @@ pub fn render_bottom_panel(app: &IntegratedPowerApp, ctx: &egui::Context) {
 
                 ui.label(egui::RichText::new(status_text).color(status_color).size(14.0));
 
+                // 最近一次识别的成功率
+                if let Some(stats) = &app.last_identification_stats {
+                    let health = RecognitionHealth::from_rate(stats.success_rate);
+                    ui.add_space(12.0);
+                    ui.label(
+                        egui::RichText::new(format!("◆ 识别率 {}", crate::models::format_percentage(stats.success_rate * 100.0)))
+                            .color(health.color())
+                            .size(14.0),
+                    )
+                    .on_hover_text(format!(
+                        "{}：共 {} 行，未识别 {} 行，按默认类型归类 {} 行",
+                        health.label(),
+                        stats.total_rows,
+                        stats.unknown_count,
+                        stats.defaulted_count
+                    ));
+                    render_breakdown_bar(ui, &stats.chart_series(), stats.total_rows);
+                }
+
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     // 从当前选中的处理器配置中获取输入路径
                     let input_path = app
@@ -189,6 +225,45 @@ pub fn render_bottom_panel(app: &IntegratedPowerApp, ctx: &egui::Context) {
         });
 }
 
+/// 识别质量等级，按识别成功率划分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecognitionHealth {
+    /// 成功率 ≥ 95%
+    Good,
+    /// 成功率 ≥ 80%
+    Fair,
+    /// 成功率 < 80%
+    Poor,
+}
+
+impl RecognitionHealth {
+    pub fn from_rate(success_rate: f32) -> Self {
+        if success_rate >= 0.95 {
+            Self::Good
+        } else if success_rate >= 0.8 {
+            Self::Fair
+        } else {
+            Self::Poor
+        }
+    }
+
+    pub fn color(&self) -> egui::Color32 {
+        match self {
+            Self::Good => egui::Color32::from_rgb(76, 175, 80),
+            Self::Fair => egui::Color32::from_rgb(255, 193, 7),
+            Self::Poor => egui::Color32::from_rgb(244, 67, 54),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Good => "识别质量良好",
+            Self::Fair => "部分行未识别",
+            Self::Poor => "大量行未识别，请检查配置",
+        }
+    }
+}
+
 // 渲染中央面板
 pub fn render_central_panel(app: &mut IntegratedPowerApp, ctx: &egui::Context) {
     egui::CentralPanel::default().show(ctx, |ui| {
@@ -200,3 +275,21 @@ pub fn render_central_panel(app: &mut IntegratedPowerApp, ctx: &egui::Context) {
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recognition_health_thresholds() {
+        assert_eq!(RecognitionHealth::from_rate(1.0), RecognitionHealth::Good);
+        assert_eq!(RecognitionHealth::from_rate(0.95), RecognitionHealth::Good);
+        assert_eq!(RecognitionHealth::from_rate(0.949), RecognitionHealth::Fair);
+        assert_eq!(RecognitionHealth::from_rate(0.8), RecognitionHealth::Fair);
+        assert_eq!(RecognitionHealth::from_rate(0.79), RecognitionHealth::Poor);
+        assert_eq!(RecognitionHealth::from_rate(0.0), RecognitionHealth::Poor);
+
+        assert_eq!(RecognitionHealth::Good.color(), egui::Color32::from_rgb(76, 175, 80));
+        assert_ne!(RecognitionHealth::Fair.color(), RecognitionHealth::Poor.color());
+    }
+}
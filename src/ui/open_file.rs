@@ -0,0 +1,77 @@
+// 用系统默认程序打开文件
+use crate::error::{AppError, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// 构造在指定平台上用默认程序打开文件的命令
+///
+/// `os` 取值同 `std::env::consts::OS`。Windows 的 `start` 是 cmd 内置命令，
+/// 需要经 `cmd /C` 调用，第一个空字符串参数是窗口标题，避免带引号的路径被当作标题。
+pub fn open_command(path: &Path, os: &str) -> Command {
+    let mut command = match os {
+        "windows" => {
+            let mut command = Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        }
+        "macos" => Command::new("open"),
+        _ => Command::new("xdg-open"),
+    };
+    command.arg(path);
+    command
+}
+
+/// 用系统默认程序打开文件，文件不存在时返回错误
+pub fn open_in_default_app(path: &Path) -> Result<()> {
+    if !path.is_file() {
+        return Err(AppError::processing_error(format!("文件不存在: {}", path.display())));
+    }
+    open_command(path, std::env::consts::OS).spawn()?;
+    Ok(())
+}
+
+/// 在文件管理器中打开文件夹，文件夹不存在时返回错误
+pub fn open_folder(path: &Path) -> Result<()> {
+    if !path.is_dir() {
+        return Err(AppError::processing_error(format!("文件夹不存在: {}", path.display())));
+    }
+    open_command(path, std::env::consts::OS).spawn()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+    use tempfile::tempdir;
+
+    fn parts(command: &Command) -> (&OsStr, Vec<&OsStr>) {
+        (command.get_program(), command.get_args().collect())
+    }
+
+    #[test]
+    fn test_open_command_per_platform() {
+        let path = Path::new("/data/输出 结果.xlsx");
+
+        let (program, args) = parts(&open_command(path, "windows"));
+        assert_eq!(program, "cmd");
+        assert_eq!(args, vec![OsStr::new("/C"), OsStr::new("start"), OsStr::new(""), path.as_os_str()]);
+
+        let (program, args) = parts(&open_command(path, "macos"));
+        assert_eq!(program, "open");
+        assert_eq!(args, vec![path.as_os_str()]);
+
+        let (program, args) = parts(&open_command(path, "linux"));
+        assert_eq!(program, "xdg-open");
+        assert_eq!(args, vec![path.as_os_str()]);
+    }
+
+    #[test]
+    fn test_open_missing_file_fails() {
+        let dir = tempdir().unwrap();
+        assert!(open_in_default_app(&dir.path().join("missing.xlsx")).is_err());
+        // 目录不是可打开的输出文件
+        assert!(open_in_default_app(dir.path()).is_err());
+        assert!(open_folder(&dir.path().join("missing")).is_err());
+    }
+}
@@ -1,7 +1,7 @@
 // 处理视图
 use crate::app::IntegratedPowerApp;
 
-pub fn render(app: &IntegratedPowerApp, ui: &mut egui::Ui) {
+pub fn render(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
     ui.vertical_centered(|ui| {
         ui.add_space(50.0);
         
@@ -14,7 +14,7 @@ pub fn render(app: &IntegratedPowerApp, ui: &mut egui::Ui) {
         let progress = app.progress.percentage / 100.0;
         ui.add(
             egui::ProgressBar::new(progress)
-                .text(format!("{:.0}%", app.progress.percentage))
+                .text(crate::models::format_percentage(app.progress.percentage))
                 .animate(true),
         );
 
@@ -25,10 +25,84 @@ pub fn render(app: &IntegratedPowerApp, ui: &mut egui::Ui) {
             app.progress.processed_files, app.progress.total_files
         ));
 
+        if !app.recent_outcomes.is_empty() {
+            ui.add_space(15.0);
+            ui.label(egui::RichText::new("最近完成").strong());
+            ui.add_space(5.0);
+
+            for outcome in app.recent_outcomes.iter_latest_first() {
+                let (mark, color) = if outcome.success {
+                    ("✓", egui::Color32::from_rgb(76, 175, 80))
+                } else {
+                    ("✗", egui::Color32::from_rgb(244, 67, 54))
+                };
+                ui.label(egui::RichText::new(format!("{} {}", mark, outcome.file)).color(color));
+            }
+        }
+
+        render_queue(app, ui);
+
         ui.add_space(20.0);
 
-        if ui.button("取消处理").clicked() {
-            // 取消逻辑将在任务 12 中实现
+        if let crate::models::ProcessingState::Processing { cancel } = &app.processing_state {
+            let cancelling = cancel.is_cancelled();
+            let label = if cancelling { "正在取消..." } else { "取消处理" };
+            if ui.add_enabled(!cancelling, egui::Button::new(label)).clicked() {
+                cancel.cancel("用户取消了处理");
+            }
         }
     });
 }
+
+/// 队列中正在执行和等待中的任务，等待中的任务可逐个取消
+pub fn render_queue(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
+    if let Some(running) = app.job_queue.running() {
+        ui.add_space(15.0);
+        ui.horizontal(|ui| {
+            ui.label(format!("当前任务: {}", running.label()));
+            if ui
+                .small_button("📋")
+                .on_hover_text("复制运行 ID，可在日志和历史记录中查找")
+                .clicked()
+            {
+                ui.output_mut(|o| o.copied_text = running.run_id.clone());
+            }
+        });
+        if let Some(progress) = app.job_queue.progress() {
+            ui.add(
+                egui::ProgressBar::new(progress.percentage / 100.0)
+                    .text(format!(
+                        "{}/{} 文件",
+                        progress.processed_files, progress.total_files
+                    ))
+                    .animate(true),
+            );
+        }
+    }
+
+    let pending: Vec<(u64, String)> = app.job_queue.pending().map(|job| (job.id, job.label())).collect();
+    if pending.is_empty() {
+        return;
+    }
+
+    ui.add_space(15.0);
+    ui.label(egui::RichText::new(format!("排队中 ({})", pending.len())).strong());
+    if app.job_queue.is_held() && !app.job_queue.is_running() {
+        ui.label(egui::RichText::new("等待当前处理任务完成后开始").weak());
+    }
+    ui.add_space(5.0);
+
+    let mut cancelled = None;
+    for (index, (id, label)) in pending.iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}. {}", index + 1, label));
+            if ui.small_button("✖ 取消").clicked() {
+                cancelled = Some(*id);
+            }
+        });
+    }
+
+    if let Some(id) = cancelled {
+        app.job_queue.cancel(id);
+    }
+}
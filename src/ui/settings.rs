@@ -1,9 +1,175 @@
 // 设置视图
 use crate::app::IntegratedPowerApp;
 
-pub fn render(_app: &IntegratedPowerApp, ui: &mut egui::Ui) {
+pub fn render(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
     ui.add_space(20.0);
     ui.heading("设置");
     ui.add_space(10.0);
-    ui.label("设置界面将在任务 13 中实现");
+
+    render_font_settings(app, ui);
+
+    ui.add_space(10.0);
+    render_auto_save_settings(app, ui);
+
+    ui.add_space(10.0);
+    render_parallel_settings(app, ui);
+
+    ui.add_space(10.0);
+    render_empty_input_settings(app, ui);
+
+    ui.add_space(10.0);
+    render_window_settings(app, ui);
+
+    ui.add_space(10.0);
+    render_verify_output_settings(app, ui);
+
+    ui.add_space(10.0);
+    ui.label("其余设置界面将在任务 13 中实现");
+}
+
+// 自定义中文字体路径
+fn render_font_settings(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
+    ui.label(egui::RichText::new("字体").strong());
+
+    let current = app.config_manager.get_config().custom_font_path.clone();
+    let mut new_path = current.clone();
+
+    ui.horizontal(|ui| {
+        ui.label("自定义中文字体:");
+        match &current {
+            Some(path) => ui.monospace(path.display().to_string()),
+            None => ui.weak("未设置"),
+        };
+
+        if ui.button("📂 选择字体").clicked() {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("字体文件", &["ttf", "otf", "ttc"])
+                .pick_file()
+            {
+                new_path = Some(path);
+            }
+        }
+
+        if current.is_some() && ui.button("清除").clicked() {
+            new_path = None;
+        }
+    });
+    ui.weak("修改后需重启应用生效");
+
+    if new_path != current {
+        let mut config = app.config_manager.get_config().clone();
+        config.custom_font_path = new_path;
+        if let Err(e) = app.config_manager.update_config(config) {
+            app.report_error("保存字体设置失败", e);
+        }
+    }
+}
+
+// 并行处理的内存限制
+fn render_parallel_settings(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
+    ui.label(egui::RichText::new("并行处理").strong());
+
+    let current = app.config_manager.get_config().clone();
+    let mut adaptive = current.adaptive_parallelism;
+    let mut budget = current.memory_budget_mb;
+
+    ui.label(format!("最大并行任务数: {}", current.max_parallel_tasks));
+    ui.checkbox(&mut adaptive, "按内存自动限制并行数");
+    ui.add_enabled_ui(adaptive, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("内存预算:");
+            ui.add(egui::DragValue::new(&mut budget).range(256..=65536).suffix(" MB"));
+        });
+    });
+    ui.weak("根据文件大小估算内存占用，大文件较多时减少同时处理的文件数，避免内存不足");
+
+    if adaptive != current.adaptive_parallelism || budget != current.memory_budget_mb {
+        let mut config = current;
+        config.adaptive_parallelism = adaptive;
+        config.memory_budget_mb = budget;
+        if let Err(e) = app.config_manager.update_config(config) {
+            app.report_error("保存并行设置失败", e);
+        }
+    }
+}
+
+// 输入目录中没有 Excel 文件时的处理方式
+fn render_empty_input_settings(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
+    ui.label(egui::RichText::new("空输入目录").strong());
+
+    let current = app.config_manager.get_config().clone();
+    let mut fail = current.fail_on_empty_input;
+    ui.checkbox(&mut fail, "输入目录中没有 Excel 文件时视为错误");
+    ui.weak("关闭时仅提示“输入目录中没有 Excel 文件”，开启后该任务按失败处理");
+
+    if fail != current.fail_on_empty_input {
+        let mut config = current;
+        config.fail_on_empty_input = fail;
+        if let Err(e) = app.config_manager.update_config(config) {
+            app.report_error("保存设置失败", e);
+        }
+    }
+}
+
+// 记住窗口尺寸和位置
+fn render_window_settings(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
+    ui.label(egui::RichText::new("窗口").strong());
+
+    let current = app.config_manager.get_config().clone();
+    let mut remember = current.remember_window_geometry;
+    ui.checkbox(&mut remember, "记住窗口尺寸和位置");
+    ui.weak("关闭时保存窗口尺寸和位置，下次启动时恢复");
+
+    if remember != current.remember_window_geometry {
+        let mut config = current;
+        config.remember_window_geometry = remember;
+        if !remember {
+            config.window_geometry = None;
+        }
+        if let Err(e) = app.config_manager.update_config(config) {
+            app.report_error("保存设置失败", e);
+        }
+    }
+}
+
+// 写出后校验输出文件
+fn render_verify_output_settings(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
+    ui.label(egui::RichText::new("输出校验").strong());
+
+    let current = app.config_manager.get_config().clone();
+    let mut verify = current.verify_output;
+    ui.checkbox(&mut verify, "写出 Excel 后重新打开校验");
+    ui.weak("确认输出文件能正常打开且行数正确，校验失败的文件按处理失败计");
+
+    if verify != current.verify_output {
+        let mut config = current;
+        config.verify_output = verify;
+        if let Err(e) = app.config_manager.update_config(config) {
+            app.report_error("保存设置失败", e);
+        }
+    }
+}
+
+// 配置和界面状态的自动保存间隔
+fn render_auto_save_settings(app: &mut IntegratedPowerApp, ui: &mut egui::Ui) {
+    ui.label(egui::RichText::new("自动保存").strong());
+
+    let current = app.config_manager.get_config().auto_save_interval_secs;
+    let mut interval = current;
+    ui.horizontal(|ui| {
+        ui.label("保存间隔:");
+        ui.add(egui::DragValue::new(&mut interval).range(0..=3600).suffix(" 秒"));
+        if interval == 0 {
+            ui.weak("已关闭");
+        }
+    });
+    ui.weak("修改处理器配置、选中的处理器或窗口位置后，停止操作该秒数即自动保存，意外退出时尽量少丢失修改");
+
+    if interval != current {
+        let mut config = app.config_manager.get_config().clone();
+        config.auto_save_interval_secs = interval;
+        if let Err(e) = app.config_manager.update_config(config) {
+            app.report_error("保存自动保存设置失败", e);
+        }
+    }
 }
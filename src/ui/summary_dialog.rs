@@ -0,0 +1,190 @@
+// 处理完成摘要对话框
+//
+// 任务完成后弹出，显示文件数、耗时、成功率和失败列表，
+// 可以直接打开输出文件夹或查看日志。
+use crate::app::IntegratedPowerApp;
+use crate::models::{format_percentage, ProcessingResult};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 失败列表中最多显示的条数，其余折叠为一行提示
+const MAX_LISTED_FAILURES: usize = 20;
+
+/// 摘要对话框的显示内容
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessingSummary {
+    /// 对话框标题（处理器名称）
+    pub title: String,
+    pub total_files: usize,
+    pub successful: usize,
+    pub failed: usize,
+    /// 格式化后的耗时
+    pub duration: String,
+    /// 格式化后的成功率
+    pub success_rate: String,
+    /// 失败的文件名及原因
+    pub failures: Vec<(String, String)>,
+    /// 提前中止的原因
+    pub aborted: Option<String>,
+    /// 输出目录
+    pub output_dir: PathBuf,
+}
+
+impl ProcessingSummary {
+    /// 由处理结果构建
+    pub fn from_result(title: impl Into<String>, result: &ProcessingResult, output_dir: &Path) -> Self {
+        let failures = result
+            .errors
+            .iter()
+            .map(|error| {
+                let name = error
+                    .file
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| error.file.display().to_string());
+                (name, error.error_message.clone())
+            })
+            .collect();
+
+        Self {
+            title: title.into(),
+            total_files: result.total_files,
+            successful: result.successful,
+            failed: result.failed,
+            duration: format_duration(result.duration),
+            success_rate: format_percentage(result.success_rate()),
+            failures,
+            aborted: result.aborted.clone(),
+            output_dir: output_dir.to_path_buf(),
+        }
+    }
+
+    /// 未处理的文件数（提前中止时剩余的文件）
+    pub fn skipped(&self) -> usize {
+        self.total_files.saturating_sub(self.successful + self.failed)
+    }
+}
+
+/// 格式化耗时，如 “3.2 秒”、“2 分 5 秒”
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs < 60.0 {
+        format!("{:.1} 秒", secs)
+    } else {
+        let total = duration.as_secs();
+        format!("{} 分 {} 秒", total / 60, total % 60)
+    }
+}
+
+/// 显示摘要对话框（`app.processing_summary` 为 None 时不显示）
+pub fn render(app: &mut IntegratedPowerApp, ctx: &egui::Context) {
+    let Some(summary) = app.processing_summary.clone() else {
+        return;
+    };
+
+    let mut close = false;
+    egui::Window::new(format!("✅ 处理完成 - {}", summary.title))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            egui::Grid::new("processing_summary_grid").num_columns(2).show(ui, |ui| {
+                ui.label("文件总数:");
+                ui.label(summary.total_files.to_string());
+                ui.end_row();
+                ui.label("成功:");
+                ui.label(summary.successful.to_string());
+                ui.end_row();
+                ui.label("失败:");
+                ui.label(summary.failed.to_string());
+                ui.end_row();
+                if summary.skipped() > 0 {
+                    ui.label("未处理:");
+                    ui.label(summary.skipped().to_string());
+                    ui.end_row();
+                }
+                ui.label("成功率:");
+                ui.label(&summary.success_rate);
+                ui.end_row();
+                ui.label("耗时:");
+                ui.label(&summary.duration);
+                ui.end_row();
+            });
+
+            if let Some(reason) = &summary.aborted {
+                ui.add_space(6.0);
+                ui.colored_label(egui::Color32::from_rgb(244, 67, 54), reason);
+            }
+
+            if !summary.failures.is_empty() {
+                ui.add_space(6.0);
+                ui.label(egui::RichText::new("失败的文件").strong());
+                egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    for (file, message) in summary.failures.iter().take(MAX_LISTED_FAILURES) {
+                        ui.label(format!("✗ {}: {}", file, message));
+                    }
+                    if summary.failures.len() > MAX_LISTED_FAILURES {
+                        ui.weak(format!("…… 另有 {} 个文件失败", summary.failures.len() - MAX_LISTED_FAILURES));
+                    }
+                });
+            }
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button("📂 打开输出文件夹").clicked() {
+                    if let Err(e) = super::open_file::open_folder(&summary.output_dir) {
+                        app.report_error("打开输出文件夹失败", e);
+                    }
+                }
+                if ui.button("📋 查看日志").clicked() {
+                    app.log_viewer.show = true;
+                    close = true;
+                }
+                if ui.button("关闭").clicked() {
+                    close = true;
+                }
+            });
+        });
+
+    if close {
+        app.processing_summary = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProcessingError;
+
+    #[test]
+    fn test_summary_from_result() {
+        let mut result = ProcessingResult::new(4);
+        result.add_success();
+        result.add_success();
+        result.add_failure(ProcessingError::new(
+            PathBuf::from("/data/in/b.xlsx"),
+            "处理失败: 模拟失败".to_string(),
+        ));
+        result.set_duration(Duration::from_millis(3200));
+        result.mark_aborted("失败文件过多");
+
+        let summary = ProcessingSummary::from_result("货物分析表处理器", &result, Path::new("/data/out"));
+        assert_eq!(summary.title, "货物分析表处理器");
+        assert_eq!((summary.total_files, summary.successful, summary.failed), (4, 2, 1));
+        assert_eq!(summary.skipped(), 1);
+        assert_eq!(summary.success_rate, "50%");
+        assert_eq!(summary.duration, "3.2 秒");
+        assert_eq!(
+            summary.failures,
+            vec![("b.xlsx".to_string(), "处理失败: 模拟失败".to_string())]
+        );
+        assert_eq!(summary.aborted.as_deref(), Some("失败文件过多"));
+        assert_eq!(summary.output_dir, PathBuf::from("/data/out"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(0)), "0.0 秒");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2 分 5 秒");
+    }
+}